@@ -0,0 +1,834 @@
+//! Encode and decode benchmarks for every message.
+//!
+//! Checked paths (`AnyMessage::decode`, `finish()`, the `bids()`/`offers()` iterators and
+//! `encode_bids`/`encode_offers`) are benchmarked next to the raw generated accessors they wrap,
+//! and price groups additionally against the bulk `read_all_into` decode.
+//! Use `cargo bench --bench codecs -- --save-baseline <name>` and `--baseline <name>` to track
+//! regressions between revisions.
+use com_dev_4fx_sor_codecs_sbe::*;
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+
+const BUFFER_LENGTH: usize = 8192;
+const LEVELS: [usize; 3] = [1, 10, 100];
+
+macro_rules! read_fields {
+    ($decoder:expr, $($field:ident),+) => {
+        $(black_box($decoder.$field());)+
+    };
+}
+
+fn levels(count: usize) -> Vec<PriceLevel> {
+    (0..count)
+        .map(|i| PriceLevel {
+            id: i as u32,
+            price: 1.1 + i as f64 * 0.0001,
+            leaves_qty: 1_000_000.0,
+            transact_time: 1_600_000_000_000_000_000 + i as i64,
+        })
+        .collect()
+}
+
+fn level_increments(count: usize) -> Vec<PriceLevelIncrement> {
+    levels(count)
+        .into_iter()
+        .map(|price_level| PriceLevelIncrement { price_level, update_action: UpdateAction::UPDATE })
+        .collect()
+}
+
+fn encode_new_order_single(buf: &mut [u8], cl_ord_id: u64) -> NewOrderSingleEncoder<'_> {
+    let mut encoder = NewOrderSingleEncoder::default()
+        .wrap(WriteBuf::new(buf), message_header_codec::ENCODED_LENGTH)
+        .header(0)
+        .parent()
+        .unwrap();
+    encoder.cl_ord_id(cl_ord_id);
+    encoder.cl_ord_link_id(0);
+    encoder.instrument_id(1);
+    encoder.target_comp_id(2);
+    encoder.source_comp_id(3);
+    encoder.side(OrderSide::BUY);
+    encoder.order_type(OrderType::LIMIT);
+    encoder.price(1.1025);
+    encoder.order_qty(1_000_000.0);
+    encoder.time_in_force(TimeInForce::IOC);
+    encoder.transact_time(cl_ord_id);
+    encoder.effective_time(0);
+    encoder.expire_time(0);
+    encoder.target_strategy(Strategy::VENUE);
+    encoder.source_strategy(Strategy::TWAP);
+    encoder
+}
+
+fn encode_execution_report(buf: &mut [u8], exec_id: u64) -> usize {
+    let mut encoder = ExecutionReportEncoder::default()
+        .wrap(WriteBuf::new(buf), message_header_codec::ENCODED_LENGTH)
+        .header(0)
+        .parent()
+        .unwrap();
+    encoder.cl_ord_id(exec_id);
+    encoder.cl_ord_link_id(0);
+    encoder.instrument_id(1);
+    encoder.target_comp_id(3);
+    encoder.source_comp_id(2);
+    encoder.side(OrderSide::BUY);
+    encoder.order_type(OrderType::LIMIT);
+    encoder.price(1.1025);
+    encoder.order_qty(1_000_000.0);
+    encoder.time_in_force(TimeInForce::IOC);
+    encoder.transact_time(exec_id);
+    encoder.effective_time(0);
+    encoder.expire_time(0);
+    encoder.target_strategy(Strategy::TWAP);
+    encoder.source_strategy(Strategy::VENUE);
+    encoder.orig_cl_ord_id(u64::MAX);
+    encoder.exec_id(exec_id);
+    encoder.order_id(exec_id);
+    encoder.exec_type(ExecType::PARTIAL_FILL);
+    encoder.order_status(OrderStatus::PARTIALLY_FILLED);
+    encoder.order_rej_reason(OrderRejectReason::NullVal);
+    encoder.last_qty(500_000.0);
+    encoder.last_price(1.1024);
+    encoder.leaves_qty(500_000.0);
+    encoder.cum_qty(500_000.0);
+    encoder.avg_price(1.1024);
+    encoder.text(b"partially filled at venue").unwrap();
+    message_header_codec::ENCODED_LENGTH + encoder.encoded_length()
+}
+
+fn encode_order_cancel_request(buf: &mut [u8], cl_ord_id: u64) -> usize {
+    let mut encoder = OrderCancelRequestEncoder::default()
+        .wrap(WriteBuf::new(buf), message_header_codec::ENCODED_LENGTH)
+        .header(0)
+        .parent()
+        .unwrap();
+    encoder.orig_cl_ord_id(cl_ord_id - 1);
+    encoder.order_id(7);
+    encoder.cl_ord_id(cl_ord_id);
+    encoder.instrument_id(1);
+    encoder.target_comp_id(2);
+    encoder.source_comp_id(3);
+    encoder.target_strategy(Strategy::VENUE);
+    encoder.source_strategy(Strategy::TWAP);
+    encoder.side(OrderSide::BUY);
+    encoder.price(1.1025);
+    encoder.transact_time(cl_ord_id);
+    message_header_codec::ENCODED_LENGTH + encoder.encoded_length()
+}
+
+fn encode_order_cancel_reject(buf: &mut [u8], cl_ord_id: u64) -> usize {
+    let mut encoder = OrderCancelRejectEncoder::default()
+        .wrap(WriteBuf::new(buf), message_header_codec::ENCODED_LENGTH)
+        .header(0)
+        .parent()
+        .unwrap();
+    encoder.orig_cl_ord_id(cl_ord_id - 1);
+    encoder.order_id(7);
+    encoder.cl_ord_id(cl_ord_id);
+    encoder.order_status(OrderStatus::FILLED);
+    encoder.instrument_id(1);
+    encoder.target_comp_id(3);
+    encoder.source_comp_id(2);
+    encoder.target_strategy(Strategy::TWAP);
+    encoder.source_strategy(Strategy::VENUE);
+    encoder.cancel_rej_response_to(CancelRejectResponseTo::ORDER_CANCEL_REQUEST);
+    encoder.cancel_rej_reason(CancelRejectReason::TOO_LATE_TO_CANCEL);
+    encoder.transact_time(cl_ord_id);
+    encoder.text(b"order is no longer working").unwrap();
+    message_header_codec::ENCODED_LENGTH + encoder.encoded_length()
+}
+
+fn encode_last_market_trade(buf: &mut [u8], transact_time: u64) -> usize {
+    let mut encoder = LastMarketTradeEncoder::default()
+        .wrap(WriteBuf::new(buf), message_header_codec::ENCODED_LENGTH)
+        .header(0)
+        .parent()
+        .unwrap();
+    encoder.comp_id(2);
+    encoder.instrument_id(1);
+    encoder.quantity(1_000_000.0);
+    encoder.price(1.1025);
+    encoder.transact_time(transact_time);
+    message_header_codec::ENCODED_LENGTH + encoder.encoded_length()
+}
+
+fn encode_price_snapshot_request(buf: &mut [u8], transact_time: u64) -> usize {
+    let mut encoder = PriceSnapshotRequestEncoder::default()
+        .wrap(WriteBuf::new(buf), message_header_codec::ENCODED_LENGTH)
+        .header(0)
+        .parent()
+        .unwrap();
+    encoder.target_comp_id(2);
+    encoder.source_comp_id(3);
+    encoder.instrument_id(1);
+    encoder.transact_time(transact_time);
+    message_header_codec::ENCODED_LENGTH + encoder.encoded_length()
+}
+
+macro_rules! encode_session_message {
+    ($name:ident, $encoder:ident) => {
+        fn $name(buf: &mut [u8], transact_time: u64) -> usize {
+            let mut encoder = $encoder::default()
+                .wrap(WriteBuf::new(buf), message_header_codec::ENCODED_LENGTH)
+                .header(0)
+                .parent()
+                .unwrap();
+            encoder.target_comp_id(2);
+            encoder.source_comp_id(3);
+            encoder.transact_time(transact_time);
+            message_header_codec::ENCODED_LENGTH + encoder.encoded_length()
+        }
+    };
+}
+
+encode_session_message!(encode_logon_request, LogonRequestEncoder);
+encode_session_message!(encode_logon_response, LogonResponseEncoder);
+encode_session_message!(encode_logout_request, LogoutRequestEncoder);
+encode_session_message!(encode_logout_response, LogoutResponseEncoder);
+
+fn encode_instrument_definition(buf: &mut [u8], transact_time: u64) -> usize {
+    let mut encoder = InstrumentDefinitionEncoder::default()
+        .wrap(WriteBuf::new(buf), message_header_codec::ENCODED_LENGTH)
+        .header(0)
+        .parent()
+        .unwrap();
+    encoder.instrument_id(1);
+    encoder.symbol(&to_char_array(b"EURUSD").unwrap());
+    encoder.base_currency(b"EUR");
+    encoder.term_currency(b"USD");
+    encoder.tick_size(0.00001);
+    encoder.min_qty(1_000.0);
+    encoder.max_qty(50_000_000.0);
+    encoder.price_precision(5);
+    encoder.instrument_status(InstrumentStatus::ACTIVE);
+    encoder.transact_time(transact_time);
+    message_header_codec::ENCODED_LENGTH + encoder.encoded_length()
+}
+
+fn encode_market_data_request(buf: &mut [u8], md_req_id: u64, instrument_ids: &[u32]) -> usize {
+    let mut encoder = MarketDataRequestEncoder::default()
+        .wrap(WriteBuf::new(buf), message_header_codec::ENCODED_LENGTH)
+        .header(0)
+        .parent()
+        .unwrap();
+    encoder.md_req_id(md_req_id);
+    encoder.target_comp_id(2);
+    encoder.source_comp_id(3);
+    encoder.subscription_request_type(SubscriptionRequestType::SNAPSHOT_AND_UPDATES);
+    encoder.market_depth(5);
+    encoder.transact_time(md_req_id);
+    encoder.encode_instruments(instrument_ids.iter().copied()).unwrap();
+    message_header_codec::ENCODED_LENGTH + encoder.encoded_length()
+}
+
+fn encode_market_data_request_reject(buf: &mut [u8], md_req_id: u64) -> usize {
+    let mut encoder = MarketDataRequestRejectEncoder::default()
+        .wrap(WriteBuf::new(buf), message_header_codec::ENCODED_LENGTH)
+        .header(0)
+        .parent()
+        .unwrap();
+    encoder.md_req_id(md_req_id);
+    encoder.target_comp_id(3);
+    encoder.source_comp_id(2);
+    encoder.md_req_rej_reason(MarketDataRequestRejectReason::UNKNOWN_SYMBOL);
+    encoder.transact_time(md_req_id);
+    encoder.text(b"unknown instrument").unwrap();
+    message_header_codec::ENCODED_LENGTH + encoder.encoded_length()
+}
+
+fn encode_market_data_request_ack(buf: &mut [u8], md_req_id: u64) -> usize {
+    let mut encoder = MarketDataRequestAckEncoder::default()
+        .wrap(WriteBuf::new(buf), message_header_codec::ENCODED_LENGTH)
+        .header(0)
+        .parent()
+        .unwrap();
+    encoder.md_req_id(md_req_id);
+    encoder.target_comp_id(3);
+    encoder.source_comp_id(2);
+    encoder.subscription_request_type(SubscriptionRequestType::SNAPSHOT_AND_UPDATES);
+    encoder.transact_time(md_req_id);
+    message_header_codec::ENCODED_LENGTH + encoder.encoded_length()
+}
+
+fn encode_order_status_request(buf: &mut [u8], ord_status_req_id: u64) -> usize {
+    let mut encoder = OrderStatusRequestEncoder::default()
+        .wrap(WriteBuf::new(buf), message_header_codec::ENCODED_LENGTH)
+        .header(0)
+        .parent()
+        .unwrap();
+    encoder.ord_status_req_id(ord_status_req_id);
+    encoder.cl_ord_id(42);
+    encoder.order_id(u64::MAX);
+    encoder.instrument_id(1);
+    encoder.target_comp_id(2);
+    encoder.source_comp_id(3);
+    encoder.target_strategy(Strategy::VENUE);
+    encoder.source_strategy(Strategy::TWAP);
+    encoder.transact_time(ord_status_req_id);
+    message_header_codec::ENCODED_LENGTH + encoder.encoded_length()
+}
+
+fn encode_order_mass_status_request(buf: &mut [u8], mass_status_req_id: u64) -> usize {
+    let mut encoder = OrderMassStatusRequestEncoder::default()
+        .wrap(WriteBuf::new(buf), message_header_codec::ENCODED_LENGTH)
+        .header(0)
+        .parent()
+        .unwrap();
+    encoder.mass_status_req_id(mass_status_req_id);
+    encoder.mass_status_req_type(MassStatusReqType::ALL_ORDERS);
+    encoder.instrument_id(u32::MAX);
+    encoder.strategy(Strategy::NullVal);
+    encoder.target_comp_id(2);
+    encoder.source_comp_id(3);
+    encoder.target_strategy(Strategy::VENUE);
+    encoder.source_strategy(Strategy::TWAP);
+    encoder.transact_time(mass_status_req_id);
+    message_header_codec::ENCODED_LENGTH + encoder.encoded_length()
+}
+
+fn encode_order_mass_cancel_request(buf: &mut [u8], cl_ord_id: u64) -> usize {
+    let mut encoder = OrderMassCancelRequestEncoder::default()
+        .wrap(WriteBuf::new(buf), message_header_codec::ENCODED_LENGTH)
+        .header(0)
+        .parent()
+        .unwrap();
+    encoder.cl_ord_id(cl_ord_id);
+    encoder.mass_cancel_request_type(MassCancelRequestType::INSTRUMENT);
+    encoder.instrument_id(1);
+    encoder.side(OrderSide::NullVal);
+    encoder.strategy(Strategy::NullVal);
+    encoder.target_comp_id(2);
+    encoder.source_comp_id(3);
+    encoder.target_strategy(Strategy::VENUE);
+    encoder.source_strategy(Strategy::TWAP);
+    encoder.transact_time(cl_ord_id);
+    message_header_codec::ENCODED_LENGTH + encoder.encoded_length()
+}
+
+fn encode_order_mass_cancel_report(buf: &mut [u8], cl_ord_id: u64) -> usize {
+    let mut encoder = OrderMassCancelReportEncoder::default()
+        .wrap(WriteBuf::new(buf), message_header_codec::ENCODED_LENGTH)
+        .header(0)
+        .parent()
+        .unwrap();
+    encoder.cl_ord_id(cl_ord_id);
+    encoder.mass_cancel_request_type(MassCancelRequestType::INSTRUMENT);
+    encoder.mass_cancel_response(MassCancelResponse::ACCEPTED);
+    encoder.mass_cancel_reject_reason(MassCancelRejectReason::NullVal);
+    encoder.total_affected_orders(12);
+    encoder.instrument_id(1);
+    encoder.side(OrderSide::NullVal);
+    encoder.strategy(Strategy::NullVal);
+    encoder.target_comp_id(3);
+    encoder.source_comp_id(2);
+    encoder.target_strategy(Strategy::TWAP);
+    encoder.source_strategy(Strategy::VENUE);
+    encoder.transact_time(cl_ord_id);
+    encoder.text(b"").unwrap();
+    message_header_codec::ENCODED_LENGTH + encoder.encoded_length()
+}
+
+fn encode_business_message_reject(buf: &mut [u8], business_reject_ref_id: u64) -> usize {
+    let mut encoder = BusinessMessageRejectEncoder::default()
+        .wrap(WriteBuf::new(buf), message_header_codec::ENCODED_LENGTH)
+        .header(0)
+        .parent()
+        .unwrap();
+    encoder.ref_template_id(new_order_single_codec::SBE_TEMPLATE_ID);
+    encoder.business_reject_ref_id(business_reject_ref_id);
+    encoder.business_reject_reason(BusinessRejectReason::REQUIRED_FIELD_MISSING);
+    encoder.target_comp_id(3);
+    encoder.source_comp_id(2);
+    encoder.transact_time(business_reject_ref_id);
+    encoder.text(b"orderQty").unwrap();
+    message_header_codec::ENCODED_LENGTH + encoder.encoded_length()
+}
+
+fn encode_price_snapshot_checked(buf: &mut [u8], bids: &[PriceLevel], offers: &[PriceLevel]) -> usize {
+    let mut encoder = PriceSnapshotEncoder::default()
+        .wrap(WriteBuf::new(buf), message_header_codec::ENCODED_LENGTH)
+        .header(0)
+        .parent()
+        .unwrap();
+    encoder.comp_id(2);
+    encoder.instrument_id(1);
+    encoder.md_req_id(7);
+    encoder.encode_bids(bids).unwrap();
+    encoder.encode_offers(offers).unwrap();
+    encoder.get_limit()
+}
+
+fn encode_price_snapshot_unchecked(buf: &mut [u8], bids: &[PriceLevel], offers: &[PriceLevel]) -> usize {
+    let mut encoder = PriceSnapshotEncoder::default()
+        .wrap(WriteBuf::new(buf), message_header_codec::ENCODED_LENGTH)
+        .header(0)
+        .parent()
+        .unwrap();
+    encoder.comp_id(2);
+    encoder.instrument_id(1);
+    encoder.md_req_id(7);
+    let mut group = encoder.bids_encoder(bids.len() as u16, Default::default());
+    for level in bids {
+        group.advance().unwrap();
+        let mut entry = group.bid_encoder();
+        entry.copy_from(level);
+        group = entry.parent().unwrap();
+    }
+    let mut group = group.parent().unwrap().offers_encoder(offers.len() as u16, Default::default());
+    for level in offers {
+        group.advance().unwrap();
+        let mut entry = group.offer_encoder();
+        entry.copy_from(level);
+        group = entry.parent().unwrap();
+    }
+    group.parent().unwrap().get_limit()
+}
+
+fn encode_price_increment_checked(
+    buf: &mut [u8],
+    bids: &[PriceLevelIncrement],
+    offers: &[PriceLevelIncrement],
+) -> usize {
+    let mut encoder = PriceIncrementEncoder::default()
+        .wrap(WriteBuf::new(buf), message_header_codec::ENCODED_LENGTH)
+        .header(0)
+        .parent()
+        .unwrap();
+    encoder.comp_id(2);
+    encoder.instrument_id(1);
+    encoder.md_req_id(7);
+    encoder.encode_bids(bids).unwrap();
+    encoder.encode_offers(offers).unwrap();
+    encoder.get_limit()
+}
+
+fn encode_price_increment_unchecked(
+    buf: &mut [u8],
+    bids: &[PriceLevelIncrement],
+    offers: &[PriceLevelIncrement],
+) -> usize {
+    let mut encoder = PriceIncrementEncoder::default()
+        .wrap(WriteBuf::new(buf), message_header_codec::ENCODED_LENGTH)
+        .header(0)
+        .parent()
+        .unwrap();
+    encoder.comp_id(2);
+    encoder.instrument_id(1);
+    encoder.md_req_id(7);
+    let mut group = encoder.bids_encoder(bids.len() as u16, Default::default());
+    for increment in bids {
+        group.advance().unwrap();
+        let mut entry = group.bid_encoder();
+        entry.copy_from(increment);
+        group = entry.parent().unwrap();
+    }
+    let mut group = group.parent().unwrap().offers_encoder(offers.len() as u16, Default::default());
+    for increment in offers {
+        group.advance().unwrap();
+        let mut entry = group.offer_encoder();
+        entry.copy_from(increment);
+        group = entry.parent().unwrap();
+    }
+    group.parent().unwrap().get_limit()
+}
+
+#[inline]
+fn header(bytes: &[u8]) -> MessageHeaderDecoder<ReadBuf<'_>> {
+    MessageHeaderDecoder::default().wrap(ReadBuf::new(bytes), 0)
+}
+
+fn decode_price_snapshot_checked(bytes: &[u8]) {
+    if let Ok(AnyMessage::PriceSnapshot(mut decoder)) = AnyMessage::decode(bytes, 0) {
+        read_fields!(decoder, comp_id, instrument_id, md_req_id);
+        for level in decoder.bids().unwrap() {
+            black_box(level);
+        }
+        for level in decoder.offers().unwrap() {
+            black_box(level);
+        }
+    }
+}
+
+fn decode_price_snapshot_unchecked(bytes: &[u8]) {
+    let decoder = PriceSnapshotDecoder::default().header(header(bytes));
+    read_fields!(decoder, comp_id, instrument_id, md_req_id);
+    let mut group = decoder.bids_decoder();
+    while group.advance().unwrap().is_some() {
+        let mut entry = group.bid_decoder();
+        read_fields!(entry, id, price, leaves_qty, transact_time);
+        group = entry.parent().unwrap();
+    }
+    let mut group = group.parent().unwrap().offers_decoder();
+    while group.advance().unwrap().is_some() {
+        let mut entry = group.offer_decoder();
+        read_fields!(entry, id, price, leaves_qty, transact_time);
+        group = entry.parent().unwrap();
+    }
+}
+
+fn decode_price_snapshot_bulk(bytes: &[u8], levels: &mut [PriceLevel]) {
+    let decoder = PriceSnapshotDecoder::default().header(header(bytes));
+    read_fields!(decoder, comp_id, instrument_id, md_req_id);
+    let mut group = decoder.bids_decoder();
+    black_box(group.read_all_into(levels).unwrap());
+    let mut group = group.parent().unwrap().offers_decoder();
+    black_box(group.read_all_into(levels).unwrap());
+}
+
+fn decode_price_increment_checked(bytes: &[u8]) {
+    if let Ok(AnyMessage::PriceIncrement(mut decoder)) = AnyMessage::decode(bytes, 0) {
+        read_fields!(decoder, comp_id, instrument_id, md_req_id);
+        for increment in decoder.bids().unwrap() {
+            black_box(increment);
+        }
+        for increment in decoder.offers().unwrap() {
+            black_box(increment);
+        }
+    }
+}
+
+fn decode_price_increment_unchecked(bytes: &[u8]) {
+    let decoder = PriceIncrementDecoder::default().header(header(bytes));
+    read_fields!(decoder, comp_id, instrument_id, md_req_id);
+    let mut group = decoder.bids_decoder();
+    while group.advance().unwrap().is_some() {
+        let entry = group.bid_decoder();
+        black_box(entry.update_action());
+        let mut level = entry.price_level_decoder();
+        read_fields!(level, id, price, leaves_qty, transact_time);
+        group = level.parent().unwrap().parent().unwrap();
+    }
+    let mut group = group.parent().unwrap().offers_decoder();
+    while group.advance().unwrap().is_some() {
+        let entry = group.offer_decoder();
+        black_box(entry.update_action());
+        let mut level = entry.price_level_decoder();
+        read_fields!(level, id, price, leaves_qty, transact_time);
+        group = level.parent().unwrap().parent().unwrap();
+    }
+}
+
+fn decode_price_increment_bulk(bytes: &[u8], increments: &mut [PriceLevelIncrement]) {
+    let decoder = PriceIncrementDecoder::default().header(header(bytes));
+    read_fields!(decoder, comp_id, instrument_id, md_req_id);
+    let mut group = decoder.bids_decoder();
+    black_box(group.read_all_into(increments).unwrap());
+    let mut group = group.parent().unwrap().offers_decoder();
+    black_box(group.read_all_into(increments).unwrap());
+}
+
+fn fixed_length_messages(c: &mut Criterion) {
+    let mut buf = vec![0_u8; BUFFER_LENGTH];
+    let mut group = c.benchmark_group("encode");
+    group.bench_function("new_order_single", |b| {
+        b.iter(|| encode_new_order_single(&mut buf, black_box(42)).encoded_length())
+    });
+    group.bench_function("new_order_single/finish", |b| {
+        b.iter(|| encode_new_order_single(&mut buf, black_box(42)).finish().unwrap().0)
+    });
+    group.bench_function("execution_report", |b| b.iter(|| encode_execution_report(&mut buf, black_box(42))));
+    group.bench_function("order_cancel_request", |b| b.iter(|| encode_order_cancel_request(&mut buf, black_box(42))));
+    group.bench_function("order_cancel_reject", |b| b.iter(|| encode_order_cancel_reject(&mut buf, black_box(42))));
+    group.bench_function("last_market_trade", |b| b.iter(|| encode_last_market_trade(&mut buf, black_box(42))));
+    group.bench_function("price_snapshot_request", |b| {
+        b.iter(|| encode_price_snapshot_request(&mut buf, black_box(42)))
+    });
+    group.bench_function("logon_request", |b| b.iter(|| encode_logon_request(&mut buf, black_box(42))));
+    group.bench_function("logon_response", |b| b.iter(|| encode_logon_response(&mut buf, black_box(42))));
+    group.bench_function("logout_request", |b| b.iter(|| encode_logout_request(&mut buf, black_box(42))));
+    group.bench_function("logout_response", |b| b.iter(|| encode_logout_response(&mut buf, black_box(42))));
+    group.bench_function("instrument_definition", |b| {
+        b.iter(|| encode_instrument_definition(&mut buf, black_box(42)))
+    });
+    group.bench_function("market_data_request", |b| {
+        b.iter(|| encode_market_data_request(&mut buf, black_box(42), &[1, 2, 3]))
+    });
+    group.bench_function("market_data_request_reject", |b| {
+        b.iter(|| encode_market_data_request_reject(&mut buf, black_box(42)))
+    });
+    group.bench_function("market_data_request_ack", |b| {
+        b.iter(|| encode_market_data_request_ack(&mut buf, black_box(42)))
+    });
+    group.bench_function("order_status_request", |b| {
+        b.iter(|| encode_order_status_request(&mut buf, black_box(42)))
+    });
+    group.bench_function("order_mass_status_request", |b| {
+        b.iter(|| encode_order_mass_status_request(&mut buf, black_box(42)))
+    });
+    group.bench_function("order_mass_cancel_request", |b| {
+        b.iter(|| encode_order_mass_cancel_request(&mut buf, black_box(42)))
+    });
+    group.bench_function("order_mass_cancel_report", |b| {
+        b.iter(|| encode_order_mass_cancel_report(&mut buf, black_box(42)))
+    });
+    group.bench_function("business_message_reject", |b| {
+        b.iter(|| encode_business_message_reject(&mut buf, black_box(42)))
+    });
+    group.finish();
+
+    let mut group = c.benchmark_group("decode");
+    let length = encode_new_order_single(&mut buf, 42).encoded_length() + message_header_codec::ENCODED_LENGTH;
+    let bytes = &buf[..length];
+    group.bench_function("new_order_single", |b| {
+        b.iter(|| {
+            let decoder = NewOrderSingleDecoder::default().header(header(black_box(bytes)));
+            read_fields!(
+                decoder, cl_ord_id, cl_ord_link_id, instrument_id, target_comp_id, source_comp_id, side,
+                order_type, price, order_qty, time_in_force, transact_time, effective_time, expire_time,
+                target_strategy, source_strategy
+            );
+        })
+    });
+    group.bench_function("new_order_single/any_message", |b| {
+        b.iter(|| {
+            if let Ok(AnyMessage::NewOrderSingle(decoder)) = AnyMessage::decode(black_box(bytes), 0) {
+                read_fields!(
+                    decoder, cl_ord_id, cl_ord_link_id, instrument_id, target_comp_id, source_comp_id, side,
+                    order_type, price, order_qty, time_in_force, transact_time, effective_time, expire_time,
+                    target_strategy, source_strategy
+                );
+            }
+        })
+    });
+
+    let length = encode_execution_report(&mut buf, 42);
+    let bytes = &buf[..length];
+    group.bench_function("execution_report", |b| {
+        b.iter(|| {
+            let mut decoder = ExecutionReportDecoder::default().header(header(black_box(bytes)));
+            read_fields!(
+                decoder, cl_ord_id, cl_ord_link_id, instrument_id, target_comp_id, source_comp_id, side,
+                order_type, price, order_qty, time_in_force, transact_time, effective_time, expire_time,
+                target_strategy, source_strategy, orig_cl_ord_id, exec_id, order_id, exec_type, order_status,
+                order_rej_reason, last_qty, last_price, leaves_qty, cum_qty, avg_price, ord_status_req_id,
+                mass_status_req_id, last_rpt_requested
+            );
+            black_box(decoder.text().unwrap());
+        })
+    });
+
+    let length = encode_order_cancel_request(&mut buf, 42);
+    let bytes = &buf[..length];
+    group.bench_function("order_cancel_request", |b| {
+        b.iter(|| {
+            let decoder = OrderCancelRequestDecoder::default().header(header(black_box(bytes)));
+            read_fields!(
+                decoder, orig_cl_ord_id, order_id, cl_ord_id, instrument_id, target_comp_id, source_comp_id,
+                target_strategy, source_strategy, side, price, transact_time
+            );
+        })
+    });
+
+    let length = encode_order_cancel_reject(&mut buf, 42);
+    let bytes = &buf[..length];
+    group.bench_function("order_cancel_reject", |b| {
+        b.iter(|| {
+            let mut decoder = OrderCancelRejectDecoder::default().header(header(black_box(bytes)));
+            read_fields!(
+                decoder, orig_cl_ord_id, order_id, cl_ord_id, order_status, instrument_id, target_comp_id,
+                source_comp_id, target_strategy, source_strategy, cancel_rej_response_to, cancel_rej_reason,
+                transact_time
+            );
+            black_box(decoder.text().unwrap());
+        })
+    });
+
+    let length = encode_last_market_trade(&mut buf, 42);
+    let bytes = &buf[..length];
+    group.bench_function("last_market_trade", |b| {
+        b.iter(|| {
+            let decoder = LastMarketTradeDecoder::default().header(header(black_box(bytes)));
+            read_fields!(decoder, comp_id, instrument_id, quantity, price, transact_time);
+        })
+    });
+
+    let length = encode_price_snapshot_request(&mut buf, 42);
+    let bytes = &buf[..length];
+    group.bench_function("price_snapshot_request", |b| {
+        b.iter(|| {
+            let decoder = PriceSnapshotRequestDecoder::default().header(header(black_box(bytes)));
+            read_fields!(decoder, target_comp_id, source_comp_id, instrument_id, transact_time);
+        })
+    });
+
+    macro_rules! bench_decode_session_message {
+        ($name:literal, $encode:ident, $decoder:ident) => {
+            let length = $encode(&mut buf, 42);
+            let bytes = &buf[..length];
+            group.bench_function($name, |b| {
+                b.iter(|| {
+                    let decoder = $decoder::default().header(header(black_box(bytes)));
+                    read_fields!(decoder, target_comp_id, source_comp_id, transact_time);
+                })
+            });
+        };
+    }
+    bench_decode_session_message!("logon_request", encode_logon_request, LogonRequestDecoder);
+    bench_decode_session_message!("logon_response", encode_logon_response, LogonResponseDecoder);
+    bench_decode_session_message!("logout_request", encode_logout_request, LogoutRequestDecoder);
+    bench_decode_session_message!("logout_response", encode_logout_response, LogoutResponseDecoder);
+
+    let length = encode_instrument_definition(&mut buf, 42);
+    let bytes = &buf[..length];
+    group.bench_function("instrument_definition", |b| {
+        b.iter(|| {
+            let decoder = InstrumentDefinitionDecoder::default().header(header(black_box(bytes)));
+            read_fields!(
+                decoder, instrument_id, symbol, base_currency, term_currency, tick_size, min_qty, max_qty,
+                price_precision, instrument_status, transact_time
+            );
+        })
+    });
+
+    let length = encode_market_data_request(&mut buf, 42, &[1, 2, 3]);
+    let bytes = &buf[..length];
+    group.bench_function("market_data_request", |b| {
+        b.iter(|| {
+            let mut decoder = MarketDataRequestDecoder::default().header(header(black_box(bytes)));
+            read_fields!(
+                decoder, md_req_id, target_comp_id, source_comp_id, subscription_request_type, market_depth,
+                transact_time
+            );
+            for instrument_id in decoder.instruments().unwrap() {
+                black_box(instrument_id);
+            }
+        })
+    });
+
+    let length = encode_market_data_request_reject(&mut buf, 42);
+    let bytes = &buf[..length];
+    group.bench_function("market_data_request_reject", |b| {
+        b.iter(|| {
+            let mut decoder = MarketDataRequestRejectDecoder::default().header(header(black_box(bytes)));
+            read_fields!(decoder, md_req_id, target_comp_id, source_comp_id, md_req_rej_reason, transact_time);
+            black_box(decoder.text().unwrap());
+        })
+    });
+
+    let length = encode_market_data_request_ack(&mut buf, 42);
+    let bytes = &buf[..length];
+    group.bench_function("market_data_request_ack", |b| {
+        b.iter(|| {
+            let decoder = MarketDataRequestAckDecoder::default().header(header(black_box(bytes)));
+            read_fields!(decoder, md_req_id, target_comp_id, source_comp_id, subscription_request_type, transact_time);
+        })
+    });
+
+    let length = encode_order_status_request(&mut buf, 42);
+    let bytes = &buf[..length];
+    group.bench_function("order_status_request", |b| {
+        b.iter(|| {
+            let decoder = OrderStatusRequestDecoder::default().header(header(black_box(bytes)));
+            read_fields!(
+                decoder, ord_status_req_id, cl_ord_id, order_id, instrument_id, target_comp_id, source_comp_id,
+                target_strategy, source_strategy, transact_time
+            );
+        })
+    });
+
+    let length = encode_order_mass_status_request(&mut buf, 42);
+    let bytes = &buf[..length];
+    group.bench_function("order_mass_status_request", |b| {
+        b.iter(|| {
+            let decoder = OrderMassStatusRequestDecoder::default().header(header(black_box(bytes)));
+            read_fields!(
+                decoder, mass_status_req_id, mass_status_req_type, instrument_id, strategy, target_comp_id,
+                source_comp_id, target_strategy, source_strategy, transact_time
+            );
+        })
+    });
+
+    let length = encode_order_mass_cancel_request(&mut buf, 42);
+    let bytes = &buf[..length];
+    group.bench_function("order_mass_cancel_request", |b| {
+        b.iter(|| {
+            let decoder = OrderMassCancelRequestDecoder::default().header(header(black_box(bytes)));
+            read_fields!(
+                decoder, cl_ord_id, mass_cancel_request_type, instrument_id, side, strategy, target_comp_id,
+                source_comp_id, target_strategy, source_strategy, transact_time
+            );
+        })
+    });
+
+    let length = encode_order_mass_cancel_report(&mut buf, 42);
+    let bytes = &buf[..length];
+    group.bench_function("order_mass_cancel_report", |b| {
+        b.iter(|| {
+            let mut decoder = OrderMassCancelReportDecoder::default().header(header(black_box(bytes)));
+            read_fields!(
+                decoder, cl_ord_id, mass_cancel_request_type, mass_cancel_response, mass_cancel_reject_reason,
+                total_affected_orders, instrument_id, side, strategy, target_comp_id, source_comp_id,
+                target_strategy, source_strategy, transact_time
+            );
+            black_box(decoder.text().unwrap());
+        })
+    });
+
+    let length = encode_business_message_reject(&mut buf, 42);
+    let bytes = &buf[..length];
+    group.bench_function("business_message_reject", |b| {
+        b.iter(|| {
+            let mut decoder = BusinessMessageRejectDecoder::default().header(header(black_box(bytes)));
+            read_fields!(
+                decoder, ref_template_id, business_reject_ref_id, business_reject_reason, target_comp_id,
+                source_comp_id, transact_time
+            );
+            black_box(decoder.text().unwrap());
+        })
+    });
+    group.finish();
+}
+
+fn price_messages(c: &mut Criterion) {
+    let mut buf = vec![0_u8; BUFFER_LENGTH];
+
+    let mut group = c.benchmark_group("price_snapshot");
+    for count in LEVELS {
+        let levels = levels(count);
+        group.bench_with_input(BenchmarkId::new("encode/checked", count), &levels, |b, levels| {
+            b.iter(|| encode_price_snapshot_checked(&mut buf, levels, levels))
+        });
+        group.bench_with_input(BenchmarkId::new("encode/unchecked", count), &levels, |b, levels| {
+            b.iter(|| encode_price_snapshot_unchecked(&mut buf, levels, levels))
+        });
+        let length = encode_price_snapshot_checked(&mut buf, &levels, &levels);
+        let bytes = &buf[..length];
+        group.bench_with_input(BenchmarkId::new("decode/checked", count), bytes, |b, bytes| {
+            b.iter(|| decode_price_snapshot_checked(black_box(bytes)))
+        });
+        group.bench_with_input(BenchmarkId::new("decode/unchecked", count), bytes, |b, bytes| {
+            b.iter(|| decode_price_snapshot_unchecked(black_box(bytes)))
+        });
+        let mut decoded = vec![PriceLevel::default(); count];
+        group.bench_with_input(BenchmarkId::new("decode/bulk", count), bytes, |b, bytes| {
+            b.iter(|| decode_price_snapshot_bulk(black_box(bytes), &mut decoded))
+        });
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("price_increment");
+    for count in LEVELS {
+        let increments = level_increments(count);
+        group.bench_with_input(BenchmarkId::new("encode/checked", count), &increments, |b, increments| {
+            b.iter(|| encode_price_increment_checked(&mut buf, increments, increments))
+        });
+        group.bench_with_input(BenchmarkId::new("encode/unchecked", count), &increments, |b, increments| {
+            b.iter(|| encode_price_increment_unchecked(&mut buf, increments, increments))
+        });
+        let length = encode_price_increment_checked(&mut buf, &increments, &increments);
+        let bytes = &buf[..length];
+        group.bench_with_input(BenchmarkId::new("decode/checked", count), bytes, |b, bytes| {
+            b.iter(|| decode_price_increment_checked(black_box(bytes)))
+        });
+        group.bench_with_input(BenchmarkId::new("decode/unchecked", count), bytes, |b, bytes| {
+            b.iter(|| decode_price_increment_unchecked(black_box(bytes)))
+        });
+        let mut decoded = vec![PriceLevelIncrement::default(); count];
+        group.bench_with_input(BenchmarkId::new("decode/bulk", count), bytes, |b, bytes| {
+            b.iter(|| decode_price_increment_bulk(black_box(bytes), &mut decoded))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, fixed_length_messages, price_messages);
+criterion_main!(benches);
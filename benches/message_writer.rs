@@ -0,0 +1,118 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use com_dev_4fx_sor_codecs_sbe::*;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+/// Counts heap allocations so the benchmark can assert the hot path performs none.
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+fn encode_order(writer: &mut MessageWriter<Vec<u8>>, cl_ord_id: u64) -> usize {
+    writer.reset();
+    writer
+        .new_order_single(|order| {
+            order.cl_ord_id(cl_ord_id);
+            order.cl_ord_link_id(0);
+            order.instrument_id(1);
+            order.target_comp_id(2);
+            order.source_comp_id(3);
+            order.side(OrderSide::BUY);
+            order.order_type(OrderType::LIMIT);
+            order.price(1.1025);
+            order.order_qty(1_000_000.0);
+            order.time_in_force(TimeInForce::IOC);
+            order.transact_time(cl_ord_id);
+            order.effective_time(0);
+            order.expire_time(0);
+            order.target_strategy(Strategy::VENUE);
+            order.source_strategy(Strategy::TWAP);
+        })
+        .expect("buffer large enough")
+        .len()
+}
+
+fn message_writer(c: &mut Criterion) {
+    let mut writer = MessageWriter::with_capacity(1024);
+
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    for cl_ord_id in 0..100_000 {
+        black_box(encode_order(&mut writer, cl_ord_id));
+    }
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - before;
+    assert_eq!(0, allocations, "encoding through MessageWriter allocated");
+
+    let mut cl_ord_id = 0;
+    c.bench_function("message_writer/new_order_single", |b| {
+        b.iter(|| {
+            cl_ord_id += 1;
+            black_box(encode_order(&mut writer, black_box(cl_ord_id)))
+        })
+    });
+}
+
+fn encode_order_and_cancel(packet: &mut PacketEncoder<Vec<u8>>, cl_ord_id: u64) -> usize {
+    packet
+        .message(|writer| {
+            writer.new_order_single(|order| {
+                order.cl_ord_id(cl_ord_id);
+                order.instrument_id(1);
+                order.side(OrderSide::BUY);
+                order.order_type(OrderType::LIMIT);
+                order.price(1.1025);
+                order.order_qty(1_000_000.0);
+                order.time_in_force(TimeInForce::GTC);
+                order.transact_time(cl_ord_id);
+            })
+        })
+        .expect("buffer large enough");
+    packet
+        .message(|writer| {
+            writer.order_cancel_request(|request| {
+                request.orig_cl_ord_id(cl_ord_id);
+                request.cl_ord_id(cl_ord_id + 1);
+                request.instrument_id(1);
+                request.side(OrderSide::BUY);
+                request.transact_time(cl_ord_id);
+            })
+        })
+        .expect("buffer large enough");
+    packet.finish(cl_ord_id).expect("buffer large enough").len()
+}
+
+fn packet_encoder(c: &mut Criterion) {
+    let mut packet = PacketEncoder::with_capacity(1024);
+
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    for cl_ord_id in 0..100_000 {
+        black_box(encode_order_and_cancel(&mut packet, cl_ord_id));
+    }
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - before;
+    assert_eq!(0, allocations, "encoding through PacketEncoder allocated");
+
+    let mut cl_ord_id = 0;
+    c.bench_function("packet_encoder/new_order_single_and_cancel", |b| {
+        b.iter(|| {
+            cl_ord_id += 2;
+            black_box(encode_order_and_cancel(&mut packet, black_box(cl_ord_id)))
+        })
+    });
+}
+
+criterion_group!(benches, message_writer, packet_encoder);
+criterion_main!(benches);
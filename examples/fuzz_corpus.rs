@@ -0,0 +1,338 @@
+//! Writes the seed corpus of the fuzz targets: valid encoded messages, one file each, under
+//! `fuzz/corpus/<target>`. Every seed is also added to the `any_message` dispatch target, price
+//! message seeds to the `price_groups` target, and all of them are packed into the one seed of
+//! the `packet` target.
+use com_dev_4fx_sor_codecs_sbe::*;
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+fn level(id: u32, price: f64) -> PriceLevel {
+    PriceLevel { id, price, leaves_qty: 1_000_000.0, transact_time: 1_600_000_000_000_000_000 }
+}
+
+fn levels(count: usize) -> Vec<PriceLevel> {
+    (0..count).map(|i| level(i as u32, 1.1 + i as f64 * 0.0001)).collect()
+}
+
+fn increments(count: usize) -> Vec<PriceLevelIncrement> {
+    let actions = [UpdateAction::NEW, UpdateAction::UPDATE, UpdateAction::DELETE];
+    levels(count)
+        .into_iter()
+        .zip(actions.iter().cycle())
+        .map(|(price_level, &update_action)| PriceLevelIncrement { price_level, update_action })
+        .collect()
+}
+
+fn seeds() -> SbeResult<Vec<(&'static str, Vec<u8>)>> {
+    let mut writer = MessageWriter::with_capacity(4096);
+    let mut seeds = Vec::new();
+    for &count in &[0, 1, 3] {
+        writer.reset();
+        let bytes = writer.price_snapshot(|snapshot| {
+            snapshot.comp_id(2);
+            snapshot.instrument_id(1);
+            snapshot.md_req_id(1);
+            let _ = snapshot.encode_bids(levels(count));
+            let _ = snapshot.encode_offers(levels(count));
+        })?;
+        seeds.push(("price_snapshot", bytes.to_vec()));
+
+        writer.reset();
+        let bytes = writer.price_increment(|increment| {
+            increment.comp_id(2);
+            increment.instrument_id(1);
+            increment.md_req_id(1);
+            let _ = increment.encode_bids(increments(count));
+            let _ = increment.encode_offers(increments(count));
+        })?;
+        seeds.push(("price_increment", bytes.to_vec()));
+    }
+
+    writer.reset();
+    let bytes = writer.price_snapshot_request(|request| {
+        request.target_comp_id(2);
+        request.source_comp_id(3);
+        request.instrument_id(1);
+        request.transact_time(1);
+    })?;
+    seeds.push(("price_snapshot_request", bytes.to_vec()));
+
+    writer.reset();
+    let bytes = writer.last_market_trade(|trade| {
+        trade.comp_id(2);
+        trade.instrument_id(1);
+        trade.quantity(1_000_000.0);
+        trade.price(1.1025);
+        trade.transact_time(1);
+    })?;
+    seeds.push(("last_market_trade", bytes.to_vec()));
+
+    writer.reset();
+    let bytes = writer.new_order_single(|order| {
+        order.cl_ord_id(1);
+        order.cl_ord_link_id(u64::MAX);
+        order.instrument_id(1);
+        order.target_comp_id(2);
+        order.source_comp_id(3);
+        order.side(OrderSide::BUY);
+        order.order_type(OrderType::LIMIT);
+        order.price(1.1025);
+        order.order_qty(1_000_000.0);
+        order.time_in_force(TimeInForce::GTD);
+        order.transact_time(1);
+        order.effective_time(1);
+        order.expire_time(2);
+        order.target_strategy(Strategy::VENUE);
+        order.source_strategy(Strategy::TWAP);
+    })?;
+    seeds.push(("new_order_single", bytes.to_vec()));
+
+    writer.reset();
+    let bytes = writer.execution_report(|report| {
+        report.cl_ord_id(1);
+        report.cl_ord_link_id(u64::MAX);
+        report.instrument_id(1);
+        report.target_comp_id(3);
+        report.source_comp_id(2);
+        report.side(OrderSide::BUY);
+        report.order_type(OrderType::LIMIT);
+        report.price(1.1025);
+        report.order_qty(1_000_000.0);
+        report.time_in_force(TimeInForce::GTD);
+        report.transact_time(1);
+        report.effective_time(1);
+        report.expire_time(2);
+        report.target_strategy(Strategy::TWAP);
+        report.source_strategy(Strategy::VENUE);
+        report.orig_cl_ord_id(u64::MAX);
+        report.exec_id(1);
+        report.order_id(1);
+        report.exec_type(ExecType::PARTIAL_FILL);
+        report.order_status(OrderStatus::PARTIALLY_FILLED);
+        report.order_rej_reason(OrderRejectReason::NullVal);
+        report.last_qty(500_000.0);
+        report.last_price(1.1024);
+        report.leaves_qty(500_000.0);
+        report.cum_qty(500_000.0);
+        report.avg_price(1.1024);
+        report.text(b"partially filled at venue").expect("writer holds the text");
+    })?;
+    seeds.push(("execution_report", bytes.to_vec()));
+
+    writer.reset();
+    let bytes = writer.order_cancel_request(|request| {
+        request.orig_cl_ord_id(1);
+        request.order_id(1);
+        request.cl_ord_id(2);
+        request.instrument_id(1);
+        request.target_comp_id(2);
+        request.source_comp_id(3);
+        request.target_strategy(Strategy::VENUE);
+        request.source_strategy(Strategy::TWAP);
+        request.side(OrderSide::BUY);
+        request.price(1.1025);
+        request.transact_time(1);
+    })?;
+    seeds.push(("order_cancel_request", bytes.to_vec()));
+
+    writer.reset();
+    let bytes = writer.order_cancel_reject(|reject| {
+        reject.orig_cl_ord_id(1);
+        reject.order_id(1);
+        reject.cl_ord_id(2);
+        reject.order_status(OrderStatus::FILLED);
+        reject.instrument_id(1);
+        reject.target_comp_id(3);
+        reject.source_comp_id(2);
+        reject.target_strategy(Strategy::TWAP);
+        reject.source_strategy(Strategy::VENUE);
+        reject.cancel_rej_response_to(CancelRejectResponseTo::ORDER_CANCEL_REQUEST);
+        reject.cancel_rej_reason(CancelRejectReason::TOO_LATE_TO_CANCEL);
+        reject.transact_time(1);
+        reject.text(b"order is no longer working").expect("writer holds the text");
+    })?;
+    seeds.push(("order_cancel_reject", bytes.to_vec()));
+
+    writer.reset();
+    let bytes = writer.logon_request(|logon| {
+        logon.target_comp_id(2);
+        logon.source_comp_id(3);
+        logon.transact_time(1);
+    })?;
+    seeds.push(("logon_request", bytes.to_vec()));
+
+    writer.reset();
+    let bytes = writer.logon_response(|logon| {
+        logon.target_comp_id(3);
+        logon.source_comp_id(2);
+        logon.transact_time(1);
+    })?;
+    seeds.push(("logon_response", bytes.to_vec()));
+
+    writer.reset();
+    let bytes = writer.logout_request(|logout| {
+        logout.target_comp_id(2);
+        logout.source_comp_id(3);
+        logout.transact_time(1);
+    })?;
+    seeds.push(("logout_request", bytes.to_vec()));
+
+    writer.reset();
+    let bytes = writer.logout_response(|logout| {
+        logout.target_comp_id(3);
+        logout.source_comp_id(2);
+        logout.transact_time(1);
+    })?;
+    seeds.push(("logout_response", bytes.to_vec()));
+
+    writer.reset();
+    let bytes = writer.instrument_definition(|definition| {
+        definition.instrument_id(1);
+        definition.symbol(&to_char_array(b"EURUSD").expect("symbol fits"));
+        definition.base_currency(b"EUR");
+        definition.term_currency(b"USD");
+        definition.tick_size(0.00001);
+        definition.min_qty(1_000.0);
+        definition.max_qty(50_000_000.0);
+        definition.price_precision(5);
+        definition.instrument_status(InstrumentStatus::ACTIVE);
+        definition.transact_time(1);
+    })?;
+    seeds.push(("instrument_definition", bytes.to_vec()));
+
+    for &count in &[0, 1, 3] {
+        writer.reset();
+        let bytes = writer.market_data_request(|request| {
+            request.md_req_id(1);
+            request.target_comp_id(2);
+            request.source_comp_id(3);
+            request.subscription_request_type(SubscriptionRequestType::SNAPSHOT_AND_UPDATES);
+            request.market_depth(5);
+            request.transact_time(1);
+            let _ = request.encode_instruments(1..=count as u32);
+        })?;
+        seeds.push(("market_data_request", bytes.to_vec()));
+    }
+
+    writer.reset();
+    let bytes = writer.market_data_request_reject(|reject| {
+        reject.md_req_id(1);
+        reject.target_comp_id(3);
+        reject.source_comp_id(2);
+        reject.md_req_rej_reason(MarketDataRequestRejectReason::UNKNOWN_SYMBOL);
+        reject.transact_time(1);
+        let _ = reject.text(b"unknown instrument");
+    })?;
+    seeds.push(("market_data_request_reject", bytes.to_vec()));
+
+    writer.reset();
+    let bytes = writer.market_data_request_ack(|ack| {
+        ack.md_req_id(1);
+        ack.target_comp_id(3);
+        ack.source_comp_id(2);
+        ack.subscription_request_type(SubscriptionRequestType::SNAPSHOT_AND_UPDATES);
+        ack.transact_time(1);
+    })?;
+    seeds.push(("market_data_request_ack", bytes.to_vec()));
+
+    writer.reset();
+    let bytes = writer.order_status_request(|request| {
+        request.ord_status_req_id(1);
+        request.cl_ord_id(1);
+        request.order_id(u64::MAX);
+        request.instrument_id(1);
+        request.target_comp_id(2);
+        request.source_comp_id(3);
+        request.target_strategy(Strategy::VENUE);
+        request.source_strategy(Strategy::TWAP);
+        request.transact_time(1);
+    })?;
+    seeds.push(("order_status_request", bytes.to_vec()));
+
+    writer.reset();
+    let bytes = writer.order_mass_status_request(|request| {
+        request.mass_status_req_id(1);
+        request.mass_status_req_type(MassStatusReqType::INSTRUMENT);
+        request.instrument_id(1);
+        request.strategy(Strategy::NullVal);
+        request.target_comp_id(2);
+        request.source_comp_id(3);
+        request.target_strategy(Strategy::VENUE);
+        request.source_strategy(Strategy::TWAP);
+        request.transact_time(1);
+    })?;
+    seeds.push(("order_mass_status_request", bytes.to_vec()));
+
+    writer.reset();
+    let bytes = writer.order_mass_cancel_request(|request| {
+        request.cl_ord_id(1);
+        request.mass_cancel_request_type(MassCancelRequestType::ALL_ORDERS);
+        request.instrument_id(u32::MAX);
+        request.side(OrderSide::NullVal);
+        request.strategy(Strategy::NullVal);
+        request.target_comp_id(2);
+        request.source_comp_id(3);
+        request.target_strategy(Strategy::VENUE);
+        request.source_strategy(Strategy::TWAP);
+        request.transact_time(1);
+    })?;
+    seeds.push(("order_mass_cancel_request", bytes.to_vec()));
+
+    writer.reset();
+    let bytes = writer.order_mass_cancel_report(|report| {
+        report.cl_ord_id(1);
+        report.mass_cancel_request_type(MassCancelRequestType::INSTRUMENT);
+        report.mass_cancel_response(MassCancelResponse::REJECTED);
+        report.mass_cancel_reject_reason(MassCancelRejectReason::UNKNOWN_INSTRUMENT);
+        report.total_affected_orders(0);
+        report.instrument_id(1);
+        report.side(OrderSide::NullVal);
+        report.strategy(Strategy::NullVal);
+        report.target_comp_id(3);
+        report.source_comp_id(2);
+        report.target_strategy(Strategy::TWAP);
+        report.source_strategy(Strategy::VENUE);
+        report.transact_time(1);
+        let _ = report.text(b"no price snapshot for instrument");
+    })?;
+    seeds.push(("order_mass_cancel_report", bytes.to_vec()));
+
+    let report = seeds.last().expect("mass cancel report seeded").1.clone();
+    writer.reset();
+    let bytes = writer.business_message_reject_for(&report, &SbeErr::MissingRequiredField("clOrdId"), |reject| {
+        reject.business_reject_ref_id(1);
+        reject.target_comp_id(3);
+        reject.source_comp_id(2);
+        reject.transact_time(1);
+    })?;
+    seeds.push(("business_message_reject", bytes.to_vec()));
+    Ok(seeds)
+}
+
+fn main() -> io::Result<()> {
+    let corpus = Path::new(env!("CARGO_MANIFEST_DIR")).join("fuzz").join("corpus");
+    let seeds = seeds().map_err(io::Error::other)?;
+    for (index, (target, bytes)) in seeds.iter().enumerate() {
+        let mut dirs = vec![corpus.join(target), corpus.join("any_message")];
+        if *target == "price_snapshot" || *target == "price_increment" {
+            dirs.push(corpus.join("price_groups"));
+        }
+        for dir in dirs.iter() {
+            fs::create_dir_all(dir)?;
+            fs::write(dir.join(format!("seed-{:02}", index)), bytes)?;
+        }
+    }
+
+    let packet_length = packet_header_codec::ENCODED_LENGTH + seeds.iter().map(|(_, bytes)| bytes.len()).sum::<usize>();
+    let mut packet = PacketEncoder::with_capacity(packet_length);
+    for (_, bytes) in seeds.iter() {
+        packet.push(bytes).map_err(io::Error::other)?;
+    }
+    let dir = corpus.join("packet");
+    fs::create_dir_all(&dir)?;
+    fs::write(dir.join("seed-00"), packet.finish(1).map_err(io::Error::other)?)?;
+    println!("wrote {} seeds to {}", seeds.len(), corpus.display());
+    Ok(())
+}
@@ -0,0 +1,27 @@
+//! Prints the messages published to a shared memory ring as they arrive, without consuming them:
+//! `cargo run --example ring_tail -- /dev/shm/<ring>`.
+use com_dev_4fx_sor_codecs_sbe::transport::shm::RingSubscriber;
+
+use std::time::Duration;
+use std::{env, io, thread};
+
+fn main() -> io::Result<()> {
+    let path = env::args_os()
+        .nth(1)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "usage: ring_tail <ring file>"))?;
+    let mut subscriber = RingSubscriber::tail(path)?;
+    loop {
+        let polled = subscriber.poll(|record| match record.message() {
+            Ok(message) => println!(
+                "{}\ttemplate {}\t{} bytes",
+                record.position,
+                message.template_id(),
+                record.bytes().len()
+            ),
+            Err(err) => println!("{}\t{}\t{} bytes", record.position, err, record.bytes().len()),
+        })?;
+        if polled == 0 {
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+}
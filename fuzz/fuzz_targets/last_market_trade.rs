@@ -0,0 +1,5 @@
+#![no_main]
+use com_dev_4fx_sor_codecs_sbe::last_market_trade_codec::SBE_TEMPLATE_ID;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| sor_codecs_fuzz::decode_as(SBE_TEMPLATE_ID, data));
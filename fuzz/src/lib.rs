@@ -0,0 +1,217 @@
+//! Shared bodies of the fuzz targets.
+//!
+//! Every target feeds bytes from the wire through the checked decoding API: [`AnyMessage::decode`]
+//! followed by every field accessor and the group iterators. The `price_groups` target reads the
+//! price groups through the generated group decoders instead: `header()`, `bids_decoder()`,
+//! `advance()`, the entry decoders, `read_all_into` and `parent()`. Any panic is a bug. Run a target
+//! with `cargo +nightly fuzz run <target>` from the repository root; the seed corpus in
+//! `fuzz/corpus/<target>` holds valid encoded messages and is regenerated with
+//! `cargo run --example fuzz_corpus`.
+use com_dev_4fx_sor_codecs_sbe::*;
+
+macro_rules! read_fields {
+    ($decoder:expr, $($field:ident),+) => {
+        {
+            $(let _ = $decoder.$field();)+
+        }
+    };
+}
+
+/// Dispatches on the template id of the message header, as a gateway would.
+pub fn decode(data: &[u8]) {
+    if let Ok(message) = AnyMessage::decode(data, 0) {
+        read_all(message);
+    }
+}
+
+/// Reads `data` as a packet and every message it holds.
+pub fn decode_packet(data: &[u8]) {
+    if let Ok(packet) = PacketDecoder::wrap(data) {
+        let _ = (packet.sequence_number(), packet.sending_time());
+        for message in packet.messages().flatten() {
+            let _ = message.header().template_id();
+            if let Ok(message) = message.message() {
+                read_all(message);
+            }
+        }
+    }
+}
+
+/// Decodes `data` as a message of `template_id`, whatever its header says, so the fuzzer spends
+/// its time on the body of a single message.
+pub fn decode_as(template_id: u16, data: &[u8]) {
+    if data.len() < message_header_codec::ENCODED_LENGTH {
+        return decode(data);
+    }
+    let mut data = data.to_vec();
+    let mut header = MessageHeaderEncoder::<WriteBuf<'_>>::default().wrap(WriteBuf::new(&mut data), 0);
+    header.template_id(template_id);
+    header.schema_id(new_order_single_codec::SBE_SCHEMA_ID);
+    decode(&data);
+}
+
+/// Reads a price message through the generated group decoders, as gateways written before the
+/// group iterators do. Those decoders read the root block unchecked, so it is checked with
+/// [`AnyMessage::decode`] first; the groups are left to the group decoders.
+pub fn decode_price_groups(data: &[u8]) {
+    let header = || MessageHeaderDecoder::default().wrap(ReadBuf::new(data), 0);
+    match AnyMessage::decode(data, 0) {
+        Ok(AnyMessage::PriceSnapshot(_)) => {
+            let decoder = PriceSnapshotDecoder::default().header(header());
+            read_fields!(decoder, comp_id, instrument_id, md_req_id);
+            let mut group = decoder.bids_decoder();
+            let _ = group.count();
+            while let Ok(Some(_)) = group.advance() {
+                let mut entry = group.bid_decoder();
+                read_fields!(entry, id, price, leaves_qty, transact_time);
+                group = match entry.parent() {
+                    Ok(group) => group,
+                    Err(_) => return,
+                };
+            }
+            if let Ok(decoder) = group.parent() {
+                let mut group = decoder.offers_decoder();
+                let mut levels = [PriceLevel::default(); 2];
+                while let Ok(1..) = group.read_all_into(&mut levels) {}
+                let _ = group.parent().map(|decoder| decoder.get_limit());
+            }
+        }
+        Ok(AnyMessage::PriceIncrement(_)) => {
+            let decoder = PriceIncrementDecoder::default().header(header());
+            read_fields!(decoder, comp_id, instrument_id, md_req_id);
+            let mut group = decoder.bids_decoder();
+            let _ = group.count();
+            while let Ok(Some(_)) = group.advance() {
+                let entry = group.bid_decoder();
+                read_fields!(entry, update_action, to_price_level_increment);
+                let mut level = entry.price_level_decoder();
+                read_fields!(level, id, price, leaves_qty, transact_time);
+                group = match level.parent().and_then(|mut entry| entry.parent()) {
+                    Ok(group) => group,
+                    Err(_) => return,
+                };
+            }
+            if let Ok(decoder) = group.parent() {
+                let mut group = decoder.offers_decoder();
+                let mut increments = [PriceLevelIncrement::default(); 2];
+                while let Ok(1..) = group.read_all_into(&mut increments) {}
+                let _ = group.parent().map(|decoder| decoder.get_limit());
+            }
+        }
+        _ => {}
+    }
+}
+
+fn read_all(message: AnyMessage<'_>) {
+    match message {
+        AnyMessage::PriceIncrement(mut decoder) => {
+            read_fields!(decoder, comp_id, instrument_id, md_req_id);
+            if let Ok(bids) = decoder.bids() {
+                bids.for_each(drop);
+                if let Ok(offers) = decoder.offers() {
+                    offers.for_each(drop);
+                }
+            }
+        }
+        AnyMessage::PriceSnapshot(mut decoder) => {
+            read_fields!(decoder, comp_id, instrument_id, md_req_id);
+            if let Ok(bids) = decoder.bids() {
+                bids.for_each(drop);
+                if let Ok(offers) = decoder.offers() {
+                    offers.for_each(drop);
+                }
+            }
+        }
+        AnyMessage::PriceSnapshotRequest(decoder) => {
+            read_fields!(decoder, target_comp_id, source_comp_id, instrument_id, transact_time);
+        }
+        AnyMessage::LastMarketTrade(decoder) => {
+            read_fields!(decoder, comp_id, instrument_id, quantity, price, transact_time);
+        }
+        AnyMessage::NewOrderSingle(decoder) => {
+            read_fields!(
+                decoder, cl_ord_id, cl_ord_link_id, instrument_id, target_comp_id, source_comp_id, side,
+                order_type, price, order_qty, time_in_force, transact_time, effective_time, expire_time,
+                target_strategy, source_strategy
+            );
+        }
+        AnyMessage::ExecutionReport(mut decoder) => {
+            read_fields!(
+                decoder, cl_ord_id, cl_ord_link_id, instrument_id, target_comp_id, source_comp_id, side,
+                order_type, price, order_qty, time_in_force, transact_time, effective_time, expire_time,
+                target_strategy, source_strategy, orig_cl_ord_id, exec_id, order_id, exec_type, order_status,
+                order_rej_reason, last_qty, last_price, leaves_qty, cum_qty, avg_price, ord_status_req_id,
+                mass_status_req_id, last_rpt_requested, text
+            );
+        }
+        AnyMessage::OrderCancelRequest(decoder) => {
+            read_fields!(
+                decoder, orig_cl_ord_id, order_id, cl_ord_id, instrument_id, target_comp_id, source_comp_id,
+                target_strategy, source_strategy, side, price, transact_time
+            );
+        }
+        AnyMessage::OrderCancelReject(mut decoder) => {
+            read_fields!(
+                decoder, orig_cl_ord_id, order_id, cl_ord_id, order_status, instrument_id, target_comp_id,
+                source_comp_id, target_strategy, source_strategy, cancel_rej_response_to, cancel_rej_reason,
+                transact_time, text
+            );
+        }
+        AnyMessage::LogonRequest(decoder) => read_fields!(decoder, target_comp_id, source_comp_id, transact_time),
+        AnyMessage::LogonResponse(decoder) => read_fields!(decoder, target_comp_id, source_comp_id, transact_time),
+        AnyMessage::LogoutRequest(decoder) => read_fields!(decoder, target_comp_id, source_comp_id, transact_time),
+        AnyMessage::LogoutResponse(decoder) => read_fields!(decoder, target_comp_id, source_comp_id, transact_time),
+        AnyMessage::InstrumentDefinition(decoder) => {
+            read_fields!(
+                decoder, instrument_id, symbol, base_currency, term_currency, tick_size, min_qty, max_qty,
+                price_precision, instrument_status, transact_time
+            );
+        }
+        AnyMessage::MarketDataRequest(mut decoder) => {
+            read_fields!(
+                decoder, md_req_id, target_comp_id, source_comp_id, subscription_request_type, market_depth,
+                transact_time
+            );
+            if let Ok(instruments) = decoder.instruments() {
+                instruments.for_each(drop);
+            }
+        }
+        AnyMessage::MarketDataRequestReject(mut decoder) => {
+            read_fields!(decoder, md_req_id, target_comp_id, source_comp_id, md_req_rej_reason, transact_time, text);
+        }
+        AnyMessage::MarketDataRequestAck(decoder) => {
+            read_fields!(decoder, md_req_id, target_comp_id, source_comp_id, subscription_request_type, transact_time);
+        }
+        AnyMessage::OrderStatusRequest(decoder) => {
+            read_fields!(
+                decoder, ord_status_req_id, cl_ord_id, order_id, instrument_id, target_comp_id, source_comp_id,
+                target_strategy, source_strategy, transact_time
+            );
+        }
+        AnyMessage::OrderMassStatusRequest(decoder) => {
+            read_fields!(
+                decoder, mass_status_req_id, mass_status_req_type, instrument_id, strategy, target_comp_id,
+                source_comp_id, target_strategy, source_strategy, transact_time
+            );
+        }
+        AnyMessage::OrderMassCancelRequest(decoder) => {
+            read_fields!(
+                decoder, cl_ord_id, mass_cancel_request_type, instrument_id, side, strategy, target_comp_id,
+                source_comp_id, target_strategy, source_strategy, transact_time
+            );
+        }
+        AnyMessage::OrderMassCancelReport(mut decoder) => {
+            read_fields!(
+                decoder, cl_ord_id, mass_cancel_request_type, mass_cancel_response, mass_cancel_reject_reason,
+                total_affected_orders, instrument_id, side, strategy, target_comp_id, source_comp_id,
+                target_strategy, source_strategy, transact_time, text
+            );
+        }
+        AnyMessage::BusinessMessageReject(mut decoder) => {
+            read_fields!(
+                decoder, ref_template_id, business_reject_ref_id, business_reject_reason, target_comp_id,
+                source_comp_id, transact_time, text
+            );
+        }
+    }
+}
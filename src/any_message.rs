@@ -0,0 +1,255 @@
+use crate::*;
+
+/// Decoder for any message of the schema, selected by the `templateId` of its message header.
+#[derive(Debug)]
+pub enum AnyMessage<'a> {
+    PriceIncrement(PriceIncrementDecoder<'a>),
+    PriceSnapshot(PriceSnapshotDecoder<'a>),
+    PriceSnapshotRequest(PriceSnapshotRequestDecoder<'a>),
+    LastMarketTrade(LastMarketTradeDecoder<'a>),
+    NewOrderSingle(NewOrderSingleDecoder<'a>),
+    ExecutionReport(ExecutionReportDecoder<'a>),
+    OrderCancelRequest(OrderCancelRequestDecoder<'a>),
+    OrderCancelReject(OrderCancelRejectDecoder<'a>),
+    LogonRequest(LogonRequestDecoder<'a>),
+    LogonResponse(LogonResponseDecoder<'a>),
+    LogoutRequest(LogoutRequestDecoder<'a>),
+    LogoutResponse(LogoutResponseDecoder<'a>),
+    InstrumentDefinition(InstrumentDefinitionDecoder<'a>),
+    MarketDataRequest(MarketDataRequestDecoder<'a>),
+    MarketDataRequestReject(MarketDataRequestRejectDecoder<'a>),
+    MarketDataRequestAck(MarketDataRequestAckDecoder<'a>),
+    OrderStatusRequest(OrderStatusRequestDecoder<'a>),
+    OrderMassStatusRequest(OrderMassStatusRequestDecoder<'a>),
+    OrderMassCancelRequest(OrderMassCancelRequestDecoder<'a>),
+    OrderMassCancelReport(OrderMassCancelReportDecoder<'a>),
+    BusinessMessageReject(BusinessMessageRejectDecoder<'a>),
+}
+
+impl<'a> AnyMessage<'a> {
+    /// Reads the message header at `offset` and wraps the matching message decoder right after it.
+    pub fn decode(data: &'a [u8], offset: usize) -> SbeResult<Self> {
+        let body_offset = offset + message_header_codec::ENCODED_LENGTH;
+        if data.len() < body_offset {
+            return Err(SbeErr::BufferTooShort);
+        }
+        let header = MessageHeaderDecoder::default().wrap(ReadBuf::new(data), offset);
+        let block_length = header.block_length();
+        let template_id = header.template_id();
+        let schema_id = header.schema_id();
+        let version = header.version();
+        if schema_id != new_order_single_codec::SBE_SCHEMA_ID {
+            return Err(SbeErr::UnknownSchemaId(schema_id));
+        }
+
+        let buf = ReadBuf::new(data);
+        let message = match template_id {
+            price_increment_codec::SBE_TEMPLATE_ID => Self::PriceIncrement(
+                PriceIncrementDecoder::default().wrap(buf, body_offset, block_length, version),
+            ),
+            price_snapshot_codec::SBE_TEMPLATE_ID => Self::PriceSnapshot(
+                PriceSnapshotDecoder::default().wrap(buf, body_offset, block_length, version),
+            ),
+            price_snapshot_request_codec::SBE_TEMPLATE_ID => Self::PriceSnapshotRequest(
+                PriceSnapshotRequestDecoder::default().wrap(buf, body_offset, block_length, version),
+            ),
+            last_market_trade_codec::SBE_TEMPLATE_ID => Self::LastMarketTrade(
+                LastMarketTradeDecoder::default().wrap(buf, body_offset, block_length, version),
+            ),
+            new_order_single_codec::SBE_TEMPLATE_ID => Self::NewOrderSingle(
+                NewOrderSingleDecoder::default().wrap(buf, body_offset, block_length, version),
+            ),
+            execution_report_codec::SBE_TEMPLATE_ID => Self::ExecutionReport(
+                ExecutionReportDecoder::default().wrap(buf, body_offset, block_length, version),
+            ),
+            order_cancel_request_codec::SBE_TEMPLATE_ID => Self::OrderCancelRequest(
+                OrderCancelRequestDecoder::default().wrap(buf, body_offset, block_length, version),
+            ),
+            order_cancel_reject_codec::SBE_TEMPLATE_ID => Self::OrderCancelReject(
+                OrderCancelRejectDecoder::default().wrap(buf, body_offset, block_length, version),
+            ),
+            logon_request_codec::SBE_TEMPLATE_ID => Self::LogonRequest(
+                LogonRequestDecoder::default().wrap(buf, body_offset, block_length, version),
+            ),
+            logon_response_codec::SBE_TEMPLATE_ID => Self::LogonResponse(
+                LogonResponseDecoder::default().wrap(buf, body_offset, block_length, version),
+            ),
+            logout_request_codec::SBE_TEMPLATE_ID => Self::LogoutRequest(
+                LogoutRequestDecoder::default().wrap(buf, body_offset, block_length, version),
+            ),
+            logout_response_codec::SBE_TEMPLATE_ID => Self::LogoutResponse(
+                LogoutResponseDecoder::default().wrap(buf, body_offset, block_length, version),
+            ),
+            instrument_definition_codec::SBE_TEMPLATE_ID => Self::InstrumentDefinition(
+                InstrumentDefinitionDecoder::default().wrap(buf, body_offset, block_length, version),
+            ),
+            market_data_request_codec::SBE_TEMPLATE_ID => Self::MarketDataRequest(
+                MarketDataRequestDecoder::default().wrap(buf, body_offset, block_length, version),
+            ),
+            market_data_request_reject_codec::SBE_TEMPLATE_ID => Self::MarketDataRequestReject(
+                MarketDataRequestRejectDecoder::default().wrap(buf, body_offset, block_length, version),
+            ),
+            market_data_request_ack_codec::SBE_TEMPLATE_ID => Self::MarketDataRequestAck(
+                MarketDataRequestAckDecoder::default().wrap(buf, body_offset, block_length, version),
+            ),
+            order_status_request_codec::SBE_TEMPLATE_ID => Self::OrderStatusRequest(
+                OrderStatusRequestDecoder::default().wrap(buf, body_offset, block_length, version),
+            ),
+            order_mass_status_request_codec::SBE_TEMPLATE_ID => Self::OrderMassStatusRequest(
+                OrderMassStatusRequestDecoder::default().wrap(buf, body_offset, block_length, version),
+            ),
+            order_mass_cancel_request_codec::SBE_TEMPLATE_ID => Self::OrderMassCancelRequest(
+                OrderMassCancelRequestDecoder::default().wrap(buf, body_offset, block_length, version),
+            ),
+            order_mass_cancel_report_codec::SBE_TEMPLATE_ID => Self::OrderMassCancelReport(
+                OrderMassCancelReportDecoder::default().wrap(buf, body_offset, block_length, version),
+            ),
+            business_message_reject_codec::SBE_TEMPLATE_ID => Self::BusinessMessageReject(
+                BusinessMessageRejectDecoder::default().wrap(buf, body_offset, block_length, version),
+            ),
+            _ => return Err(SbeErr::UnknownTemplateId(template_id)),
+        };
+        // Checked ahead of the buffer length: waiting for more bytes cannot make it valid.
+        if block_length < message.min_block_length() {
+            return Err(SbeErr::BlockLengthTooShort);
+        }
+        if data.len() < body_offset + block_length as usize {
+            return Err(SbeErr::BufferTooShort);
+        }
+        Ok(message)
+    }
+
+    #[inline]
+    pub fn template_id(&self) -> u16 {
+        match self {
+            Self::PriceIncrement(_) => price_increment_codec::SBE_TEMPLATE_ID,
+            Self::PriceSnapshot(_) => price_snapshot_codec::SBE_TEMPLATE_ID,
+            Self::PriceSnapshotRequest(_) => price_snapshot_request_codec::SBE_TEMPLATE_ID,
+            Self::LastMarketTrade(_) => last_market_trade_codec::SBE_TEMPLATE_ID,
+            Self::NewOrderSingle(_) => new_order_single_codec::SBE_TEMPLATE_ID,
+            Self::ExecutionReport(_) => execution_report_codec::SBE_TEMPLATE_ID,
+            Self::OrderCancelRequest(_) => order_cancel_request_codec::SBE_TEMPLATE_ID,
+            Self::OrderCancelReject(_) => order_cancel_reject_codec::SBE_TEMPLATE_ID,
+            Self::LogonRequest(_) => logon_request_codec::SBE_TEMPLATE_ID,
+            Self::LogonResponse(_) => logon_response_codec::SBE_TEMPLATE_ID,
+            Self::LogoutRequest(_) => logout_request_codec::SBE_TEMPLATE_ID,
+            Self::LogoutResponse(_) => logout_response_codec::SBE_TEMPLATE_ID,
+            Self::InstrumentDefinition(_) => instrument_definition_codec::SBE_TEMPLATE_ID,
+            Self::MarketDataRequest(_) => market_data_request_codec::SBE_TEMPLATE_ID,
+            Self::MarketDataRequestReject(_) => market_data_request_reject_codec::SBE_TEMPLATE_ID,
+            Self::MarketDataRequestAck(_) => market_data_request_ack_codec::SBE_TEMPLATE_ID,
+            Self::OrderStatusRequest(_) => order_status_request_codec::SBE_TEMPLATE_ID,
+            Self::OrderMassStatusRequest(_) => order_mass_status_request_codec::SBE_TEMPLATE_ID,
+            Self::OrderMassCancelRequest(_) => order_mass_cancel_request_codec::SBE_TEMPLATE_ID,
+            Self::OrderMassCancelReport(_) => order_mass_cancel_report_codec::SBE_TEMPLATE_ID,
+            Self::BusinessMessageReject(_) => business_message_reject_codec::SBE_TEMPLATE_ID,
+        }
+    }
+
+    /// Fixed block length of the message in the current schema version.
+    #[inline]
+    pub fn sbe_block_length(&self) -> u16 {
+        match self {
+            Self::PriceIncrement(_) => price_increment_codec::SBE_BLOCK_LENGTH,
+            Self::PriceSnapshot(_) => price_snapshot_codec::SBE_BLOCK_LENGTH,
+            Self::PriceSnapshotRequest(_) => price_snapshot_request_codec::SBE_BLOCK_LENGTH,
+            Self::LastMarketTrade(_) => last_market_trade_codec::SBE_BLOCK_LENGTH,
+            Self::NewOrderSingle(_) => new_order_single_codec::SBE_BLOCK_LENGTH,
+            Self::ExecutionReport(_) => execution_report_codec::SBE_BLOCK_LENGTH,
+            Self::OrderCancelRequest(_) => order_cancel_request_codec::SBE_BLOCK_LENGTH,
+            Self::OrderCancelReject(_) => order_cancel_reject_codec::SBE_BLOCK_LENGTH,
+            Self::LogonRequest(_) => logon_request_codec::SBE_BLOCK_LENGTH,
+            Self::LogonResponse(_) => logon_response_codec::SBE_BLOCK_LENGTH,
+            Self::LogoutRequest(_) => logout_request_codec::SBE_BLOCK_LENGTH,
+            Self::LogoutResponse(_) => logout_response_codec::SBE_BLOCK_LENGTH,
+            Self::InstrumentDefinition(_) => instrument_definition_codec::SBE_BLOCK_LENGTH,
+            Self::MarketDataRequest(_) => market_data_request_codec::SBE_BLOCK_LENGTH,
+            Self::MarketDataRequestReject(_) => market_data_request_reject_codec::SBE_BLOCK_LENGTH,
+            Self::MarketDataRequestAck(_) => market_data_request_ack_codec::SBE_BLOCK_LENGTH,
+            Self::OrderStatusRequest(_) => order_status_request_codec::SBE_BLOCK_LENGTH,
+            Self::OrderMassStatusRequest(_) => order_mass_status_request_codec::SBE_BLOCK_LENGTH,
+            Self::OrderMassCancelRequest(_) => order_mass_cancel_request_codec::SBE_BLOCK_LENGTH,
+            Self::OrderMassCancelReport(_) => order_mass_cancel_report_codec::SBE_BLOCK_LENGTH,
+            Self::BusinessMessageReject(_) => business_message_reject_codec::SBE_BLOCK_LENGTH,
+        }
+    }
+
+    /// Smallest block length for the acting version of the message; [`AnyMessage::decode`] rejects
+    /// headers declaring less with [`SbeErr::BlockLengthTooShort`]. Senders predating a field added
+    /// to the block send a shorter one.
+    #[inline]
+    pub fn min_block_length(&self) -> u16 {
+        match self {
+            Self::PriceIncrement(decoder)
+                if decoder.acting_version < price_increment_codec::MD_REQ_ID_SINCE_VERSION =>
+            {
+                price_increment_codec::BLOCK_LENGTH_BEFORE_MD_REQ_ID
+            }
+            Self::PriceSnapshot(decoder)
+                if decoder.acting_version < price_snapshot_codec::MD_REQ_ID_SINCE_VERSION =>
+            {
+                price_snapshot_codec::BLOCK_LENGTH_BEFORE_MD_REQ_ID
+            }
+            Self::ExecutionReport(decoder)
+                if decoder.acting_version < execution_report_codec::STATUS_REQ_SINCE_VERSION =>
+            {
+                execution_report_codec::BLOCK_LENGTH_BEFORE_STATUS_REQ
+            }
+            _ => self.sbe_block_length(),
+        }
+    }
+
+    /// Offset just past the message: its block, every group and every var data field. Walks the
+    /// groups and var data to find it, so a message cut short inside them is `BufferTooShort`.
+    pub fn end_offset(self) -> SbeResult<usize> {
+        Ok(match self {
+            Self::PriceIncrement(mut decoder) => {
+                decoder.bids()?;
+                decoder.offers()?;
+                decoder.get_limit()
+            }
+            Self::PriceSnapshot(mut decoder) => {
+                decoder.bids()?;
+                decoder.offers()?;
+                decoder.get_limit()
+            }
+            Self::MarketDataRequest(mut decoder) => {
+                decoder.instruments()?;
+                decoder.get_limit()
+            }
+            Self::ExecutionReport(mut decoder) => {
+                decoder.text()?;
+                decoder.get_limit()
+            }
+            Self::OrderCancelReject(mut decoder) => {
+                decoder.text()?;
+                decoder.get_limit()
+            }
+            Self::MarketDataRequestReject(mut decoder) => {
+                decoder.text()?;
+                decoder.get_limit()
+            }
+            Self::OrderMassCancelReport(mut decoder) => {
+                decoder.text()?;
+                decoder.get_limit()
+            }
+            Self::BusinessMessageReject(mut decoder) => {
+                decoder.text()?;
+                decoder.get_limit()
+            }
+            Self::PriceSnapshotRequest(decoder) => decoder.get_limit(),
+            Self::LastMarketTrade(decoder) => decoder.get_limit(),
+            Self::NewOrderSingle(decoder) => decoder.get_limit(),
+            Self::OrderCancelRequest(decoder) => decoder.get_limit(),
+            Self::LogonRequest(decoder) => decoder.get_limit(),
+            Self::LogonResponse(decoder) => decoder.get_limit(),
+            Self::LogoutRequest(decoder) => decoder.get_limit(),
+            Self::LogoutResponse(decoder) => decoder.get_limit(),
+            Self::InstrumentDefinition(decoder) => decoder.get_limit(),
+            Self::MarketDataRequestAck(decoder) => decoder.get_limit(),
+            Self::OrderStatusRequest(decoder) => decoder.get_limit(),
+            Self::OrderMassStatusRequest(decoder) => decoder.get_limit(),
+            Self::OrderMassCancelRequest(decoder) => decoder.get_limit(),
+        })
+    }
+}
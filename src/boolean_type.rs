@@ -0,0 +1,21 @@
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(u8)]
+pub enum BooleanType {
+    NO = 78_u8, 
+    YES = 89_u8, 
+    NullVal = 0_u8, 
+}
+impl Default for BooleanType {
+    #[inline]
+    fn default() -> Self { BooleanType::NullVal }
+}
+impl From<u8> for BooleanType {
+    #[inline]
+    fn from(v: u8) -> Self {
+        match v {
+            78_u8 => Self::NO, 
+            89_u8 => Self::YES, 
+            _ => Self::NullVal,
+        }
+    }
+}
@@ -0,0 +1,325 @@
+use crate::*;
+
+pub use encoder::*;
+pub use decoder::*;
+
+pub const SBE_BLOCK_LENGTH: u16 = 27;
+pub const SBE_TEMPLATE_ID: u16 = 23;
+pub const SBE_SCHEMA_ID: u16 = 1;
+pub const SBE_SCHEMA_VERSION: u16 = 4;
+
+/// names of the REQUIRED fields, in the bit order of the encoder's set-field mask
+pub const REQUIRED_FIELDS: &[&str] = &[
+    "refTemplateId",
+    "businessRejectRefId",
+    "businessRejectReason",
+    "targetCompId",
+    "sourceCompId",
+    "transactTime",
+    "text",
+];
+
+pub mod encoder {
+    use super::*;
+
+    #[derive(Debug, Default)]
+    pub struct BusinessMessageRejectEncoder<'a, O: ByteOrder = SchemaByteOrder> {
+        buf: WriteBuf<'a, O>,
+        initial_offset: usize,
+        offset: usize,
+        limit: usize,
+        set_fields: u64,
+    }
+
+    impl<'a, O: ByteOrder> Writer<'a> for BusinessMessageRejectEncoder<'a, O> {
+        type ByteOrder = O;
+
+        #[inline]
+        fn get_buf_mut(&mut self) -> &mut WriteBuf<'a, O> {
+            &mut self.buf
+        }
+    }
+
+    impl<'a, O: ByteOrder> Encoder<'a> for BusinessMessageRejectEncoder<'a, O> {
+        #[inline]
+        fn get_limit(&self) -> usize {
+            self.limit
+        }
+
+        #[inline]
+        fn set_limit(&mut self, limit: usize) {
+            self.limit = limit;
+        }
+    }
+
+    impl<'a, O: ByteOrder> BusinessMessageRejectEncoder<'a, O> {
+        pub fn wrap(mut self, buf: WriteBuf<'a, O>, offset: usize) -> Self {
+            let limit = offset + SBE_BLOCK_LENGTH as usize;
+            self.buf = buf;
+            self.initial_offset = offset;
+            self.offset = offset;
+            self.limit = limit;
+            self.set_fields = 0;
+            self
+        }
+
+        #[inline]
+        pub fn encoded_length(&self) -> usize {
+            self.limit - self.offset
+        }
+
+        pub fn header(self, offset: usize) -> MessageHeaderEncoder<Self> {
+            let mut header = MessageHeaderEncoder::default().wrap(self, offset);
+            header.block_length(SBE_BLOCK_LENGTH);
+            header.template_id(SBE_TEMPLATE_ID);
+            header.schema_id(SBE_SCHEMA_ID);
+            header.version(SBE_SCHEMA_VERSION);
+            header
+        }
+
+        /// Returns the encoded length and the buffer, or the first REQUIRED field that was never set.
+        pub fn finish(self) -> SbeResult<(usize, WriteBuf<'a, O>)> {
+            if let Some(index) = (0..REQUIRED_FIELDS.len()).find(|index| self.set_fields & (1 << index) == 0) {
+                return Err(SbeErr::MissingRequiredField(REQUIRED_FIELDS[index]));
+            }
+            Ok((self.encoded_length(), self.buf))
+        }
+
+        /// primitive field 'refTemplateId'
+        /// - min value: 0
+        /// - max value: 65534
+        /// - null value: 65535
+        /// - characterEncoding: null
+        /// - semanticType: null
+        /// - encodedOffset: 0
+        /// - encodedLength: 2
+        #[inline]
+        pub fn ref_template_id(&mut self, value: u16) {
+            self.set_fields |= 1 << 0;
+            let offset = self.offset;
+            self.get_buf_mut().put_u16_at(offset, value);
+        }
+
+        /// primitive field 'businessRejectRefId'
+        /// - min value: 0
+        /// - max value: -2
+        /// - null value: -1
+        /// - characterEncoding: null
+        /// - semanticType: null
+        /// - encodedOffset: 2
+        /// - encodedLength: 8
+        #[inline]
+        pub fn business_reject_ref_id(&mut self, value: u64) {
+            self.set_fields |= 1 << 1;
+            let offset = self.offset + 2;
+            self.get_buf_mut().put_u64_at(offset, value);
+        }
+
+        /// REQUIRED enum
+        #[inline]
+        pub fn business_reject_reason(&mut self, value: BusinessRejectReason) {
+            self.set_fields |= 1 << 2;
+            let offset = self.offset + 10;
+            self.get_buf_mut().put_u8_at(offset, value as u8)
+        }
+
+        /// primitive field 'targetCompId'
+        /// - min value: 0
+        /// - max value: 4294967294
+        /// - null value: 4294967295
+        /// - characterEncoding: null
+        /// - semanticType: null
+        /// - encodedOffset: 11
+        /// - encodedLength: 4
+        #[inline]
+        pub fn target_comp_id(&mut self, value: u32) {
+            self.set_fields |= 1 << 3;
+            let offset = self.offset + 11;
+            self.get_buf_mut().put_u32_at(offset, value);
+        }
+
+        /// primitive field 'sourceCompId'
+        /// - min value: 0
+        /// - max value: 4294967294
+        /// - null value: 4294967295
+        /// - characterEncoding: null
+        /// - semanticType: null
+        /// - encodedOffset: 15
+        /// - encodedLength: 4
+        #[inline]
+        pub fn source_comp_id(&mut self, value: u32) {
+            self.set_fields |= 1 << 4;
+            let offset = self.offset + 15;
+            self.get_buf_mut().put_u32_at(offset, value);
+        }
+
+        /// primitive field 'transactTime'
+        /// - min value: 0
+        /// - max value: -2
+        /// - null value: -1
+        /// - characterEncoding: null
+        /// - semanticType: null
+        /// - encodedOffset: 19
+        /// - encodedLength: 8
+        #[inline]
+        pub fn transact_time(&mut self, value: u64) {
+            self.set_fields |= 1 << 5;
+            let offset = self.offset + 19;
+            self.get_buf_mut().put_u64_at(offset, value);
+        }
+
+        /// VAR_DATA ENCODER - character encoding: 'UTF-8'; writes at the limit, so it must be called
+        /// after every group, and only once: a second call would append a second field
+        pub fn text(&mut self, value: &[u8]) -> SbeResult<()> {
+            if self.set_fields & (1 << 6) != 0 {
+                return Err(SbeErr::DuplicateField("text"));
+            }
+            let limit = var_string_encoding_codec::encode_var_data(&mut self.buf, self.limit, value)?;
+            self.set_fields |= 1 << 6;
+            self.limit = limit;
+            Ok(())
+        }
+
+        /// Sets 'refTemplateId' from the header of `message`, then the reason and text of `error`;
+        /// the ref id, comp ids and transact time are left to the caller. Writes the 'text' var
+        /// data, so it must be called last.
+        pub fn reject_for(&mut self, message: &[u8], error: &SbeErr) -> SbeResult<()> {
+            let ref_template_id = if message.len() >= message_header_codec::ENCODED_LENGTH {
+                MessageHeaderDecoder::default().wrap(ReadBuf::with_byte_order(message, O::default()), 0).template_id()
+            } else {
+                u16::MAX
+            };
+            let (reason, text) = error.business_reject();
+            self.ref_template_id(ref_template_id);
+            self.business_reject_reason(reason);
+            self.text(text.as_bytes())
+        }
+
+    }
+
+} // end encoder
+
+pub mod decoder {
+    use super::*;
+
+    #[derive(Debug, Default)]
+    pub struct BusinessMessageRejectDecoder<'a, O: ByteOrder = SchemaByteOrder> {
+        buf: ReadBuf<'a, O>,
+        initial_offset: usize,
+        offset: usize,
+        limit: usize,
+        pub acting_block_length: u16,
+        pub acting_version: u16,
+    }
+
+    impl<'a, O: ByteOrder> Reader<'a> for BusinessMessageRejectDecoder<'a, O> {
+        type ByteOrder = O;
+
+        #[inline]
+        fn get_buf(&self) -> &ReadBuf<'a, O> {
+            &self.buf
+        }
+    }
+
+    impl<'a, O: ByteOrder> Decoder<'a> for BusinessMessageRejectDecoder<'a, O> {
+        #[inline]
+        fn get_limit(&self) -> usize {
+            self.limit
+        }
+
+        #[inline]
+        fn set_limit(&mut self, limit: usize) {
+            self.limit = limit;
+        }
+    }
+
+    impl<'a, O: ByteOrder> BusinessMessageRejectDecoder<'a, O> {
+        pub fn wrap(
+            mut self,
+            buf: ReadBuf<'a, O>,
+            offset: usize,
+            acting_block_length: u16,
+            acting_version: u16,
+        ) -> Self {
+            let limit = offset + acting_block_length as usize;
+            self.buf = buf;
+            self.initial_offset = offset;
+            self.offset = offset;
+            self.limit = limit;
+            self.acting_block_length = acting_block_length;
+            self.acting_version = acting_version;
+            self
+        }
+
+        #[inline]
+        pub fn encoded_length(&self) -> usize {
+            self.limit - self.offset
+        }
+
+        pub fn header(self, mut header: MessageHeaderDecoder<ReadBuf<'a, O>>) -> Self {
+            debug_assert_eq!(SBE_TEMPLATE_ID, header.template_id());
+            let acting_block_length = header.block_length();
+            let acting_version = header.version();
+
+            self.wrap(
+                header.parent().unwrap(),
+                message_header_codec::ENCODED_LENGTH,
+                acting_block_length,
+                acting_version,
+            )
+        }
+
+        /// primitive field - 'REQUIRED'
+        #[inline]
+        pub fn ref_template_id(&self) -> u16 {
+            self.get_buf().get_u16_at(self.offset)
+        }
+
+        /// primitive field - 'REQUIRED'
+        #[inline]
+        pub fn business_reject_ref_id(&self) -> u64 {
+            self.get_buf().get_u64_at(self.offset + 2)
+        }
+
+        /// REQUIRED enum
+        #[inline]
+        pub fn business_reject_reason(&self) -> BusinessRejectReason {
+            self.get_buf().get_u8_at(self.offset + 10).into()
+        }
+
+        /// primitive field - 'REQUIRED'
+        #[inline]
+        pub fn target_comp_id(&self) -> u32 {
+            self.get_buf().get_u32_at(self.offset + 11)
+        }
+
+        /// primitive field - 'REQUIRED'
+        #[inline]
+        pub fn source_comp_id(&self) -> u32 {
+            self.get_buf().get_u32_at(self.offset + 15)
+        }
+
+        /// primitive field - 'REQUIRED'
+        #[inline]
+        pub fn transact_time(&self) -> u64 {
+            self.get_buf().get_u64_at(self.offset + 19)
+        }
+
+        /// VAR_DATA DECODER - character encoding: 'UTF-8'; reads at the limit and moves it past the
+        /// data, so it must be called after every group
+        pub fn text(&mut self) -> SbeResult<&'a [u8]> {
+            let (offset, length) = var_string_encoding_codec::var_data_extent(&self.buf, self.limit)?;
+            self.limit = offset + length;
+            let data: &'a [u8] = self.buf.data;
+            Ok(&data[offset..offset + length])
+        }
+
+        /// A decoder over the same message with the limit back at the end of the block.
+        pub(crate) fn rewound(&self) -> Self {
+            let buf = ReadBuf::with_byte_order(self.buf.data, O::default());
+            Self::default().wrap(buf, self.offset, self.acting_block_length, self.acting_version)
+        }
+
+    }
+
+} // end decoder
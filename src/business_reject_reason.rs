@@ -0,0 +1,33 @@
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(u8)]
+pub enum BusinessRejectReason {
+    OTHER = 48_u8, 
+    UNKNOWN_ID = 49_u8, 
+    UNKNOWN_INSTRUMENT = 50_u8, 
+    UNSUPPORTED_MESSAGE_TYPE = 51_u8, 
+    APPLICATION_NOT_AVAILABLE = 52_u8, 
+    REQUIRED_FIELD_MISSING = 53_u8, 
+    NOT_AUTHORIZED = 54_u8, 
+    MALFORMED_MESSAGE = 55_u8, 
+    NullVal = 0_u8, 
+}
+impl Default for BusinessRejectReason {
+    #[inline]
+    fn default() -> Self { BusinessRejectReason::NullVal }
+}
+impl From<u8> for BusinessRejectReason {
+    #[inline]
+    fn from(v: u8) -> Self {
+        match v {
+            48_u8 => Self::OTHER, 
+            49_u8 => Self::UNKNOWN_ID, 
+            50_u8 => Self::UNKNOWN_INSTRUMENT, 
+            51_u8 => Self::UNSUPPORTED_MESSAGE_TYPE, 
+            52_u8 => Self::APPLICATION_NOT_AVAILABLE, 
+            53_u8 => Self::REQUIRED_FIELD_MISSING, 
+            54_u8 => Self::NOT_AUTHORIZED, 
+            55_u8 => Self::MALFORMED_MESSAGE, 
+            _ => Self::NullVal,
+        }
+    }
+}
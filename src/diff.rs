@@ -0,0 +1,555 @@
+//! Field-by-field comparison and hashing of decoded messages.
+//!
+//! [`MessageDiff`] is implemented for every message decoder, whatever its byte order, and reads
+//! both sides through their accessors, so nothing is copied into owned structs first. Differences are reported as
+//! [`FieldDiff`]s naming the field as in the schema. Price groups are compared by entry count
+//! and then entry by entry; var data and char arrays are compared as raw bytes.
+use crate::*;
+
+use core::hash::{Hash, Hasher};
+
+/// Value of a single field, as read from a decoder.
+#[derive(Clone, Copy, Debug)]
+pub enum FieldValue<'a> {
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    I64(i64),
+    /// Equal to itself when NaN; `-0.0` equals `0.0`.
+    F64(f64),
+    OrderSide(OrderSide),
+    OrderType(OrderType),
+    TimeInForce(TimeInForce),
+    Strategy(Strategy),
+    ExecType(ExecType),
+    OrderStatus(OrderStatus),
+    OrderRejectReason(OrderRejectReason),
+    CancelRejectReason(CancelRejectReason),
+    CancelRejectResponseTo(CancelRejectResponseTo),
+    UpdateAction(UpdateAction),
+    InstrumentStatus(InstrumentStatus),
+    SubscriptionRequestType(SubscriptionRequestType),
+    MarketDataRequestRejectReason(MarketDataRequestRejectReason),
+    BooleanType(BooleanType),
+    MassStatusReqType(MassStatusReqType),
+    MassCancelRequestType(MassCancelRequestType),
+    MassCancelResponse(MassCancelResponse),
+    MassCancelRejectReason(MassCancelRejectReason),
+    BusinessRejectReason(BusinessRejectReason),
+    Bytes(&'a [u8]),
+}
+
+impl FieldValue<'_> {
+    /// Equality, with f64 values also matching when they are within `f64_tolerance` of each other.
+    #[inline]
+    pub fn matches(&self, other: &Self, f64_tolerance: f64) -> bool {
+        match (self, other) {
+            (Self::F64(a), Self::F64(b)) => self == other || (a - b).abs() <= f64_tolerance,
+            _ => self == other,
+        }
+    }
+}
+
+impl PartialEq for FieldValue<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::U8(a), Self::U8(b)) => a == b,
+            (Self::U16(a), Self::U16(b)) => a == b,
+            (Self::U32(a), Self::U32(b)) => a == b,
+            (Self::U64(a), Self::U64(b)) => a == b,
+            (Self::I64(a), Self::I64(b)) => a == b,
+            (Self::F64(a), Self::F64(b)) => a == b || (a.is_nan() && b.is_nan()),
+            (Self::OrderSide(a), Self::OrderSide(b)) => a == b,
+            (Self::OrderType(a), Self::OrderType(b)) => a == b,
+            (Self::TimeInForce(a), Self::TimeInForce(b)) => a == b,
+            (Self::Strategy(a), Self::Strategy(b)) => a == b,
+            (Self::ExecType(a), Self::ExecType(b)) => a == b,
+            (Self::OrderStatus(a), Self::OrderStatus(b)) => a == b,
+            (Self::OrderRejectReason(a), Self::OrderRejectReason(b)) => a == b,
+            (Self::CancelRejectReason(a), Self::CancelRejectReason(b)) => a == b,
+            (Self::CancelRejectResponseTo(a), Self::CancelRejectResponseTo(b)) => a == b,
+            (Self::UpdateAction(a), Self::UpdateAction(b)) => a == b,
+            (Self::InstrumentStatus(a), Self::InstrumentStatus(b)) => a == b,
+            (Self::SubscriptionRequestType(a), Self::SubscriptionRequestType(b)) => a == b,
+            (Self::MarketDataRequestRejectReason(a), Self::MarketDataRequestRejectReason(b)) => a == b,
+            (Self::BooleanType(a), Self::BooleanType(b)) => a == b,
+            (Self::MassStatusReqType(a), Self::MassStatusReqType(b)) => a == b,
+            (Self::MassCancelRequestType(a), Self::MassCancelRequestType(b)) => a == b,
+            (Self::MassCancelResponse(a), Self::MassCancelResponse(b)) => a == b,
+            (Self::MassCancelRejectReason(a), Self::MassCancelRejectReason(b)) => a == b,
+            (Self::BusinessRejectReason(a), Self::BusinessRejectReason(b)) => a == b,
+            (Self::Bytes(a), Self::Bytes(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for FieldValue<'_> {}
+
+impl Hash for FieldValue<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        core::mem::discriminant(self).hash(state);
+        match self {
+            Self::U8(v) => v.hash(state),
+            Self::U16(v) => v.hash(state),
+            Self::U32(v) => v.hash(state),
+            Self::U64(v) => v.hash(state),
+            Self::I64(v) => v.hash(state),
+            Self::F64(v) => {
+                let canonical = if v.is_nan() { f64::NAN } else if *v == 0.0 { 0.0 } else { *v };
+                canonical.to_bits().hash(state)
+            }
+            Self::OrderSide(v) => v.hash(state),
+            Self::OrderType(v) => v.hash(state),
+            Self::TimeInForce(v) => v.hash(state),
+            Self::Strategy(v) => v.hash(state),
+            Self::ExecType(v) => v.hash(state),
+            Self::OrderStatus(v) => v.hash(state),
+            Self::OrderRejectReason(v) => v.hash(state),
+            Self::CancelRejectReason(v) => v.hash(state),
+            Self::CancelRejectResponseTo(v) => v.hash(state),
+            Self::UpdateAction(v) => v.hash(state),
+            Self::InstrumentStatus(v) => v.hash(state),
+            Self::SubscriptionRequestType(v) => v.hash(state),
+            Self::MarketDataRequestRejectReason(v) => v.hash(state),
+            Self::BooleanType(v) => v.hash(state),
+            Self::MassStatusReqType(v) => v.hash(state),
+            Self::MassCancelRequestType(v) => v.hash(state),
+            Self::MassCancelResponse(v) => v.hash(state),
+            Self::MassCancelRejectReason(v) => v.hash(state),
+            Self::BusinessRejectReason(v) => v.hash(state),
+            Self::Bytes(v) => v.hash(state),
+        }
+    }
+}
+
+macro_rules! field_value_from {
+    ($($ty:ident => $variant:ident),+ $(,)?) => {
+        $(
+            impl From<$ty> for FieldValue<'_> {
+                #[inline]
+                fn from(v: $ty) -> Self {
+                    Self::$variant(v)
+                }
+            }
+        )+
+    };
+}
+
+field_value_from! {
+    u8 => U8,
+    u16 => U16,
+    u32 => U32,
+    u64 => U64,
+    i64 => I64,
+    f64 => F64,
+    OrderSide => OrderSide,
+    OrderType => OrderType,
+    TimeInForce => TimeInForce,
+    Strategy => Strategy,
+    ExecType => ExecType,
+    OrderStatus => OrderStatus,
+    OrderRejectReason => OrderRejectReason,
+    CancelRejectReason => CancelRejectReason,
+    CancelRejectResponseTo => CancelRejectResponseTo,
+    UpdateAction => UpdateAction,
+    InstrumentStatus => InstrumentStatus,
+    SubscriptionRequestType => SubscriptionRequestType,
+    MarketDataRequestRejectReason => MarketDataRequestRejectReason,
+    BooleanType => BooleanType,
+    MassStatusReqType => MassStatusReqType,
+    MassCancelRequestType => MassCancelRequestType,
+    MassCancelResponse => MassCancelResponse,
+    MassCancelRejectReason => MassCancelRejectReason,
+    BusinessRejectReason => BusinessRejectReason,
+}
+
+impl<'a> From<&'a [u8]> for FieldValue<'a> {
+    #[inline]
+    fn from(v: &'a [u8]) -> Self {
+        Self::Bytes(v)
+    }
+}
+
+impl<'a, const N: usize> From<&'a [u8; N]> for FieldValue<'a> {
+    #[inline]
+    fn from(v: &'a [u8; N]) -> Self {
+        Self::Bytes(v)
+    }
+}
+
+/// A field whose value differs between two messages.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct FieldDiff<'a> {
+    /// Group name and entry index for fields of a group entry; `None` for root block fields and
+    /// for a group's entry count, which is reported under the group name.
+    pub entry: Option<(&'static str, usize)>,
+    pub field: &'static str,
+    pub old: FieldValue<'a>,
+    pub new: FieldValue<'a>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct DiffOptions {
+    f64_tolerance: f64,
+}
+
+impl DiffOptions {
+    /// f64 fields whose values are within `tolerance` of each other are not reported.
+    pub fn f64_tolerance(mut self, tolerance: f64) -> Self {
+        self.f64_tolerance = tolerance;
+        self
+    }
+}
+
+pub trait MessageDiff<'a> {
+    /// Calls `on_diff` for every field of `self` (old) that differs from `other` (new) and
+    /// returns the number of differences.
+    fn diff<F: FnMut(FieldDiff<'a>)>(&self, other: &Self, options: &DiffOptions, on_diff: F) -> SbeResult<usize>;
+
+    /// Feeds every field value to `state`; messages equal without tolerance hash the same.
+    fn hash_fields<H: Hasher>(&self, state: &mut H) -> SbeResult<()>;
+
+    #[inline]
+    fn fields_eq(&self, other: &Self, options: &DiffOptions) -> SbeResult<bool> {
+        Ok(self.diff(other, options, |_| {})? == 0)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn diffs(&self, other: &Self, options: &DiffOptions) -> SbeResult<alloc::vec::Vec<FieldDiff<'a>>> {
+        let mut diffs = alloc::vec::Vec::new();
+        self.diff(other, options, |diff| diffs.push(diff))?;
+        Ok(diffs)
+    }
+}
+
+#[inline]
+fn compare<'a, F: FnMut(FieldDiff<'a>)>(
+    entry: Option<(&'static str, usize)>,
+    field: &'static str,
+    old: FieldValue<'a>,
+    new: FieldValue<'a>,
+    options: &DiffOptions,
+    on_diff: &mut F,
+) -> usize {
+    if old.matches(&new, options.f64_tolerance) {
+        return 0;
+    }
+    on_diff(FieldDiff { entry, field, old, new });
+    1
+}
+
+fn price_level_fields(level: &PriceLevel) -> [(&'static str, FieldValue<'static>); 4] {
+    [
+        ("id", level.id.into()),
+        ("price", level.price.into()),
+        ("leavesQty", level.leaves_qty.into()),
+        ("transactTime", level.transact_time.into()),
+    ]
+}
+
+fn price_level_increment_fields(increment: &PriceLevelIncrement) -> [(&'static str, FieldValue<'static>); 5] {
+    let [id, price, leaves_qty, transact_time] = price_level_fields(&increment.price_level);
+    [id, price, leaves_qty, transact_time, ("updateAction", increment.update_action.into())]
+}
+
+fn instrument_id_fields(instrument_id: &u32) -> [(&'static str, FieldValue<'static>); 1] {
+    [("instrumentId", (*instrument_id).into())]
+}
+
+/// Compares the entry counts of a group, then the entries both sides have.
+fn compare_group<'a, T, I, F, const N: usize>(
+    group: &'static str,
+    old: I,
+    new: I,
+    fields: fn(&T) -> [(&'static str, FieldValue<'static>); N],
+    options: &DiffOptions,
+    on_diff: &mut F,
+) -> usize
+where
+    I: ExactSizeIterator<Item = T>,
+    F: FnMut(FieldDiff<'a>),
+{
+    let mut diffs = compare(None, group, (old.len() as u16).into(), (new.len() as u16).into(), options, on_diff);
+    for (index, (old, new)) in old.zip(new).enumerate() {
+        for ((field, old), (_, new)) in fields(&old).iter().zip(fields(&new).iter()) {
+            diffs += compare(Some((group, index)), field, *old, *new, options, on_diff);
+        }
+    }
+    diffs
+}
+
+fn hash_group<T, I, H, const N: usize>(
+    entries: I,
+    fields: fn(&T) -> [(&'static str, FieldValue<'static>); N],
+    state: &mut H,
+)
+where
+    I: ExactSizeIterator<Item = T>,
+    H: Hasher,
+{
+    entries.len().hash(state);
+    for entry in entries {
+        for (_, value) in fields(&entry).iter() {
+            value.hash(state);
+        }
+    }
+}
+
+macro_rules! message_diff {
+    ($(
+        $decoder:ident { $($name:literal => $field:ident),+ $(,)? }
+        $(groups: $levels:ident)?
+        $(group: $group_name:literal => $group:ident, $entry_fields:ident)?
+        $(var_data: $var_name:literal => $var_data:ident)?;
+    )+) => {
+        $(
+            impl<'a, O: ByteOrder> MessageDiff<'a> for $decoder<'a, O> {
+                fn diff<F: FnMut(FieldDiff<'a>)>(&self, other: &Self, options: &DiffOptions, mut on_diff: F) -> SbeResult<usize> {
+                    let mut diffs = 0;
+                    $(diffs += compare(None, $name, self.$field().into(), other.$field().into(), options, &mut on_diff);)+
+                    $(
+                        let (old_bids, old_offers) = self.groups()?;
+                        let (new_bids, new_offers) = other.groups()?;
+                        diffs += compare_group("bids", old_bids, new_bids, $levels, options, &mut on_diff);
+                        diffs += compare_group("offers", old_offers, new_offers, $levels, options, &mut on_diff);
+                    )?
+                    $(
+                        let (old, new) = (self.rewound().$group()?, other.rewound().$group()?);
+                        diffs += compare_group($group_name, old, new, $entry_fields, options, &mut on_diff);
+                    )?
+                    $(
+                        let (old, new) = (self.rewound().$var_data()?, other.rewound().$var_data()?);
+                        diffs += compare(None, $var_name, old.into(), new.into(), options, &mut on_diff);
+                    )?
+                    Ok(diffs)
+                }
+
+                fn hash_fields<H: Hasher>(&self, state: &mut H) -> SbeResult<()> {
+                    $(FieldValue::from(self.$field()).hash(state);)+
+                    $(
+                        let (bids, offers) = self.groups()?;
+                        hash_group(bids, $levels, state);
+                        hash_group(offers, $levels, state);
+                    )?
+                    $(hash_group(self.rewound().$group()?, $entry_fields, state);)?
+                    $(FieldValue::from(self.rewound().$var_data()?).hash(state);)?
+                    Ok(())
+                }
+            }
+        )+
+    };
+}
+
+message_diff! {
+    PriceIncrementDecoder {
+        "compId" => comp_id,
+        "instrumentId" => instrument_id,
+        "mdReqId" => md_req_id,
+    } groups: price_level_increment_fields;
+    PriceSnapshotDecoder {
+        "compId" => comp_id,
+        "instrumentId" => instrument_id,
+        "mdReqId" => md_req_id,
+    } groups: price_level_fields;
+    PriceSnapshotRequestDecoder {
+        "targetCompId" => target_comp_id,
+        "sourceCompId" => source_comp_id,
+        "instrumentId" => instrument_id,
+        "transactTime" => transact_time,
+    };
+    LastMarketTradeDecoder {
+        "compId" => comp_id,
+        "instrumentId" => instrument_id,
+        "quantity" => quantity,
+        "price" => price,
+        "transactTime" => transact_time,
+    };
+    NewOrderSingleDecoder {
+        "clOrdId" => cl_ord_id,
+        "clOrdLinkId" => cl_ord_link_id,
+        "instrumentId" => instrument_id,
+        "targetCompId" => target_comp_id,
+        "sourceCompId" => source_comp_id,
+        "side" => side,
+        "orderType" => order_type,
+        "price" => price,
+        "orderQty" => order_qty,
+        "timeInForce" => time_in_force,
+        "transactTime" => transact_time,
+        "effectiveTime" => effective_time,
+        "expireTime" => expire_time,
+        "targetStrategy" => target_strategy,
+        "sourceStrategy" => source_strategy,
+    };
+    ExecutionReportDecoder {
+        "clOrdId" => cl_ord_id,
+        "clOrdLinkId" => cl_ord_link_id,
+        "instrumentId" => instrument_id,
+        "targetCompId" => target_comp_id,
+        "sourceCompId" => source_comp_id,
+        "side" => side,
+        "orderType" => order_type,
+        "price" => price,
+        "orderQty" => order_qty,
+        "timeInForce" => time_in_force,
+        "transactTime" => transact_time,
+        "effectiveTime" => effective_time,
+        "expireTime" => expire_time,
+        "targetStrategy" => target_strategy,
+        "sourceStrategy" => source_strategy,
+        "origClOrdId" => orig_cl_ord_id,
+        "execId" => exec_id,
+        "orderId" => order_id,
+        "execType" => exec_type,
+        "orderStatus" => order_status,
+        "orderRejReason" => order_rej_reason,
+        "lastQty" => last_qty,
+        "lastPrice" => last_price,
+        "leavesQty" => leaves_qty,
+        "cumQty" => cum_qty,
+        "avgPrice" => avg_price,
+        "ordStatusReqId" => ord_status_req_id,
+        "massStatusReqId" => mass_status_req_id,
+        "lastRptRequested" => last_rpt_requested,
+    } var_data: "text" => text;
+    OrderCancelRequestDecoder {
+        "origClOrdId" => orig_cl_ord_id,
+        "orderId" => order_id,
+        "clOrdId" => cl_ord_id,
+        "instrumentId" => instrument_id,
+        "targetCompId" => target_comp_id,
+        "sourceCompId" => source_comp_id,
+        "targetStrategy" => target_strategy,
+        "sourceStrategy" => source_strategy,
+        "side" => side,
+        "price" => price,
+        "transactTime" => transact_time,
+    };
+    OrderCancelRejectDecoder {
+        "origClOrdId" => orig_cl_ord_id,
+        "orderId" => order_id,
+        "clOrdId" => cl_ord_id,
+        "orderStatus" => order_status,
+        "instrumentId" => instrument_id,
+        "targetCompId" => target_comp_id,
+        "sourceCompId" => source_comp_id,
+        "targetStrategy" => target_strategy,
+        "sourceStrategy" => source_strategy,
+        "cancelRejResponseTo" => cancel_rej_response_to,
+        "cancelRejReason" => cancel_rej_reason,
+        "transactTime" => transact_time,
+    } var_data: "text" => text;
+    LogonRequestDecoder {
+        "targetCompId" => target_comp_id,
+        "sourceCompId" => source_comp_id,
+        "transactTime" => transact_time,
+    };
+    LogonResponseDecoder {
+        "targetCompId" => target_comp_id,
+        "sourceCompId" => source_comp_id,
+        "transactTime" => transact_time,
+    };
+    LogoutRequestDecoder {
+        "targetCompId" => target_comp_id,
+        "sourceCompId" => source_comp_id,
+        "transactTime" => transact_time,
+    };
+    LogoutResponseDecoder {
+        "targetCompId" => target_comp_id,
+        "sourceCompId" => source_comp_id,
+        "transactTime" => transact_time,
+    };
+    InstrumentDefinitionDecoder {
+        "instrumentId" => instrument_id,
+        "symbol" => symbol,
+        "baseCurrency" => base_currency,
+        "termCurrency" => term_currency,
+        "tickSize" => tick_size,
+        "minQty" => min_qty,
+        "maxQty" => max_qty,
+        "pricePrecision" => price_precision,
+        "instrumentStatus" => instrument_status,
+        "transactTime" => transact_time,
+    };
+    MarketDataRequestDecoder {
+        "mdReqId" => md_req_id,
+        "targetCompId" => target_comp_id,
+        "sourceCompId" => source_comp_id,
+        "subscriptionRequestType" => subscription_request_type,
+        "marketDepth" => market_depth,
+        "transactTime" => transact_time,
+    } group: "instruments" => instruments, instrument_id_fields;
+    MarketDataRequestRejectDecoder {
+        "mdReqId" => md_req_id,
+        "targetCompId" => target_comp_id,
+        "sourceCompId" => source_comp_id,
+        "mdReqRejReason" => md_req_rej_reason,
+        "transactTime" => transact_time,
+    } var_data: "text" => text;
+    MarketDataRequestAckDecoder {
+        "mdReqId" => md_req_id,
+        "targetCompId" => target_comp_id,
+        "sourceCompId" => source_comp_id,
+        "subscriptionRequestType" => subscription_request_type,
+        "transactTime" => transact_time,
+    };
+    OrderStatusRequestDecoder {
+        "ordStatusReqId" => ord_status_req_id,
+        "clOrdId" => cl_ord_id,
+        "orderId" => order_id,
+        "instrumentId" => instrument_id,
+        "targetCompId" => target_comp_id,
+        "sourceCompId" => source_comp_id,
+        "targetStrategy" => target_strategy,
+        "sourceStrategy" => source_strategy,
+        "transactTime" => transact_time,
+    };
+    OrderMassStatusRequestDecoder {
+        "massStatusReqId" => mass_status_req_id,
+        "massStatusReqType" => mass_status_req_type,
+        "instrumentId" => instrument_id,
+        "strategy" => strategy,
+        "targetCompId" => target_comp_id,
+        "sourceCompId" => source_comp_id,
+        "targetStrategy" => target_strategy,
+        "sourceStrategy" => source_strategy,
+        "transactTime" => transact_time,
+    };
+    OrderMassCancelRequestDecoder {
+        "clOrdId" => cl_ord_id,
+        "massCancelRequestType" => mass_cancel_request_type,
+        "instrumentId" => instrument_id,
+        "side" => side,
+        "strategy" => strategy,
+        "targetCompId" => target_comp_id,
+        "sourceCompId" => source_comp_id,
+        "targetStrategy" => target_strategy,
+        "sourceStrategy" => source_strategy,
+        "transactTime" => transact_time,
+    };
+    OrderMassCancelReportDecoder {
+        "clOrdId" => cl_ord_id,
+        "massCancelRequestType" => mass_cancel_request_type,
+        "massCancelResponse" => mass_cancel_response,
+        "massCancelRejectReason" => mass_cancel_reject_reason,
+        "totalAffectedOrders" => total_affected_orders,
+        "instrumentId" => instrument_id,
+        "side" => side,
+        "strategy" => strategy,
+        "targetCompId" => target_comp_id,
+        "sourceCompId" => source_comp_id,
+        "targetStrategy" => target_strategy,
+        "sourceStrategy" => source_strategy,
+        "transactTime" => transact_time,
+    } var_data: "text" => text;
+    BusinessMessageRejectDecoder {
+        "refTemplateId" => ref_template_id,
+        "businessRejectRefId" => business_reject_ref_id,
+        "businessRejectReason" => business_reject_reason,
+        "targetCompId" => target_comp_id,
+        "sourceCompId" => source_comp_id,
+        "transactTime" => transact_time,
+    } var_data: "text" => text;
+}
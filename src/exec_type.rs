@@ -16,6 +16,7 @@ pub enum ExecType {
     EXPIRED = 67_u8, 
     RESTATED = 68_u8, 
     PENDING_REPLACE = 69_u8, 
+    ORDER_STATUS = 73_u8, 
     NullVal = 0_u8, 
 }
 impl Default for ExecType {
@@ -41,6 +42,7 @@ impl From<u8> for ExecType {
             67_u8 => Self::EXPIRED, 
             68_u8 => Self::RESTATED, 
             69_u8 => Self::PENDING_REPLACE, 
+            73_u8 => Self::ORDER_STATUS, 
             _ => Self::NullVal,
         }
     }
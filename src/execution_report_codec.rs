@@ -3,30 +3,70 @@ use crate::*;
 pub use encoder::*;
 pub use decoder::*;
 
-pub const SBE_BLOCK_LENGTH: u16 = 140;
+pub const SBE_BLOCK_LENGTH: u16 = 157;
 pub const SBE_TEMPLATE_ID: u16 = 8;
 pub const SBE_SCHEMA_ID: u16 = 1;
-pub const SBE_SCHEMA_VERSION: u16 = 0;
+pub const SBE_SCHEMA_VERSION: u16 = 4;
+/// schema version from which the message ends with the 'text' var data
+pub const TEXT_SINCE_VERSION: u16 = 2;
+/// schema version from which the root block carries 'ordStatusReqId', 'massStatusReqId' and
+/// 'lastRptRequested'
+pub const STATUS_REQ_SINCE_VERSION: u16 = 4;
+/// block length of senders predating 'ordStatusReqId'
+pub const BLOCK_LENGTH_BEFORE_STATUS_REQ: u16 = 140;
+
+/// names of the REQUIRED fields, in the bit order of the encoder's set-field mask
+pub const REQUIRED_FIELDS: &[&str] = &[
+    "clOrdId",
+    "clOrdLinkId",
+    "instrumentId",
+    "targetCompId",
+    "sourceCompId",
+    "side",
+    "orderType",
+    "price",
+    "orderQty",
+    "timeInForce",
+    "transactTime",
+    "effectiveTime",
+    "expireTime",
+    "targetStrategy",
+    "sourceStrategy",
+    "origClOrdId",
+    "execId",
+    "orderId",
+    "execType",
+    "orderStatus",
+    "orderRejReason",
+    "lastQty",
+    "lastPrice",
+    "leavesQty",
+    "cumQty",
+    "avgPrice",
+];
 
 pub mod encoder {
     use super::*;
 
     #[derive(Debug, Default)]
-    pub struct ExecutionReportEncoder<'a> {
-        buf: WriteBuf<'a>,
+    pub struct ExecutionReportEncoder<'a, O: ByteOrder = SchemaByteOrder> {
+        buf: WriteBuf<'a, O>,
         initial_offset: usize,
         offset: usize,
         limit: usize,
+        set_fields: u64,
     }
 
-    impl<'a> Writer<'a> for ExecutionReportEncoder<'a> {
+    impl<'a, O: ByteOrder> Writer<'a> for ExecutionReportEncoder<'a, O> {
+        type ByteOrder = O;
+
         #[inline]
-        fn get_buf_mut(&mut self) -> &mut WriteBuf<'a> {
+        fn get_buf_mut(&mut self) -> &mut WriteBuf<'a, O> {
             &mut self.buf
         }
     }
 
-    impl<'a> Encoder<'a> for ExecutionReportEncoder<'a> {
+    impl<'a, O: ByteOrder> Encoder<'a> for ExecutionReportEncoder<'a, O> {
         #[inline]
         fn get_limit(&self) -> usize {
             self.limit
@@ -38,13 +78,18 @@ pub mod encoder {
         }
     }
 
-    impl<'a> ExecutionReportEncoder<'a> {
-        pub fn wrap(mut self, buf: WriteBuf<'a>, offset: usize) -> Self {
+    impl<'a, O: ByteOrder> ExecutionReportEncoder<'a, O> {
+        pub fn wrap(mut self, buf: WriteBuf<'a, O>, offset: usize) -> Self {
             let limit = offset + SBE_BLOCK_LENGTH as usize;
             self.buf = buf;
             self.initial_offset = offset;
             self.offset = offset;
             self.limit = limit;
+            self.set_fields = 0;
+            // optional fields added in later versions stay null unless the caller sets them
+            self.buf.put_u64_at(offset + 140, u64::MAX);
+            self.buf.put_u64_at(offset + 148, u64::MAX);
+            self.buf.put_u8_at(offset + 156, BooleanType::NullVal as u8);
             self
         }
 
@@ -62,6 +107,18 @@ pub mod encoder {
             header
         }
 
+        /// Returns the encoded length and the buffer, or the first REQUIRED field that was never set.
+        /// An unset 'text' is written empty.
+        pub fn finish(mut self) -> SbeResult<(usize, WriteBuf<'a, O>)> {
+            if let Some(index) = (0..REQUIRED_FIELDS.len()).find(|index| self.set_fields & (1 << index) == 0) {
+                return Err(SbeErr::MissingRequiredField(REQUIRED_FIELDS[index]));
+            }
+            if self.set_fields & (1 << 26) == 0 {
+                self.text(&[])?;
+            }
+            Ok((self.encoded_length(), self.buf))
+        }
+
         /// primitive field 'clOrdId'
         /// - min value: 0
         /// - max value: -2
@@ -72,6 +129,7 @@ pub mod encoder {
         /// - encodedLength: 8
         #[inline]
         pub fn cl_ord_id(&mut self, value: u64) {
+            self.set_fields |= 1 << 0;
             let offset = self.offset;
             self.get_buf_mut().put_u64_at(offset, value);
         }
@@ -86,6 +144,7 @@ pub mod encoder {
         /// - encodedLength: 8
         #[inline]
         pub fn cl_ord_link_id(&mut self, value: u64) {
+            self.set_fields |= 1 << 1;
             let offset = self.offset + 8;
             self.get_buf_mut().put_u64_at(offset, value);
         }
@@ -100,6 +159,7 @@ pub mod encoder {
         /// - encodedLength: 4
         #[inline]
         pub fn instrument_id(&mut self, value: u32) {
+            self.set_fields |= 1 << 2;
             let offset = self.offset + 16;
             self.get_buf_mut().put_u32_at(offset, value);
         }
@@ -114,6 +174,7 @@ pub mod encoder {
         /// - encodedLength: 4
         #[inline]
         pub fn target_comp_id(&mut self, value: u32) {
+            self.set_fields |= 1 << 3;
             let offset = self.offset + 20;
             self.get_buf_mut().put_u32_at(offset, value);
         }
@@ -128,6 +189,7 @@ pub mod encoder {
         /// - encodedLength: 4
         #[inline]
         pub fn source_comp_id(&mut self, value: u32) {
+            self.set_fields |= 1 << 4;
             let offset = self.offset + 24;
             self.get_buf_mut().put_u32_at(offset, value);
         }
@@ -135,6 +197,7 @@ pub mod encoder {
         /// REQUIRED enum
         #[inline]
         pub fn side(&mut self, value: OrderSide) {
+            self.set_fields |= 1 << 5;
             let offset = self.offset + 28;
             self.get_buf_mut().put_u8_at(offset, value as u8)
         }
@@ -142,6 +205,7 @@ pub mod encoder {
         /// REQUIRED enum
         #[inline]
         pub fn order_type(&mut self, value: OrderType) {
+            self.set_fields |= 1 << 6;
             let offset = self.offset + 29;
             self.get_buf_mut().put_u8_at(offset, value as u8)
         }
@@ -156,6 +220,7 @@ pub mod encoder {
         /// - encodedLength: 8
         #[inline]
         pub fn price(&mut self, value: f64) {
+            self.set_fields |= 1 << 7;
             let offset = self.offset + 30;
             self.get_buf_mut().put_f64_at(offset, value);
         }
@@ -170,6 +235,7 @@ pub mod encoder {
         /// - encodedLength: 8
         #[inline]
         pub fn order_qty(&mut self, value: f64) {
+            self.set_fields |= 1 << 8;
             let offset = self.offset + 38;
             self.get_buf_mut().put_f64_at(offset, value);
         }
@@ -177,6 +243,7 @@ pub mod encoder {
         /// REQUIRED enum
         #[inline]
         pub fn time_in_force(&mut self, value: TimeInForce) {
+            self.set_fields |= 1 << 9;
             let offset = self.offset + 46;
             self.get_buf_mut().put_u8_at(offset, value as u8)
         }
@@ -191,6 +258,7 @@ pub mod encoder {
         /// - encodedLength: 8
         #[inline]
         pub fn transact_time(&mut self, value: u64) {
+            self.set_fields |= 1 << 10;
             let offset = self.offset + 47;
             self.get_buf_mut().put_u64_at(offset, value);
         }
@@ -205,6 +273,7 @@ pub mod encoder {
         /// - encodedLength: 8
         #[inline]
         pub fn effective_time(&mut self, value: u64) {
+            self.set_fields |= 1 << 11;
             let offset = self.offset + 55;
             self.get_buf_mut().put_u64_at(offset, value);
         }
@@ -219,6 +288,7 @@ pub mod encoder {
         /// - encodedLength: 8
         #[inline]
         pub fn expire_time(&mut self, value: u64) {
+            self.set_fields |= 1 << 12;
             let offset = self.offset + 63;
             self.get_buf_mut().put_u64_at(offset, value);
         }
@@ -226,6 +296,7 @@ pub mod encoder {
         /// REQUIRED enum
         #[inline]
         pub fn target_strategy(&mut self, value: Strategy) {
+            self.set_fields |= 1 << 13;
             let offset = self.offset + 71;
             self.get_buf_mut().put_u8_at(offset, value as u8)
         }
@@ -233,6 +304,7 @@ pub mod encoder {
         /// REQUIRED enum
         #[inline]
         pub fn source_strategy(&mut self, value: Strategy) {
+            self.set_fields |= 1 << 14;
             let offset = self.offset + 72;
             self.get_buf_mut().put_u8_at(offset, value as u8)
         }
@@ -247,6 +319,7 @@ pub mod encoder {
         /// - encodedLength: 8
         #[inline]
         pub fn orig_cl_ord_id(&mut self, value: u64) {
+            self.set_fields |= 1 << 15;
             let offset = self.offset + 73;
             self.get_buf_mut().put_u64_at(offset, value);
         }
@@ -261,6 +334,7 @@ pub mod encoder {
         /// - encodedLength: 8
         #[inline]
         pub fn exec_id(&mut self, value: u64) {
+            self.set_fields |= 1 << 16;
             let offset = self.offset + 81;
             self.get_buf_mut().put_u64_at(offset, value);
         }
@@ -275,6 +349,7 @@ pub mod encoder {
         /// - encodedLength: 8
         #[inline]
         pub fn order_id(&mut self, value: u64) {
+            self.set_fields |= 1 << 17;
             let offset = self.offset + 89;
             self.get_buf_mut().put_u64_at(offset, value);
         }
@@ -282,6 +357,7 @@ pub mod encoder {
         /// REQUIRED enum
         #[inline]
         pub fn exec_type(&mut self, value: ExecType) {
+            self.set_fields |= 1 << 18;
             let offset = self.offset + 97;
             self.get_buf_mut().put_u8_at(offset, value as u8)
         }
@@ -289,6 +365,7 @@ pub mod encoder {
         /// REQUIRED enum
         #[inline]
         pub fn order_status(&mut self, value: OrderStatus) {
+            self.set_fields |= 1 << 19;
             let offset = self.offset + 98;
             self.get_buf_mut().put_u8_at(offset, value as u8)
         }
@@ -296,6 +373,7 @@ pub mod encoder {
         /// REQUIRED enum
         #[inline]
         pub fn order_rej_reason(&mut self, value: OrderRejectReason) {
+            self.set_fields |= 1 << 20;
             let offset = self.offset + 99;
             self.get_buf_mut().put_u8_at(offset, value as u8)
         }
@@ -310,6 +388,7 @@ pub mod encoder {
         /// - encodedLength: 8
         #[inline]
         pub fn last_qty(&mut self, value: f64) {
+            self.set_fields |= 1 << 21;
             let offset = self.offset + 100;
             self.get_buf_mut().put_f64_at(offset, value);
         }
@@ -324,6 +403,7 @@ pub mod encoder {
         /// - encodedLength: 8
         #[inline]
         pub fn last_price(&mut self, value: f64) {
+            self.set_fields |= 1 << 22;
             let offset = self.offset + 108;
             self.get_buf_mut().put_f64_at(offset, value);
         }
@@ -338,6 +418,7 @@ pub mod encoder {
         /// - encodedLength: 8
         #[inline]
         pub fn leaves_qty(&mut self, value: f64) {
+            self.set_fields |= 1 << 23;
             let offset = self.offset + 116;
             self.get_buf_mut().put_f64_at(offset, value);
         }
@@ -352,6 +433,7 @@ pub mod encoder {
         /// - encodedLength: 8
         #[inline]
         pub fn cum_qty(&mut self, value: f64) {
+            self.set_fields |= 1 << 24;
             let offset = self.offset + 124;
             self.get_buf_mut().put_f64_at(offset, value);
         }
@@ -366,10 +448,61 @@ pub mod encoder {
         /// - encodedLength: 8
         #[inline]
         pub fn avg_price(&mut self, value: f64) {
+            self.set_fields |= 1 << 25;
             let offset = self.offset + 132;
             self.get_buf_mut().put_f64_at(offset, value);
         }
 
+        /// VAR_DATA ENCODER - character encoding: 'UTF-8'; writes at the limit, so it must be called
+        /// after every group, and only once: a second call would append a second field
+        pub fn text(&mut self, value: &[u8]) -> SbeResult<()> {
+            if self.set_fields & (1 << 26) != 0 {
+                return Err(SbeErr::DuplicateField("text"));
+            }
+            let limit = var_string_encoding_codec::encode_var_data(&mut self.buf, self.limit, value)?;
+            self.set_fields |= 1 << 26;
+            self.limit = limit;
+            Ok(())
+        }
+
+        /// primitive field 'ordStatusReqId'; echoes the 'OrderStatusRequest' answered by the report
+        /// - min value: 0
+        /// - max value: -2
+        /// - null value: -1
+        /// - characterEncoding: null
+        /// - semanticType: null
+        /// - encodedOffset: 140
+        /// - encodedLength: 8
+        #[inline]
+        pub fn ord_status_req_id(&mut self, value: u64) {
+            self.set_fields |= 1 << 27;
+            let offset = self.offset + 140;
+            self.get_buf_mut().put_u64_at(offset, value);
+        }
+
+        /// primitive field 'massStatusReqId'; echoes the 'OrderMassStatusRequest' answered by the report
+        /// - min value: 0
+        /// - max value: -2
+        /// - null value: -1
+        /// - characterEncoding: null
+        /// - semanticType: null
+        /// - encodedOffset: 148
+        /// - encodedLength: 8
+        #[inline]
+        pub fn mass_status_req_id(&mut self, value: u64) {
+            self.set_fields |= 1 << 28;
+            let offset = self.offset + 148;
+            self.get_buf_mut().put_u64_at(offset, value);
+        }
+
+        /// OPTIONAL enum; YES on the last report answering a status request
+        #[inline]
+        pub fn last_rpt_requested(&mut self, value: BooleanType) {
+            self.set_fields |= 1 << 29;
+            let offset = self.offset + 156;
+            self.get_buf_mut().put_u8_at(offset, value as u8)
+        }
+
     }
 
 } // end encoder
@@ -378,8 +511,8 @@ pub mod decoder {
     use super::*;
 
     #[derive(Debug, Default)]
-    pub struct ExecutionReportDecoder<'a> {
-        buf: ReadBuf<'a>,
+    pub struct ExecutionReportDecoder<'a, O: ByteOrder = SchemaByteOrder> {
+        buf: ReadBuf<'a, O>,
         initial_offset: usize,
         offset: usize,
         limit: usize,
@@ -387,14 +520,16 @@ pub mod decoder {
         pub acting_version: u16,
     }
 
-    impl<'a> Reader<'a> for ExecutionReportDecoder<'a> {
+    impl<'a, O: ByteOrder> Reader<'a> for ExecutionReportDecoder<'a, O> {
+        type ByteOrder = O;
+
         #[inline]
-        fn get_buf(&self) -> &ReadBuf<'a> {
+        fn get_buf(&self) -> &ReadBuf<'a, O> {
             &self.buf
         }
     }
 
-    impl<'a> Decoder<'a> for ExecutionReportDecoder<'a> {
+    impl<'a, O: ByteOrder> Decoder<'a> for ExecutionReportDecoder<'a, O> {
         #[inline]
         fn get_limit(&self) -> usize {
             self.limit
@@ -406,10 +541,10 @@ pub mod decoder {
         }
     }
 
-    impl<'a> ExecutionReportDecoder<'a> {
+    impl<'a, O: ByteOrder> ExecutionReportDecoder<'a, O> {
         pub fn wrap(
             mut self,
-            buf: ReadBuf<'a>,
+            buf: ReadBuf<'a, O>,
             offset: usize,
             acting_block_length: u16,
             acting_version: u16,
@@ -429,7 +564,7 @@ pub mod decoder {
             self.limit - self.offset
         }
 
-        pub fn header(self, mut header: MessageHeaderDecoder<ReadBuf<'a>>) -> Self {
+        pub fn header(self, mut header: MessageHeaderDecoder<ReadBuf<'a, O>>) -> Self {
             debug_assert_eq!(SBE_TEMPLATE_ID, header.template_id());
             let acting_block_length = header.block_length();
             let acting_version = header.version();
@@ -598,6 +733,51 @@ pub mod decoder {
             self.get_buf().get_f64_at(self.offset + 132)
         }
 
+        /// primitive field - 'OPTIONAL'; null for senders predating the field
+        #[inline]
+        pub fn ord_status_req_id(&self) -> u64 {
+            if self.acting_version < STATUS_REQ_SINCE_VERSION {
+                return u64::MAX;
+            }
+            self.get_buf().get_u64_at(self.offset + 140)
+        }
+
+        /// primitive field - 'OPTIONAL'; null for senders predating the field
+        #[inline]
+        pub fn mass_status_req_id(&self) -> u64 {
+            if self.acting_version < STATUS_REQ_SINCE_VERSION {
+                return u64::MAX;
+            }
+            self.get_buf().get_u64_at(self.offset + 148)
+        }
+
+        /// OPTIONAL enum; null for senders predating the field
+        #[inline]
+        pub fn last_rpt_requested(&self) -> BooleanType {
+            if self.acting_version < STATUS_REQ_SINCE_VERSION {
+                return BooleanType::NullVal;
+            }
+            self.get_buf().get_u8_at(self.offset + 156).into()
+        }
+
+        /// VAR_DATA DECODER - character encoding: 'UTF-8'; reads at the limit and moves it past the
+        /// data, so it must be called after every group. Empty when the sender predates the field.
+        pub fn text(&mut self) -> SbeResult<&'a [u8]> {
+            if self.acting_version < TEXT_SINCE_VERSION {
+                return Ok(&[]);
+            }
+            let (offset, length) = var_string_encoding_codec::var_data_extent(&self.buf, self.limit)?;
+            self.limit = offset + length;
+            let data: &'a [u8] = self.buf.data;
+            Ok(&data[offset..offset + length])
+        }
+
+        /// A decoder over the same message with the limit back at the end of the block.
+        pub(crate) fn rewound(&self) -> Self {
+            let buf = ReadBuf::with_byte_order(self.buf.data, O::default());
+            Self::default().wrap(buf, self.offset, self.acting_block_length, self.acting_version)
+        }
+
     }
 
 } // end decoder
@@ -0,0 +1,209 @@
+use crate::*;
+
+pub use encoder::*;
+pub use decoder::*;
+
+pub const ENCODED_LENGTH: usize = 4;
+/// largest 'numInGroup', 65535 being the null value
+pub const MAX_NUM_IN_GROUP: usize = 65534;
+/// schema version from which groups use 'GroupSizeEncoding' instead of 'SmallGroupSizeEncoding'
+pub const GROUP_SIZE_ENCODING_SINCE_VERSION: u16 = 1;
+
+/// Reads the group header at `offset` and checks that `count` entries of at least
+/// `entry_length` bytes fit in `buf`. Returns the offset of the first entry, the block length
+/// and the count.
+pub(crate) fn group_extent<O: ByteOrder>(
+    buf: &ReadBuf<'_, O>,
+    offset: usize,
+    entry_length: usize,
+) -> SbeResult<(usize, usize, usize)> {
+    if buf.data.len() < offset + ENCODED_LENGTH {
+        return Err(SbeErr::BufferTooShort);
+    }
+    let block_length = buf.get_u16_at(offset) as usize;
+    let count = buf.get_u16_at(offset + 2) as usize;
+    checked_extent(buf, offset + ENCODED_LENGTH, block_length, count, entry_length)
+}
+
+/// Checks that `count` entries of `block_length` bytes, the last one at least `entry_length`
+/// bytes, fit in `buf` from `first_offset`, whichever group header they follow.
+pub(crate) fn checked_extent<O: ByteOrder>(
+    buf: &ReadBuf<'_, O>,
+    first_offset: usize,
+    block_length: usize,
+    count: usize,
+    entry_length: usize,
+) -> SbeResult<(usize, usize, usize)> {
+    if count > 0 && buf.data.len() < first_offset + (count - 1) * block_length + block_length.max(entry_length) {
+        return Err(SbeErr::BufferTooShort);
+    }
+    Ok((first_offset, block_length, count))
+}
+
+/// Checks once that `count` entries of `block_length` bytes starting at `offset` fit in `buf`,
+/// then yields the leading `N` bytes of each entry.
+pub(crate) fn entries<'a, O: ByteOrder, const N: usize>(
+    buf: &ReadBuf<'a, O>,
+    offset: usize,
+    block_length: usize,
+    count: usize,
+) -> SbeResult<impl Iterator<Item = &'a [u8; N]> + 'a> {
+    let end = if count == 0 {
+        offset
+    } else {
+        (count - 1)
+            .checked_mul(block_length)
+            .and_then(|length| length.checked_add(offset + block_length.max(N)))
+            .ok_or(SbeErr::BufferTooShort)?
+    };
+    let data: &'a [u8] = buf.data.get(offset..end).ok_or(SbeErr::BufferTooShort)?;
+    Ok((0..count).map(move |index| {
+        let start = index * block_length;
+        data[start..start + N].try_into().expect("entry within checked extent")
+    }))
+}
+
+/// Writes a group header at `offset` followed by one `block_length` entry per item, each written
+/// by `encode`, which hands the buffer back. Returns the limit after the group and the number of
+/// entries; on error `buf` is left in place, without a group header.
+pub(crate) fn encode_group<'a, O: ByteOrder, I, F>(
+    buf: &mut WriteBuf<'a, O>,
+    offset: usize,
+    block_length: usize,
+    entries: I,
+    mut encode: F,
+) -> SbeResult<(usize, usize)>
+where
+    I: IntoIterator,
+    F: FnMut(WriteBuf<'a, O>, usize, I::Item) -> WriteBuf<'a, O>,
+{
+    if buf.data.len() < offset + ENCODED_LENGTH {
+        return Err(SbeErr::BufferTooShort);
+    }
+    let mut limit = offset + ENCODED_LENGTH;
+    let mut count = 0;
+    for entry in entries {
+        if count == MAX_NUM_IN_GROUP {
+            return Err(SbeErr::GroupCountOverflow);
+        }
+        if buf.data.len() < limit + block_length {
+            return Err(SbeErr::BufferTooShort);
+        }
+        *buf = encode(core::mem::take(buf), limit, entry);
+        limit += block_length;
+        count += 1;
+    }
+    buf.put_u16_at(offset, block_length as u16);
+    buf.put_u16_at(offset + 2, count as u16);
+    Ok((limit, count))
+}
+
+pub mod encoder {
+    use super::*;
+
+    #[derive(Debug, Default)]
+    pub struct GroupSizeEncodingEncoder<P> {
+        parent: Option<P>,
+        offset: usize,
+    }
+
+    impl<'a, P> Writer<'a> for GroupSizeEncodingEncoder<P> where P: Writer<'a> + Default {
+        type ByteOrder = P::ByteOrder;
+
+        #[inline]
+        fn get_buf_mut(&mut self) -> &mut WriteBuf<'a, P::ByteOrder> {
+            if let Some(parent) = self.parent.as_mut() {
+                parent.get_buf_mut()
+            } else {
+                panic!("parent was None")
+            }
+        }
+    }
+
+    impl<'a, P> GroupSizeEncodingEncoder<P> where P: Writer<'a> + Default {
+        pub fn wrap(mut self, parent: P, offset: usize) -> Self {
+            self.parent = Some(parent);
+            self.offset = offset;
+            self
+        }
+
+        #[inline]
+        pub fn parent(&mut self) -> SbeResult<P> {
+            self.parent.take().ok_or(SbeErr::ParentNotSet)
+        }
+
+        /// primitive field 'blockLength'
+        /// - min value: 0
+        /// - max value: 65534
+        /// - null value: 65535
+        /// - characterEncoding: null
+        /// - semanticType: null
+        /// - encodedOffset: 0
+        /// - encodedLength: 2
+        #[inline]
+        pub fn block_length(&mut self, value: u16) {
+            let offset = self.offset;
+            self.get_buf_mut().put_u16_at(offset, value);
+        }
+
+        /// primitive field 'numInGroup'
+        /// - min value: 0
+        /// - max value: 65534
+        /// - null value: 65535
+        /// - characterEncoding: null
+        /// - semanticType: null
+        /// - encodedOffset: 2
+        /// - encodedLength: 2
+        #[inline]
+        pub fn num_in_group(&mut self, value: u16) {
+            let offset = self.offset + 2;
+            self.get_buf_mut().put_u16_at(offset, value);
+        }
+
+    }
+} // end encoder mod 
+
+pub mod decoder {
+    use super::*;
+
+    #[derive(Debug, Default)]
+    pub struct GroupSizeEncodingDecoder<P> {
+        parent: Option<P>,
+        offset: usize,
+    }
+
+    impl<'a, P> Reader<'a> for GroupSizeEncodingDecoder<P> where P: Reader<'a> + Default {
+        type ByteOrder = P::ByteOrder;
+
+        #[inline]
+        fn get_buf(&self) -> &ReadBuf<'a, P::ByteOrder> {
+            self.parent.as_ref().expect("parent missing").get_buf()
+        }
+    }
+
+    impl<'a, P> GroupSizeEncodingDecoder<P> where P: Reader<'a> + Default {
+        pub fn wrap(mut self, parent: P, offset: usize) -> Self {
+            self.parent = Some(parent);
+            self.offset = offset;
+            self
+        }
+
+        #[inline]
+        pub fn parent(&mut self) -> SbeResult<P> {
+            self.parent.take().ok_or(SbeErr::ParentNotSet)
+        }
+
+        /// primitive field - 'REQUIRED'
+        #[inline]
+        pub fn block_length(&self) -> u16 {
+            self.get_buf().get_u16_at(self.offset)
+        }
+
+        /// primitive field - 'REQUIRED'
+        #[inline]
+        pub fn num_in_group(&self) -> u16 {
+            self.get_buf().get_u16_at(self.offset + 2)
+        }
+
+    }
+} // end decoder mod 
@@ -0,0 +1,375 @@
+use crate::*;
+
+pub use encoder::*;
+pub use decoder::*;
+
+pub const SBE_BLOCK_LENGTH: u16 = 60;
+pub const SBE_TEMPLATE_ID: u16 = 15;
+pub const SBE_SCHEMA_ID: u16 = 1;
+pub const SBE_SCHEMA_VERSION: u16 = 4;
+
+/// encoded length of the 'symbol' char array
+pub const SYMBOL_LENGTH: usize = 16;
+/// encoded length of the 'baseCurrency' and 'termCurrency' char arrays
+pub const CURRENCY_LENGTH: usize = 3;
+
+/// names of the REQUIRED fields, in the bit order of the encoder's set-field mask
+pub const REQUIRED_FIELDS: &[&str] = &[
+    "instrumentId",
+    "symbol",
+    "baseCurrency",
+    "termCurrency",
+    "tickSize",
+    "minQty",
+    "maxQty",
+    "pricePrecision",
+    "instrumentStatus",
+    "transactTime",
+];
+
+pub mod encoder {
+    use super::*;
+
+    #[derive(Debug, Default)]
+    pub struct InstrumentDefinitionEncoder<'a, O: ByteOrder = SchemaByteOrder> {
+        buf: WriteBuf<'a, O>,
+        initial_offset: usize,
+        offset: usize,
+        limit: usize,
+        set_fields: u64,
+    }
+
+    impl<'a, O: ByteOrder> Writer<'a> for InstrumentDefinitionEncoder<'a, O> {
+        type ByteOrder = O;
+
+        #[inline]
+        fn get_buf_mut(&mut self) -> &mut WriteBuf<'a, O> {
+            &mut self.buf
+        }
+    }
+
+    impl<'a, O: ByteOrder> Encoder<'a> for InstrumentDefinitionEncoder<'a, O> {
+        #[inline]
+        fn get_limit(&self) -> usize {
+            self.limit
+        }
+
+        #[inline]
+        fn set_limit(&mut self, limit: usize) {
+            self.limit = limit;
+        }
+    }
+
+    impl<'a, O: ByteOrder> InstrumentDefinitionEncoder<'a, O> {
+        pub fn wrap(mut self, buf: WriteBuf<'a, O>, offset: usize) -> Self {
+            let limit = offset + SBE_BLOCK_LENGTH as usize;
+            self.buf = buf;
+            self.initial_offset = offset;
+            self.offset = offset;
+            self.limit = limit;
+            self.set_fields = 0;
+            self
+        }
+
+        #[inline]
+        pub fn encoded_length(&self) -> usize {
+            self.limit - self.offset
+        }
+
+        pub fn header(self, offset: usize) -> MessageHeaderEncoder<Self> {
+            let mut header = MessageHeaderEncoder::default().wrap(self, offset);
+            header.block_length(SBE_BLOCK_LENGTH);
+            header.template_id(SBE_TEMPLATE_ID);
+            header.schema_id(SBE_SCHEMA_ID);
+            header.version(SBE_SCHEMA_VERSION);
+            header
+        }
+
+        /// Returns the encoded length and the buffer, or the first REQUIRED field that was never set.
+        pub fn finish(self) -> SbeResult<(usize, WriteBuf<'a, O>)> {
+            if let Some(index) = (0..REQUIRED_FIELDS.len()).find(|index| self.set_fields & (1 << index) == 0) {
+                return Err(SbeErr::MissingRequiredField(REQUIRED_FIELDS[index]));
+            }
+            Ok((self.encoded_length(), self.buf))
+        }
+
+        /// primitive field 'instrumentId'
+        /// - min value: 0
+        /// - max value: 4294967294
+        /// - null value: 4294967295
+        /// - characterEncoding: null
+        /// - semanticType: null
+        /// - encodedOffset: 0
+        /// - encodedLength: 4
+        #[inline]
+        pub fn instrument_id(&mut self, value: u32) {
+            self.set_fields |= 1 << 0;
+            let offset = self.offset;
+            self.get_buf_mut().put_u32_at(offset, value);
+        }
+
+        /// primitive array field 'symbol'
+        /// - min value: 32
+        /// - max value: 126
+        /// - null value: 0
+        /// - characterEncoding: US-ASCII
+        /// - semanticType: null
+        /// - encodedOffset: 4
+        /// - encodedLength: 16
+        #[inline]
+        pub fn symbol(&mut self, value: &[u8; SYMBOL_LENGTH]) {
+            self.set_fields |= 1 << 1;
+            let offset = self.offset + 4;
+            self.get_buf_mut().put_slice_at(offset, value);
+        }
+
+        /// primitive array field 'baseCurrency'
+        /// - min value: 32
+        /// - max value: 126
+        /// - null value: 0
+        /// - characterEncoding: US-ASCII
+        /// - semanticType: Currency
+        /// - encodedOffset: 20
+        /// - encodedLength: 3
+        #[inline]
+        pub fn base_currency(&mut self, value: &[u8; CURRENCY_LENGTH]) {
+            self.set_fields |= 1 << 2;
+            let offset = self.offset + 20;
+            self.get_buf_mut().put_slice_at(offset, value);
+        }
+
+        /// primitive array field 'termCurrency'
+        /// - min value: 32
+        /// - max value: 126
+        /// - null value: 0
+        /// - characterEncoding: US-ASCII
+        /// - semanticType: Currency
+        /// - encodedOffset: 23
+        /// - encodedLength: 3
+        #[inline]
+        pub fn term_currency(&mut self, value: &[u8; CURRENCY_LENGTH]) {
+            self.set_fields |= 1 << 3;
+            let offset = self.offset + 23;
+            self.get_buf_mut().put_slice_at(offset, value);
+        }
+
+        /// primitive field 'tickSize'
+        /// - min value: 4.9E-324
+        /// - max value: 1.7976931348623157E308
+        /// - null value: NaN
+        /// - characterEncoding: null
+        /// - semanticType: null
+        /// - encodedOffset: 26
+        /// - encodedLength: 8
+        #[inline]
+        pub fn tick_size(&mut self, value: f64) {
+            self.set_fields |= 1 << 4;
+            let offset = self.offset + 26;
+            self.get_buf_mut().put_f64_at(offset, value);
+        }
+
+        /// primitive field 'minQty'
+        /// - min value: 4.9E-324
+        /// - max value: 1.7976931348623157E308
+        /// - null value: NaN
+        /// - characterEncoding: null
+        /// - semanticType: null
+        /// - encodedOffset: 34
+        /// - encodedLength: 8
+        #[inline]
+        pub fn min_qty(&mut self, value: f64) {
+            self.set_fields |= 1 << 5;
+            let offset = self.offset + 34;
+            self.get_buf_mut().put_f64_at(offset, value);
+        }
+
+        /// primitive field 'maxQty'
+        /// - min value: 4.9E-324
+        /// - max value: 1.7976931348623157E308
+        /// - null value: NaN
+        /// - characterEncoding: null
+        /// - semanticType: null
+        /// - encodedOffset: 42
+        /// - encodedLength: 8
+        #[inline]
+        pub fn max_qty(&mut self, value: f64) {
+            self.set_fields |= 1 << 6;
+            let offset = self.offset + 42;
+            self.get_buf_mut().put_f64_at(offset, value);
+        }
+
+        /// primitive field 'pricePrecision'
+        /// - min value: 0
+        /// - max value: 254
+        /// - null value: 255
+        /// - characterEncoding: null
+        /// - semanticType: null
+        /// - encodedOffset: 50
+        /// - encodedLength: 1
+        #[inline]
+        pub fn price_precision(&mut self, value: u8) {
+            self.set_fields |= 1 << 7;
+            let offset = self.offset + 50;
+            self.get_buf_mut().put_u8_at(offset, value);
+        }
+
+        /// REQUIRED enum
+        #[inline]
+        pub fn instrument_status(&mut self, value: InstrumentStatus) {
+            self.set_fields |= 1 << 8;
+            let offset = self.offset + 51;
+            self.get_buf_mut().put_u8_at(offset, value as u8)
+        }
+
+        /// primitive field 'transactTime'
+        /// - min value: 0
+        /// - max value: -2
+        /// - null value: -1
+        /// - characterEncoding: null
+        /// - semanticType: null
+        /// - encodedOffset: 52
+        /// - encodedLength: 8
+        #[inline]
+        pub fn transact_time(&mut self, value: u64) {
+            self.set_fields |= 1 << 9;
+            let offset = self.offset + 52;
+            self.get_buf_mut().put_u64_at(offset, value);
+        }
+
+    }
+
+} // end encoder
+
+pub mod decoder {
+    use super::*;
+
+    #[derive(Debug, Default)]
+    pub struct InstrumentDefinitionDecoder<'a, O: ByteOrder = SchemaByteOrder> {
+        buf: ReadBuf<'a, O>,
+        initial_offset: usize,
+        offset: usize,
+        limit: usize,
+        pub acting_block_length: u16,
+        pub acting_version: u16,
+    }
+
+    impl<'a, O: ByteOrder> Reader<'a> for InstrumentDefinitionDecoder<'a, O> {
+        type ByteOrder = O;
+
+        #[inline]
+        fn get_buf(&self) -> &ReadBuf<'a, O> {
+            &self.buf
+        }
+    }
+
+    impl<'a, O: ByteOrder> Decoder<'a> for InstrumentDefinitionDecoder<'a, O> {
+        #[inline]
+        fn get_limit(&self) -> usize {
+            self.limit
+        }
+
+        #[inline]
+        fn set_limit(&mut self, limit: usize) {
+            self.limit = limit;
+        }
+    }
+
+    impl<'a, O: ByteOrder> InstrumentDefinitionDecoder<'a, O> {
+        pub fn wrap(
+            mut self,
+            buf: ReadBuf<'a, O>,
+            offset: usize,
+            acting_block_length: u16,
+            acting_version: u16,
+        ) -> Self {
+            let limit = offset + acting_block_length as usize;
+            self.buf = buf;
+            self.initial_offset = offset;
+            self.offset = offset;
+            self.limit = limit;
+            self.acting_block_length = acting_block_length;
+            self.acting_version = acting_version;
+            self
+        }
+
+        #[inline]
+        pub fn encoded_length(&self) -> usize {
+            self.limit - self.offset
+        }
+
+        pub fn header(self, mut header: MessageHeaderDecoder<ReadBuf<'a, O>>) -> Self {
+            debug_assert_eq!(SBE_TEMPLATE_ID, header.template_id());
+            let acting_block_length = header.block_length();
+            let acting_version = header.version();
+
+            self.wrap(
+                header.parent().unwrap(),
+                message_header_codec::ENCODED_LENGTH,
+                acting_block_length,
+                acting_version,
+            )
+        }
+
+        /// primitive field - 'REQUIRED'
+        #[inline]
+        pub fn instrument_id(&self) -> u32 {
+            self.get_buf().get_u32_at(self.offset)
+        }
+
+        /// primitive array field - 'REQUIRED'
+        #[inline]
+        pub fn symbol(&self) -> &'a [u8; SYMBOL_LENGTH] {
+            self.buf.get_array_at(self.offset + 4)
+        }
+
+        /// primitive array field - 'REQUIRED'
+        #[inline]
+        pub fn base_currency(&self) -> &'a [u8; CURRENCY_LENGTH] {
+            self.buf.get_array_at(self.offset + 20)
+        }
+
+        /// primitive array field - 'REQUIRED'
+        #[inline]
+        pub fn term_currency(&self) -> &'a [u8; CURRENCY_LENGTH] {
+            self.buf.get_array_at(self.offset + 23)
+        }
+
+        /// primitive field - 'REQUIRED'
+        #[inline]
+        pub fn tick_size(&self) -> f64 {
+            self.get_buf().get_f64_at(self.offset + 26)
+        }
+
+        /// primitive field - 'REQUIRED'
+        #[inline]
+        pub fn min_qty(&self) -> f64 {
+            self.get_buf().get_f64_at(self.offset + 34)
+        }
+
+        /// primitive field - 'REQUIRED'
+        #[inline]
+        pub fn max_qty(&self) -> f64 {
+            self.get_buf().get_f64_at(self.offset + 42)
+        }
+
+        /// primitive field - 'REQUIRED'
+        #[inline]
+        pub fn price_precision(&self) -> u8 {
+            self.get_buf().get_u8_at(self.offset + 50)
+        }
+
+        /// REQUIRED enum
+        #[inline]
+        pub fn instrument_status(&self) -> InstrumentStatus {
+            self.get_buf().get_u8_at(self.offset + 51).into()
+        }
+
+        /// primitive field - 'REQUIRED'
+        #[inline]
+        pub fn transact_time(&self) -> u64 {
+            self.get_buf().get_u64_at(self.offset + 52)
+        }
+
+    }
+
+} // end decoder
@@ -0,0 +1,145 @@
+//! Instrument reference data keyed by `instrumentId`.
+//!
+//! [`InstrumentRegistry`] is populated from `InstrumentDefinition` messages and answers lookups
+//! by instrument id or symbol. A later definition of the same instrument replaces the earlier
+//! one, including its symbol.
+use crate::*;
+
+use std::collections::HashMap;
+
+/// Reference data of one instrument, as carried by an `InstrumentDefinition`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Instrument {
+    pub instrument_id: u32,
+    pub symbol: [u8; SYMBOL_LENGTH],
+    pub base_currency: [u8; CURRENCY_LENGTH],
+    pub term_currency: [u8; CURRENCY_LENGTH],
+    pub tick_size: f64,
+    pub min_qty: f64,
+    pub max_qty: f64,
+    pub price_precision: u8,
+    pub instrument_status: InstrumentStatus,
+}
+
+impl Instrument {
+    pub fn from_decoder(decoder: &InstrumentDefinitionDecoder<'_>) -> Self {
+        Self {
+            instrument_id: decoder.instrument_id(),
+            symbol: *decoder.symbol(),
+            base_currency: *decoder.base_currency(),
+            term_currency: *decoder.term_currency(),
+            tick_size: decoder.tick_size(),
+            min_qty: decoder.min_qty(),
+            max_qty: decoder.max_qty(),
+            price_precision: decoder.price_precision(),
+            instrument_status: decoder.instrument_status(),
+        }
+    }
+
+    /// Symbol without its null padding; empty when it is not ASCII.
+    pub fn symbol(&self) -> &str {
+        char_array_str(&self.symbol)
+    }
+
+    pub fn base_currency(&self) -> &str {
+        char_array_str(&self.base_currency)
+    }
+
+    pub fn term_currency(&self) -> &str {
+        char_array_str(&self.term_currency)
+    }
+
+    /// `price` rounded to the nearest tick, then to `pricePrecision` decimals. Returned unchanged
+    /// when the tick size is null or not positive.
+    pub fn round_to_tick(&self, price: f64) -> f64 {
+        if self.tick_size.is_nan() || self.tick_size <= 0.0 {
+            return price;
+        }
+        let rounded = (price / self.tick_size).round() * self.tick_size;
+        if self.price_precision == u8::MAX {
+            return rounded;
+        }
+        let scale = 10_f64.powi(self.price_precision as i32);
+        (rounded * scale).round() / scale
+    }
+
+    /// Whether `qty` lies within `minQty` and `maxQty`; a null bound does not limit.
+    pub fn accepts_qty(&self, qty: f64) -> bool {
+        !qty.is_nan()
+            && (self.min_qty.is_nan() || qty >= self.min_qty)
+            && (self.max_qty.is_nan() || qty <= self.max_qty)
+    }
+}
+
+#[inline]
+fn char_array_str(chars: &[u8]) -> &str {
+    std::str::from_utf8(trim_char_array(chars)).ok().filter(|chars| chars.is_ascii()).unwrap_or_default()
+}
+
+/// Instruments by id and by symbol.
+#[derive(Debug, Default)]
+pub struct InstrumentRegistry {
+    instruments: HashMap<u32, Instrument>,
+    ids_by_symbol: HashMap<[u8; SYMBOL_LENGTH], u32>,
+}
+
+impl InstrumentRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Applies one framed message; messages other than `InstrumentDefinition` are ignored.
+    pub fn handle(&mut self, message: &[u8]) -> SbeResult<()> {
+        if let AnyMessage::InstrumentDefinition(definition) = AnyMessage::decode(message, 0)? {
+            self.insert(Instrument::from_decoder(&definition));
+        }
+        Ok(())
+    }
+
+    /// Adds or replaces an instrument and returns its previous definition. The symbol is
+    /// reassigned to this instrument if another one held it.
+    pub fn insert(&mut self, instrument: Instrument) -> Option<Instrument> {
+        let previous = self.instruments.insert(instrument.instrument_id, instrument);
+        if let Some(previous) = previous {
+            if previous.symbol != instrument.symbol
+                && self.ids_by_symbol.get(&previous.symbol) == Some(&previous.instrument_id)
+            {
+                self.ids_by_symbol.remove(&previous.symbol);
+            }
+        }
+        self.ids_by_symbol.insert(instrument.symbol, instrument.instrument_id);
+        previous
+    }
+
+    pub fn remove(&mut self, instrument_id: u32) -> Option<Instrument> {
+        let instrument = self.instruments.remove(&instrument_id)?;
+        if self.ids_by_symbol.get(&instrument.symbol) == Some(&instrument_id) {
+            self.ids_by_symbol.remove(&instrument.symbol);
+        }
+        Some(instrument)
+    }
+
+    #[inline]
+    pub fn get(&self, instrument_id: u32) -> Option<&Instrument> {
+        self.instruments.get(&instrument_id)
+    }
+
+    pub fn by_symbol(&self, symbol: &str) -> Option<&Instrument> {
+        let symbol = to_char_array(symbol.as_bytes())?;
+        self.ids_by_symbol.get(&symbol).and_then(|id| self.instruments.get(id))
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.instruments.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.instruments.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Instrument> {
+        self.instruments.values()
+    }
+}
@@ -0,0 +1,23 @@
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(u8)]
+pub enum InstrumentStatus {
+    ACTIVE = 65_u8, 
+    HALTED = 72_u8, 
+    CLOSED = 67_u8, 
+    NullVal = 0_u8, 
+}
+impl Default for InstrumentStatus {
+    #[inline]
+    fn default() -> Self { InstrumentStatus::NullVal }
+}
+impl From<u8> for InstrumentStatus {
+    #[inline]
+    fn from(v: u8) -> Self {
+        match v {
+            65_u8 => Self::ACTIVE, 
+            72_u8 => Self::HALTED, 
+            67_u8 => Self::CLOSED, 
+            _ => Self::NullVal,
+        }
+    }
+}
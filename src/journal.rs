@@ -0,0 +1,336 @@
+//! Append-only journal of framed SBE messages.
+//!
+//! A journal is a directory of segment files written with plain file I/O. Segments are named
+//! `{segment index}_{timestamp of first record}.journal`, so they sort chronologically by name.
+//! A lookup by timestamp binary-searches the segment names, then the timestamp index of the
+//! segment, built from its record headers when it is read. Each record is a fixed header
+//! followed by the message bytes exactly as they were passed to [`JournalWriter::append`]:
+//!
+//! | offset | length | field                          |
+//! |--------|--------|--------------------------------|
+//! | 0      | 4      | message length (u32)           |
+//! | 4      | 1      | [`Direction`]                  |
+//! | 5      | 3      | reserved                       |
+//! | 8      | 8      | receive timestamp (u64)        |
+//!
+//! Timestamp lookup assumes records are appended with non-decreasing timestamps.
+use crate::*;
+
+use core::convert::TryFrom;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+pub const RECORD_HEADER_LENGTH: usize = 16;
+pub const SEGMENT_FILE_EXTENSION: &str = "journal";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(u8)]
+pub enum Direction {
+    INBOUND = 73_u8,
+    OUTBOUND = 79_u8,
+    NullVal = 0_u8,
+}
+impl Default for Direction {
+    #[inline]
+    fn default() -> Self { Direction::NullVal }
+}
+impl From<u8> for Direction {
+    #[inline]
+    fn from(v: u8) -> Self {
+        match v {
+            73_u8 => Self::INBOUND,
+            79_u8 => Self::OUTBOUND,
+            _ => Self::NullVal,
+        }
+    }
+}
+
+/// Location of a record: segment index within the reader and byte offset within the segment.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct JournalPosition {
+    pub segment: usize,
+    pub offset: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct SegmentFile {
+    index: u64,
+    first_timestamp: u64,
+    path: PathBuf,
+}
+
+fn segment_file_name(index: u64, first_timestamp: u64) -> String {
+    format!("{:010}_{:020}.{}", index, first_timestamp, SEGMENT_FILE_EXTENSION)
+}
+
+fn parse_segment_file_name(path: &Path) -> Option<(u64, u64)> {
+    if path.extension()? != SEGMENT_FILE_EXTENSION {
+        return None;
+    }
+    let stem = path.file_stem()?.to_str()?;
+    let (index, first_timestamp) = stem.split_once('_')?;
+    Some((index.parse().ok()?, first_timestamp.parse().ok()?))
+}
+
+fn list_segments(dir: &Path) -> io::Result<Vec<SegmentFile>> {
+    let mut segments = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if let Some((index, first_timestamp)) = parse_segment_file_name(&path) {
+            segments.push(SegmentFile { index, first_timestamp, path });
+        }
+    }
+    segments.sort_by_key(|segment| segment.index);
+    Ok(segments)
+}
+
+/// Returns the length of the prefix of `data` made of complete records.
+fn complete_records_length(data: &[u8]) -> usize {
+    Records { data, offset: 0 }.last().map_or(0, |record| record.offset + record.encoded_length())
+}
+
+/// Appends records to the newest segment of a journal directory, rolling to a new segment
+/// once `max_segment_length` would be exceeded.
+#[derive(Debug)]
+pub struct JournalWriter {
+    dir: PathBuf,
+    max_segment_length: u64,
+    next_segment_index: u64,
+    segment_length: u64,
+    file: Option<BufWriter<File>>,
+}
+
+impl JournalWriter {
+    /// Opens (creating if needed) the journal in `dir`. Appending continues in the newest
+    /// segment, after dropping any partially written record at its tail.
+    pub fn open<P: AsRef<Path>>(dir: P, max_segment_length: u64) -> io::Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir)?;
+        let mut writer = Self {
+            dir,
+            max_segment_length,
+            next_segment_index: 0,
+            segment_length: 0,
+            file: None,
+        };
+        if let Some(last) = list_segments(&writer.dir)?.pop() {
+            let valid_length = complete_records_length(&fs::read(&last.path)?) as u64;
+            let file = OpenOptions::new().write(true).open(&last.path)?;
+            file.set_len(valid_length)?;
+            let mut file = BufWriter::new(file);
+            io::Seek::seek(file.get_mut(), io::SeekFrom::End(0))?;
+            writer.next_segment_index = last.index + 1;
+            writer.segment_length = valid_length;
+            writer.file = Some(file);
+        }
+        Ok(writer)
+    }
+
+    /// Appends one framed message (message header included) byte-for-byte.
+    pub fn append(&mut self, timestamp: u64, direction: Direction, message: &[u8]) -> io::Result<()> {
+        let message_length = u32::try_from(message.len())
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "message too long for journal record"))?;
+        let record_length = (RECORD_HEADER_LENGTH + message.len()) as u64;
+        if self.file.is_none()
+            || (self.segment_length > 0 && self.segment_length + record_length > self.max_segment_length)
+        {
+            self.roll(timestamp)?;
+        }
+
+        let mut header = [0_u8; RECORD_HEADER_LENGTH];
+        let mut buf = WriteBuf::new(&mut header);
+        buf.put_u32_at(0, message_length);
+        buf.put_u8_at(4, direction as u8);
+        buf.put_u64_at(8, timestamp);
+
+        let file = self.file.as_mut().expect("segment open");
+        file.write_all(&header)?;
+        file.write_all(message)?;
+        self.segment_length += record_length;
+        Ok(())
+    }
+
+    /// Closes the current segment and starts a new one whose first record has `timestamp`.
+    pub fn roll(&mut self, timestamp: u64) -> io::Result<()> {
+        self.flush()?;
+        let path = self.dir.join(segment_file_name(self.next_segment_index, timestamp));
+        let file = OpenOptions::new().write(true).create_new(true).open(path)?;
+        self.file = Some(BufWriter::new(file));
+        self.next_segment_index += 1;
+        self.segment_length = 0;
+        Ok(())
+    }
+
+    /// Hands buffered records to the operating system.
+    pub fn flush(&mut self) -> io::Result<()> {
+        match self.file.as_mut() {
+            Some(file) => file.flush(),
+            None => Ok(()),
+        }
+    }
+
+    /// Flushes and waits until the current segment is persisted to storage.
+    pub fn sync(&mut self) -> io::Result<()> {
+        self.flush()?;
+        match self.file.as_mut() {
+            Some(file) => file.get_ref().sync_data(),
+            None => Ok(()),
+        }
+    }
+}
+
+/// A journal record borrowed from a loaded [`Segment`].
+#[derive(Clone, Copy, Debug)]
+pub struct JournalRecord<'a> {
+    pub offset: usize,
+    pub timestamp: u64,
+    pub direction: Direction,
+    message: &'a [u8],
+}
+
+impl<'a> JournalRecord<'a> {
+    /// The framed message bytes exactly as they were appended.
+    #[inline]
+    pub fn bytes(&self) -> &'a [u8] {
+        self.message
+    }
+
+    #[inline]
+    pub fn message(&self) -> SbeResult<AnyMessage<'a>> {
+        AnyMessage::decode(self.message, 0)
+    }
+
+    #[inline]
+    pub fn encoded_length(&self) -> usize {
+        RECORD_HEADER_LENGTH + self.message.len()
+    }
+}
+
+/// Iterates the complete records of a segment, stopping at a partially written tail.
+#[derive(Clone, Debug)]
+pub struct Records<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Iterator for Records<'a> {
+    type Item = JournalRecord<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let remaining = self.data.get(self.offset..)?;
+        if remaining.len() < RECORD_HEADER_LENGTH {
+            return None;
+        }
+        let buf = ReadBuf::new(remaining);
+        let message_length = buf.get_u32_at(0) as usize;
+        let message = remaining.get(RECORD_HEADER_LENGTH..RECORD_HEADER_LENGTH + message_length)?;
+        let record = JournalRecord {
+            offset: self.offset,
+            timestamp: buf.get_u64_at(8),
+            direction: buf.get_u8_at(4).into(),
+            message,
+        };
+        self.offset += record.encoded_length();
+        Some(record)
+    }
+}
+
+/// The contents of one segment file, with the timestamp and offset of each of its records.
+#[derive(Clone, Debug, Default)]
+pub struct Segment {
+    data: Vec<u8>,
+    index: Vec<(u64, usize)>,
+}
+
+impl Segment {
+    fn new(data: Vec<u8>) -> Self {
+        let index = Records { data: &data, offset: 0 }.map(|record| (record.timestamp, record.offset)).collect();
+        Self { data, index }
+    }
+
+    /// Offset of the first record with a timestamp at or after `timestamp`, if any.
+    #[inline]
+    pub fn seek(&self, timestamp: u64) -> Option<usize> {
+        let first = self.index.partition_point(|&(record_timestamp, _)| record_timestamp < timestamp);
+        self.index.get(first).map(|&(_, offset)| offset)
+    }
+
+    #[inline]
+    pub fn records(&self) -> Records<'_> {
+        self.records_from(0)
+    }
+
+    /// Records starting at `offset`, which must be the offset of a record.
+    #[inline]
+    pub fn records_from(&self, offset: usize) -> Records<'_> {
+        Records { data: &self.data, offset }
+    }
+}
+
+/// Reads the segments of a journal directory.
+#[derive(Clone, Debug)]
+pub struct JournalReader {
+    segments: Vec<SegmentFile>,
+}
+
+impl JournalReader {
+    pub fn open<P: AsRef<Path>>(dir: P) -> io::Result<Self> {
+        Ok(Self { segments: list_segments(dir.as_ref())? })
+    }
+
+    #[inline]
+    pub fn segment_count(&self) -> usize {
+        self.segments.len()
+    }
+
+    /// Receive timestamp of the first record of `segment`.
+    #[inline]
+    pub fn segment_first_timestamp(&self, segment: usize) -> Option<u64> {
+        self.segments.get(segment).map(|segment| segment.first_timestamp)
+    }
+
+    pub fn read_segment(&self, segment: usize) -> io::Result<Segment> {
+        let file = self.segments.get(segment)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no such journal segment"))?;
+        Ok(Segment::new(fs::read(&file.path)?))
+    }
+
+    /// Position of the first record with a timestamp at or after `timestamp`, if any.
+    ///
+    /// Records with the same timestamp may span segments, so the search starts in the last
+    /// segment whose first record is strictly earlier than `timestamp`.
+    pub fn seek(&self, timestamp: u64) -> io::Result<Option<JournalPosition>> {
+        let first_candidate = self.segments
+            .partition_point(|segment| segment.first_timestamp < timestamp)
+            .saturating_sub(1);
+        for segment in first_candidate..self.segments.len() {
+            if let Some(offset) = self.read_segment(segment)?.seek(timestamp) {
+                return Ok(Some(JournalPosition { segment, offset }));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Calls `f` for every record from `position` to the end of the journal.
+    pub fn replay_from<F>(&self, position: JournalPosition, mut f: F) -> io::Result<()>
+    where
+        F: FnMut(&JournalRecord<'_>),
+    {
+        for segment in position.segment..self.segments.len() {
+            let data = self.read_segment(segment)?;
+            let offset = if segment == position.segment { position.offset } else { 0 };
+            data.records_from(offset).for_each(|record| f(&record));
+        }
+        Ok(())
+    }
+
+    /// Calls `f` for every record in the journal.
+    #[inline]
+    pub fn replay<F>(&self, f: F) -> io::Result<()>
+    where
+        F: FnMut(&JournalRecord<'_>),
+    {
+        self.replay_from(JournalPosition::default(), f)
+    }
+}
@@ -6,27 +6,39 @@ pub use decoder::*;
 pub const SBE_BLOCK_LENGTH: u16 = 32;
 pub const SBE_TEMPLATE_ID: u16 = 6;
 pub const SBE_SCHEMA_ID: u16 = 1;
-pub const SBE_SCHEMA_VERSION: u16 = 0;
+pub const SBE_SCHEMA_VERSION: u16 = 4;
+
+/// names of the REQUIRED fields, in the bit order of the encoder's set-field mask
+pub const REQUIRED_FIELDS: &[&str] = &[
+    "compId",
+    "instrumentId",
+    "quantity",
+    "price",
+    "transactTime",
+];
 
 pub mod encoder {
     use super::*;
 
     #[derive(Debug, Default)]
-    pub struct LastMarketTradeEncoder<'a> {
-        buf: WriteBuf<'a>,
+    pub struct LastMarketTradeEncoder<'a, O: ByteOrder = SchemaByteOrder> {
+        buf: WriteBuf<'a, O>,
         initial_offset: usize,
         offset: usize,
         limit: usize,
+        set_fields: u64,
     }
 
-    impl<'a> Writer<'a> for LastMarketTradeEncoder<'a> {
+    impl<'a, O: ByteOrder> Writer<'a> for LastMarketTradeEncoder<'a, O> {
+        type ByteOrder = O;
+
         #[inline]
-        fn get_buf_mut(&mut self) -> &mut WriteBuf<'a> {
+        fn get_buf_mut(&mut self) -> &mut WriteBuf<'a, O> {
             &mut self.buf
         }
     }
 
-    impl<'a> Encoder<'a> for LastMarketTradeEncoder<'a> {
+    impl<'a, O: ByteOrder> Encoder<'a> for LastMarketTradeEncoder<'a, O> {
         #[inline]
         fn get_limit(&self) -> usize {
             self.limit
@@ -38,13 +50,14 @@ pub mod encoder {
         }
     }
 
-    impl<'a> LastMarketTradeEncoder<'a> {
-        pub fn wrap(mut self, buf: WriteBuf<'a>, offset: usize) -> Self {
+    impl<'a, O: ByteOrder> LastMarketTradeEncoder<'a, O> {
+        pub fn wrap(mut self, buf: WriteBuf<'a, O>, offset: usize) -> Self {
             let limit = offset + SBE_BLOCK_LENGTH as usize;
             self.buf = buf;
             self.initial_offset = offset;
             self.offset = offset;
             self.limit = limit;
+            self.set_fields = 0;
             self
         }
 
@@ -62,6 +75,14 @@ pub mod encoder {
             header
         }
 
+        /// Returns the encoded length and the buffer, or the first REQUIRED field that was never set.
+        pub fn finish(self) -> SbeResult<(usize, WriteBuf<'a, O>)> {
+            if let Some(index) = (0..REQUIRED_FIELDS.len()).find(|index| self.set_fields & (1 << index) == 0) {
+                return Err(SbeErr::MissingRequiredField(REQUIRED_FIELDS[index]));
+            }
+            Ok((self.encoded_length(), self.buf))
+        }
+
         /// primitive field 'compId'
         /// - min value: 0
         /// - max value: 4294967294
@@ -72,6 +93,7 @@ pub mod encoder {
         /// - encodedLength: 4
         #[inline]
         pub fn comp_id(&mut self, value: u32) {
+            self.set_fields |= 1 << 0;
             let offset = self.offset;
             self.get_buf_mut().put_u32_at(offset, value);
         }
@@ -86,6 +108,7 @@ pub mod encoder {
         /// - encodedLength: 4
         #[inline]
         pub fn instrument_id(&mut self, value: u32) {
+            self.set_fields |= 1 << 1;
             let offset = self.offset + 4;
             self.get_buf_mut().put_u32_at(offset, value);
         }
@@ -100,6 +123,7 @@ pub mod encoder {
         /// - encodedLength: 8
         #[inline]
         pub fn quantity(&mut self, value: f64) {
+            self.set_fields |= 1 << 2;
             let offset = self.offset + 8;
             self.get_buf_mut().put_f64_at(offset, value);
         }
@@ -114,6 +138,7 @@ pub mod encoder {
         /// - encodedLength: 8
         #[inline]
         pub fn price(&mut self, value: f64) {
+            self.set_fields |= 1 << 3;
             let offset = self.offset + 16;
             self.get_buf_mut().put_f64_at(offset, value);
         }
@@ -128,6 +153,7 @@ pub mod encoder {
         /// - encodedLength: 8
         #[inline]
         pub fn transact_time(&mut self, value: u64) {
+            self.set_fields |= 1 << 4;
             let offset = self.offset + 24;
             self.get_buf_mut().put_u64_at(offset, value);
         }
@@ -140,8 +166,8 @@ pub mod decoder {
     use super::*;
 
     #[derive(Debug, Default)]
-    pub struct LastMarketTradeDecoder<'a> {
-        buf: ReadBuf<'a>,
+    pub struct LastMarketTradeDecoder<'a, O: ByteOrder = SchemaByteOrder> {
+        buf: ReadBuf<'a, O>,
         initial_offset: usize,
         offset: usize,
         limit: usize,
@@ -149,14 +175,16 @@ pub mod decoder {
         pub acting_version: u16,
     }
 
-    impl<'a> Reader<'a> for LastMarketTradeDecoder<'a> {
+    impl<'a, O: ByteOrder> Reader<'a> for LastMarketTradeDecoder<'a, O> {
+        type ByteOrder = O;
+
         #[inline]
-        fn get_buf(&self) -> &ReadBuf<'a> {
+        fn get_buf(&self) -> &ReadBuf<'a, O> {
             &self.buf
         }
     }
 
-    impl<'a> Decoder<'a> for LastMarketTradeDecoder<'a> {
+    impl<'a, O: ByteOrder> Decoder<'a> for LastMarketTradeDecoder<'a, O> {
         #[inline]
         fn get_limit(&self) -> usize {
             self.limit
@@ -168,10 +196,10 @@ pub mod decoder {
         }
     }
 
-    impl<'a> LastMarketTradeDecoder<'a> {
+    impl<'a, O: ByteOrder> LastMarketTradeDecoder<'a, O> {
         pub fn wrap(
             mut self,
-            buf: ReadBuf<'a>,
+            buf: ReadBuf<'a, O>,
             offset: usize,
             acting_block_length: u16,
             acting_version: u16,
@@ -191,7 +219,7 @@ pub mod decoder {
             self.limit - self.offset
         }
 
-        pub fn header(self, mut header: MessageHeaderDecoder<ReadBuf<'a>>) -> Self {
+        pub fn header(self, mut header: MessageHeaderDecoder<ReadBuf<'a, O>>) -> Self {
             debug_assert_eq!(SBE_TEMPLATE_ID, header.template_id());
             let acting_block_length = header.block_length();
             let acting_version = header.version();
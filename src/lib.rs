@@ -1,7 +1,17 @@
-#![forbid(unsafe_code)]
+#![cfg_attr(not(feature = "std"), no_std)]
+// Mapping the shared memory ring needs `unsafe`, so with the `shm` feature `transport::shm` alone
+// opts back in.
+#![cfg_attr(not(feature = "shm"), forbid(unsafe_code))]
+#![cfg_attr(feature = "shm", deny(unsafe_code))]
 #![allow(clippy::upper_case_acronyms)]
 #![allow(non_camel_case_types)]
-use ::core::{convert::TryInto};
+// Every codec module has its own `SBE_TEMPLATE_ID`, `SBE_BLOCK_LENGTH`, ... constants, which the
+// glob re-exports below leave ambiguous at the crate root; they are used through the module path.
+#![allow(ambiguous_glob_reexports)]
+use ::core::{convert::TryInto, marker::PhantomData};
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
 
 pub mod strategy;
 pub mod logon_request_codec;
@@ -10,6 +20,7 @@ pub mod cancel_reject_response_to;
 pub mod execution_report_codec;
 pub mod price_increment_codec;
 pub mod small_group_size_encoding_codec;
+pub mod group_size_encoding_codec;
 pub mod time_in_force;
 pub mod logon_response_codec;
 pub mod new_order_single_codec;
@@ -29,6 +40,42 @@ pub mod price_snapshot_request_codec;
 pub mod last_market_trade_codec;
 pub mod order_side;
 pub mod order_cancel_reject_codec;
+pub mod var_string_encoding_codec;
+pub mod instrument_status;
+pub mod instrument_definition_codec;
+pub mod subscription_request_type;
+pub mod market_data_request_reject_reason;
+pub mod market_data_request_codec;
+pub mod market_data_request_reject_codec;
+pub mod market_data_request_ack_codec;
+pub mod boolean_type;
+pub mod mass_status_req_type;
+pub mod order_status_request_codec;
+pub mod order_mass_status_request_codec;
+pub mod mass_cancel_request_type;
+pub mod mass_cancel_response;
+pub mod mass_cancel_reject_reason;
+pub mod order_mass_cancel_request_codec;
+pub mod order_mass_cancel_report_codec;
+pub mod business_reject_reason;
+pub mod business_message_reject_codec;
+pub mod packet_header_codec;
+pub mod any_message;
+pub mod diff;
+pub mod message_writer;
+pub mod packet;
+#[cfg(feature = "std")]
+pub mod journal;
+#[cfg(feature = "std")]
+pub mod replay;
+#[cfg(feature = "std")]
+pub mod simulator;
+#[cfg(feature = "std")]
+pub mod instrument_registry;
+#[cfg(feature = "std")]
+pub mod transport;
+#[cfg(feature = "tokio")]
+pub mod tokio_codec;
 
 pub use strategy::*;
 pub use logon_request_codec::*;
@@ -37,6 +84,7 @@ pub use cancel_reject_response_to::*;
 pub use execution_report_codec::*;
 pub use price_increment_codec::*;
 pub use small_group_size_encoding_codec::*;
+pub use group_size_encoding_codec::*;
 pub use time_in_force::*;
 pub use logon_response_codec::*;
 pub use new_order_single_codec::*;
@@ -56,12 +104,51 @@ pub use price_snapshot_request_codec::*;
 pub use last_market_trade_codec::*;
 pub use order_side::*;
 pub use order_cancel_reject_codec::*;
+pub use var_string_encoding_codec::*;
+pub use instrument_status::*;
+pub use instrument_definition_codec::*;
+pub use subscription_request_type::*;
+pub use market_data_request_reject_reason::*;
+pub use market_data_request_codec::*;
+pub use market_data_request_reject_codec::*;
+pub use market_data_request_ack_codec::*;
+pub use boolean_type::*;
+pub use mass_status_req_type::*;
+pub use order_status_request_codec::*;
+pub use order_mass_status_request_codec::*;
+pub use mass_cancel_request_type::*;
+pub use mass_cancel_response::*;
+pub use mass_cancel_reject_reason::*;
+pub use order_mass_cancel_request_codec::*;
+pub use order_mass_cancel_report_codec::*;
+pub use business_reject_reason::*;
+pub use business_message_reject_codec::*;
+pub use packet_header_codec::*;
+pub use any_message::*;
+pub use diff::*;
+pub use message_writer::*;
+pub use packet::*;
+
+/// Byte order declared by the schema's `byteOrder` attribute; the default of [`ReadBuf`] and [`WriteBuf`].
+pub type SchemaByteOrder = LittleEndian;
 
 pub type SbeResult<T> = core::result::Result<T, SbeErr>;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum SbeErr {
     ParentNotSet,
+    BufferTooShort,
+    UnknownSchemaId(u16),
+    UnknownTemplateId(u16),
+    /// Message header block length below the smallest one of the message's acting version.
+    BlockLengthTooShort,
+    MissingRequiredField(&'static str),
+    /// A field the encoder can only write once, such as var data, was written again.
+    DuplicateField(&'static str),
+    GroupCountOverflow,
+    VarDataTooLong,
+    /// Bytes left over in a packet after its last message.
+    TrailingBytes,
 }
 impl core::fmt::Display for SbeErr {
     #[inline]
@@ -69,8 +156,39 @@ impl core::fmt::Display for SbeErr {
         write!(f, "{:?}", self)
     }
 }
+#[cfg(feature = "std")]
 impl std::error::Error for SbeErr {}
 
+impl SbeErr {
+    /// Reason and text of the `BusinessMessageReject` answering a message that failed with this error.
+    pub fn business_reject(&self) -> (BusinessRejectReason, &'static str) {
+        match self {
+            Self::UnknownSchemaId(_) => (BusinessRejectReason::UNSUPPORTED_MESSAGE_TYPE, "unknown schema id"),
+            Self::UnknownTemplateId(_) => (BusinessRejectReason::UNSUPPORTED_MESSAGE_TYPE, "unknown template id"),
+            Self::MissingRequiredField(field) => (BusinessRejectReason::REQUIRED_FIELD_MISSING, field),
+            Self::BufferTooShort => (BusinessRejectReason::MALFORMED_MESSAGE, "message is truncated"),
+            Self::BlockLengthTooShort => (BusinessRejectReason::MALFORMED_MESSAGE, "block length too short"),
+            Self::GroupCountOverflow => (BusinessRejectReason::MALFORMED_MESSAGE, "group count overflow"),
+            Self::VarDataTooLong => (BusinessRejectReason::MALFORMED_MESSAGE, "var data too long"),
+            Self::TrailingBytes => (BusinessRejectReason::MALFORMED_MESSAGE, "trailing bytes"),
+            Self::DuplicateField(_) => (BusinessRejectReason::OTHER, "duplicate field"),
+            Self::ParentNotSet => (BusinessRejectReason::OTHER, "parent not set"),
+        }
+    }
+}
+
+/// `value` padded with the null character to a fixed-length char array; `None` when it is longer.
+pub fn to_char_array<const N: usize>(value: &[u8]) -> Option<[u8; N]> {
+    let mut chars = [0; N];
+    chars.get_mut(..value.len())?.copy_from_slice(value);
+    Some(chars)
+}
+
+/// Characters of a fixed-length char array up to the first null character.
+pub fn trim_char_array(value: &[u8]) -> &[u8] {
+    value.split(|&c| c == 0).next().unwrap_or(value)
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Either<L, R> {
     Left(L),
@@ -78,7 +196,10 @@ pub enum Either<L, R> {
 }
 
 pub trait Writer<'a>: Sized {
-    fn get_buf_mut(&mut self) -> &mut WriteBuf<'a>;
+    /// Byte order of the underlying [`WriteBuf`].
+    type ByteOrder: ByteOrder;
+
+    fn get_buf_mut(&mut self) -> &mut WriteBuf<'a, Self::ByteOrder>;
 }
 
 pub trait Encoder<'a>: Writer<'a> {
@@ -87,7 +208,10 @@ pub trait Encoder<'a>: Writer<'a> {
 }
 
 pub trait Reader<'a>: Sized {
-    fn get_buf(&self) -> &ReadBuf<'a>;
+    /// Byte order of the underlying [`ReadBuf`].
+    type ByteOrder: ByteOrder;
+
+    fn get_buf(&self) -> &ReadBuf<'a, Self::ByteOrder>;
 }
 
 pub trait Decoder<'a>: Reader<'a> {
@@ -95,20 +219,47 @@ pub trait Decoder<'a>: Reader<'a> {
     fn set_limit(&mut self, limit: usize);
 }
 
+/// Byte order of multi-byte primitives on the wire.
+pub trait ByteOrder: Clone + Copy + core::fmt::Debug + Default {
+    const BIG_ENDIAN: bool;
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct LittleEndian;
+impl ByteOrder for LittleEndian {
+    const BIG_ENDIAN: bool = false;
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BigEndian;
+impl ByteOrder for BigEndian {
+    const BIG_ENDIAN: bool = true;
+}
+
 #[derive(Debug, Default)]
-pub struct ReadBuf<'a> {
+pub struct ReadBuf<'a, O: ByteOrder = SchemaByteOrder> {
     data: &'a [u8],
+    byte_order: PhantomData<O>,
 }
-impl<'a> Reader<'a> for ReadBuf<'a> {
+impl<'a, O: ByteOrder> Reader<'a> for ReadBuf<'a, O> {
+    type ByteOrder = O;
+
     #[inline]
-    fn get_buf(&self) -> &ReadBuf<'a> {
+    fn get_buf(&self) -> &ReadBuf<'a, O> {
         self
     }
 }
 impl<'a> ReadBuf<'a> {
     #[inline]
     pub fn new(data: &'a [u8]) -> Self {
-        Self { data }
+        Self::with_byte_order(data, SchemaByteOrder::default())
+    }
+}
+impl<'a, O: ByteOrder> ReadBuf<'a, O> {
+    /// Reads `data` in byte order `O` rather than the schema's.
+    #[inline]
+    pub fn with_byte_order(data: &'a [u8], _byte_order: O) -> Self {
+        Self { data, byte_order: PhantomData }
     }
 
     #[inline]
@@ -121,54 +272,70 @@ impl<'a> ReadBuf<'a> {
         Self::get_bytes(slice.split_at(index).1.split_at(COUNT).0)
     }
 
+    /// Borrows the `COUNT` bytes at `index`, e.g. a fixed-length char array.
+    #[inline]
+    pub fn get_array_at<const COUNT: usize>(&self, index: usize) -> &'a [u8; COUNT] {
+        self.data[index..index + COUNT].try_into().expect("slice with incorrect length")
+    }
+
     #[inline]
     pub fn get_u8_at(&self, index: usize) -> u8 {
-        u8::from_le_bytes(Self::get_bytes_at(self.data, index))
+        let bytes = Self::get_bytes_at(self.data, index);
+        if O::BIG_ENDIAN { u8::from_be_bytes(bytes) } else { u8::from_le_bytes(bytes) }
     }
 
     #[inline]
     pub fn get_i8_at(&self, index: usize) -> i8 {
-        i8::from_le_bytes(Self::get_bytes_at(self.data, index))
+        let bytes = Self::get_bytes_at(self.data, index);
+        if O::BIG_ENDIAN { i8::from_be_bytes(bytes) } else { i8::from_le_bytes(bytes) }
     }
 
     #[inline]
     pub fn get_i16_at(&self, index: usize) -> i16 {
-        i16::from_le_bytes(Self::get_bytes_at(self.data, index))
+        let bytes = Self::get_bytes_at(self.data, index);
+        if O::BIG_ENDIAN { i16::from_be_bytes(bytes) } else { i16::from_le_bytes(bytes) }
     }
 
     #[inline]
     pub fn get_i32_at(&self, index: usize) -> i32 {
-        i32::from_le_bytes(Self::get_bytes_at(self.data, index))
+        let bytes = Self::get_bytes_at(self.data, index);
+        if O::BIG_ENDIAN { i32::from_be_bytes(bytes) } else { i32::from_le_bytes(bytes) }
     }
 
     #[inline]
     pub fn get_i64_at(&self, index: usize) -> i64 {
-        i64::from_le_bytes(Self::get_bytes_at(self.data, index))
+        let bytes = Self::get_bytes_at(self.data, index);
+        if O::BIG_ENDIAN { i64::from_be_bytes(bytes) } else { i64::from_le_bytes(bytes) }
     }
 
     #[inline]
     pub fn get_u16_at(&self, index: usize) -> u16 {
-        u16::from_le_bytes(Self::get_bytes_at(self.data, index))
+        let bytes = Self::get_bytes_at(self.data, index);
+        if O::BIG_ENDIAN { u16::from_be_bytes(bytes) } else { u16::from_le_bytes(bytes) }
     }
 
     #[inline]
     pub fn get_u32_at(&self, index: usize) -> u32 {
-        u32::from_le_bytes(Self::get_bytes_at(self.data, index))
+        let bytes = Self::get_bytes_at(self.data, index);
+        if O::BIG_ENDIAN { u32::from_be_bytes(bytes) } else { u32::from_le_bytes(bytes) }
     }
 
     #[inline]
     pub fn get_u64_at(&self, index: usize) -> u64 {
-        u64::from_le_bytes(Self::get_bytes_at(self.data, index))
+        let bytes = Self::get_bytes_at(self.data, index);
+        if O::BIG_ENDIAN { u64::from_be_bytes(bytes) } else { u64::from_le_bytes(bytes) }
     }
 
     #[inline]
     pub fn get_f32_at(&self, index: usize) -> f32 {
-        f32::from_le_bytes(Self::get_bytes_at(self.data, index))
+        let bytes = Self::get_bytes_at(self.data, index);
+        if O::BIG_ENDIAN { f32::from_be_bytes(bytes) } else { f32::from_le_bytes(bytes) }
     }
 
     #[inline]
     pub fn get_f64_at(&self, index: usize) -> f64 {
-        f64::from_le_bytes(Self::get_bytes_at(self.data, index))
+        let bytes = Self::get_bytes_at(self.data, index);
+        if O::BIG_ENDIAN { f64::from_be_bytes(bytes) } else { f64::from_le_bytes(bytes) }
     }
 
     #[inline]
@@ -179,12 +346,28 @@ impl<'a> ReadBuf<'a> {
 }
 
 #[derive(Debug, Default)]
-pub struct WriteBuf<'a> {
+pub struct WriteBuf<'a, O: ByteOrder = SchemaByteOrder> {
     data: &'a mut [u8],
+    byte_order: PhantomData<O>,
+}
+impl<'a, O: ByteOrder> Writer<'a> for WriteBuf<'a, O> {
+    type ByteOrder = O;
+
+    #[inline]
+    fn get_buf_mut(&mut self) -> &mut WriteBuf<'a, O> {
+        self
+    }
 }
 impl<'a> WriteBuf<'a> {
     pub fn new(data: &'a mut [u8]) -> Self {
-        Self { data }
+        Self::with_byte_order(data, SchemaByteOrder::default())
+    }
+}
+impl<'a, O: ByteOrder> WriteBuf<'a, O> {
+    /// Writes into `data` in byte order `O` rather than the schema's.
+    #[inline]
+    pub fn with_byte_order(data: &'a mut [u8], _byte_order: O) -> Self {
+        Self { data, byte_order: PhantomData }
     }
 
     #[inline]
@@ -197,52 +380,62 @@ impl<'a> WriteBuf<'a> {
 
     #[inline]
     pub fn put_u8_at(&mut self, index: usize, value: u8) {
-        self.put_bytes_at(index, u8::to_le_bytes(value));
+        let bytes = if O::BIG_ENDIAN { u8::to_be_bytes(value) } else { u8::to_le_bytes(value) };
+        self.put_bytes_at(index, bytes);
     }
 
     #[inline]
     pub fn put_i8_at(&mut self, index: usize, value: i8) {
-        self.put_bytes_at(index, i8::to_le_bytes(value));
+        let bytes = if O::BIG_ENDIAN { i8::to_be_bytes(value) } else { i8::to_le_bytes(value) };
+        self.put_bytes_at(index, bytes);
     }
 
     #[inline]
     pub fn put_i16_at(&mut self, index: usize, value: i16) {
-        self.put_bytes_at(index, i16::to_le_bytes(value));
+        let bytes = if O::BIG_ENDIAN { i16::to_be_bytes(value) } else { i16::to_le_bytes(value) };
+        self.put_bytes_at(index, bytes);
     }
 
     #[inline]
     pub fn put_i32_at(&mut self, index: usize, value: i32) {
-        self.put_bytes_at(index, i32::to_le_bytes(value));
+        let bytes = if O::BIG_ENDIAN { i32::to_be_bytes(value) } else { i32::to_le_bytes(value) };
+        self.put_bytes_at(index, bytes);
     }
 
     #[inline]
     pub fn put_i64_at(&mut self, index: usize, value: i64) {
-        self.put_bytes_at(index, i64::to_le_bytes(value));
+        let bytes = if O::BIG_ENDIAN { i64::to_be_bytes(value) } else { i64::to_le_bytes(value) };
+        self.put_bytes_at(index, bytes);
     }
 
     #[inline]
     pub fn put_u16_at(&mut self, index: usize, value: u16) {
-        self.put_bytes_at(index, u16::to_le_bytes(value));
+        let bytes = if O::BIG_ENDIAN { u16::to_be_bytes(value) } else { u16::to_le_bytes(value) };
+        self.put_bytes_at(index, bytes);
     }
 
     #[inline]
     pub fn put_u32_at(&mut self, index: usize, value: u32) {
-        self.put_bytes_at(index, u32::to_le_bytes(value));
+        let bytes = if O::BIG_ENDIAN { u32::to_be_bytes(value) } else { u32::to_le_bytes(value) };
+        self.put_bytes_at(index, bytes);
     }
 
     #[inline]
     pub fn put_u64_at(&mut self, index: usize, value: u64) {
-        self.put_bytes_at(index, u64::to_le_bytes(value));
+        let bytes = if O::BIG_ENDIAN { u64::to_be_bytes(value) } else { u64::to_le_bytes(value) };
+        self.put_bytes_at(index, bytes);
     }
 
     #[inline]
     pub fn put_f32_at(&mut self, index: usize, value: f32) {
-        self.put_bytes_at(index, f32::to_le_bytes(value));
+        let bytes = if O::BIG_ENDIAN { f32::to_be_bytes(value) } else { f32::to_le_bytes(value) };
+        self.put_bytes_at(index, bytes);
     }
 
     #[inline]
     pub fn put_f64_at(&mut self, index: usize, value: f64) {
-        self.put_bytes_at(index, f64::to_le_bytes(value));
+        let bytes = if O::BIG_ENDIAN { f64::to_be_bytes(value) } else { f64::to_le_bytes(value) };
+        self.put_bytes_at(index, bytes);
     }
 
     #[inline]
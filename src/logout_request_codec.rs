@@ -6,27 +6,37 @@ pub use decoder::*;
 pub const SBE_BLOCK_LENGTH: u16 = 16;
 pub const SBE_TEMPLATE_ID: u16 = 13;
 pub const SBE_SCHEMA_ID: u16 = 1;
-pub const SBE_SCHEMA_VERSION: u16 = 0;
+pub const SBE_SCHEMA_VERSION: u16 = 4;
+
+/// names of the REQUIRED fields, in the bit order of the encoder's set-field mask
+pub const REQUIRED_FIELDS: &[&str] = &[
+    "targetCompId",
+    "sourceCompId",
+    "transactTime",
+];
 
 pub mod encoder {
     use super::*;
 
     #[derive(Debug, Default)]
-    pub struct LogoutRequestEncoder<'a> {
-        buf: WriteBuf<'a>,
+    pub struct LogoutRequestEncoder<'a, O: ByteOrder = SchemaByteOrder> {
+        buf: WriteBuf<'a, O>,
         initial_offset: usize,
         offset: usize,
         limit: usize,
+        set_fields: u64,
     }
 
-    impl<'a> Writer<'a> for LogoutRequestEncoder<'a> {
+    impl<'a, O: ByteOrder> Writer<'a> for LogoutRequestEncoder<'a, O> {
+        type ByteOrder = O;
+
         #[inline]
-        fn get_buf_mut(&mut self) -> &mut WriteBuf<'a> {
+        fn get_buf_mut(&mut self) -> &mut WriteBuf<'a, O> {
             &mut self.buf
         }
     }
 
-    impl<'a> Encoder<'a> for LogoutRequestEncoder<'a> {
+    impl<'a, O: ByteOrder> Encoder<'a> for LogoutRequestEncoder<'a, O> {
         #[inline]
         fn get_limit(&self) -> usize {
             self.limit
@@ -38,13 +48,14 @@ pub mod encoder {
         }
     }
 
-    impl<'a> LogoutRequestEncoder<'a> {
-        pub fn wrap(mut self, buf: WriteBuf<'a>, offset: usize) -> Self {
+    impl<'a, O: ByteOrder> LogoutRequestEncoder<'a, O> {
+        pub fn wrap(mut self, buf: WriteBuf<'a, O>, offset: usize) -> Self {
             let limit = offset + SBE_BLOCK_LENGTH as usize;
             self.buf = buf;
             self.initial_offset = offset;
             self.offset = offset;
             self.limit = limit;
+            self.set_fields = 0;
             self
         }
 
@@ -62,6 +73,14 @@ pub mod encoder {
             header
         }
 
+        /// Returns the encoded length and the buffer, or the first REQUIRED field that was never set.
+        pub fn finish(self) -> SbeResult<(usize, WriteBuf<'a, O>)> {
+            if let Some(index) = (0..REQUIRED_FIELDS.len()).find(|index| self.set_fields & (1 << index) == 0) {
+                return Err(SbeErr::MissingRequiredField(REQUIRED_FIELDS[index]));
+            }
+            Ok((self.encoded_length(), self.buf))
+        }
+
         /// primitive field 'targetCompId'
         /// - min value: 0
         /// - max value: 4294967294
@@ -72,6 +91,7 @@ pub mod encoder {
         /// - encodedLength: 4
         #[inline]
         pub fn target_comp_id(&mut self, value: u32) {
+            self.set_fields |= 1 << 0;
             let offset = self.offset;
             self.get_buf_mut().put_u32_at(offset, value);
         }
@@ -86,6 +106,7 @@ pub mod encoder {
         /// - encodedLength: 4
         #[inline]
         pub fn source_comp_id(&mut self, value: u32) {
+            self.set_fields |= 1 << 1;
             let offset = self.offset + 4;
             self.get_buf_mut().put_u32_at(offset, value);
         }
@@ -100,6 +121,7 @@ pub mod encoder {
         /// - encodedLength: 8
         #[inline]
         pub fn transact_time(&mut self, value: u64) {
+            self.set_fields |= 1 << 2;
             let offset = self.offset + 8;
             self.get_buf_mut().put_u64_at(offset, value);
         }
@@ -112,8 +134,8 @@ pub mod decoder {
     use super::*;
 
     #[derive(Debug, Default)]
-    pub struct LogoutRequestDecoder<'a> {
-        buf: ReadBuf<'a>,
+    pub struct LogoutRequestDecoder<'a, O: ByteOrder = SchemaByteOrder> {
+        buf: ReadBuf<'a, O>,
         initial_offset: usize,
         offset: usize,
         limit: usize,
@@ -121,14 +143,16 @@ pub mod decoder {
         pub acting_version: u16,
     }
 
-    impl<'a> Reader<'a> for LogoutRequestDecoder<'a> {
+    impl<'a, O: ByteOrder> Reader<'a> for LogoutRequestDecoder<'a, O> {
+        type ByteOrder = O;
+
         #[inline]
-        fn get_buf(&self) -> &ReadBuf<'a> {
+        fn get_buf(&self) -> &ReadBuf<'a, O> {
             &self.buf
         }
     }
 
-    impl<'a> Decoder<'a> for LogoutRequestDecoder<'a> {
+    impl<'a, O: ByteOrder> Decoder<'a> for LogoutRequestDecoder<'a, O> {
         #[inline]
         fn get_limit(&self) -> usize {
             self.limit
@@ -140,10 +164,10 @@ pub mod decoder {
         }
     }
 
-    impl<'a> LogoutRequestDecoder<'a> {
+    impl<'a, O: ByteOrder> LogoutRequestDecoder<'a, O> {
         pub fn wrap(
             mut self,
-            buf: ReadBuf<'a>,
+            buf: ReadBuf<'a, O>,
             offset: usize,
             acting_block_length: u16,
             acting_version: u16,
@@ -163,7 +187,7 @@ pub mod decoder {
             self.limit - self.offset
         }
 
-        pub fn header(self, mut header: MessageHeaderDecoder<ReadBuf<'a>>) -> Self {
+        pub fn header(self, mut header: MessageHeaderDecoder<ReadBuf<'a, O>>) -> Self {
             debug_assert_eq!(SBE_TEMPLATE_ID, header.template_id());
             let acting_block_length = header.block_length();
             let acting_version = header.version();
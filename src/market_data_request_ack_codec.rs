@@ -0,0 +1,260 @@
+use crate::*;
+
+pub use encoder::*;
+pub use decoder::*;
+
+pub const SBE_BLOCK_LENGTH: u16 = 25;
+pub const SBE_TEMPLATE_ID: u16 = 18;
+pub const SBE_SCHEMA_ID: u16 = 1;
+pub const SBE_SCHEMA_VERSION: u16 = 4;
+
+/// names of the REQUIRED fields, in the bit order of the encoder's set-field mask
+pub const REQUIRED_FIELDS: &[&str] = &[
+    "mdReqId",
+    "targetCompId",
+    "sourceCompId",
+    "subscriptionRequestType",
+    "transactTime",
+];
+
+pub mod encoder {
+    use super::*;
+
+    #[derive(Debug, Default)]
+    pub struct MarketDataRequestAckEncoder<'a, O: ByteOrder = SchemaByteOrder> {
+        buf: WriteBuf<'a, O>,
+        initial_offset: usize,
+        offset: usize,
+        limit: usize,
+        set_fields: u64,
+    }
+
+    impl<'a, O: ByteOrder> Writer<'a> for MarketDataRequestAckEncoder<'a, O> {
+        type ByteOrder = O;
+
+        #[inline]
+        fn get_buf_mut(&mut self) -> &mut WriteBuf<'a, O> {
+            &mut self.buf
+        }
+    }
+
+    impl<'a, O: ByteOrder> Encoder<'a> for MarketDataRequestAckEncoder<'a, O> {
+        #[inline]
+        fn get_limit(&self) -> usize {
+            self.limit
+        }
+
+        #[inline]
+        fn set_limit(&mut self, limit: usize) {
+            self.limit = limit;
+        }
+    }
+
+    impl<'a, O: ByteOrder> MarketDataRequestAckEncoder<'a, O> {
+        pub fn wrap(mut self, buf: WriteBuf<'a, O>, offset: usize) -> Self {
+            let limit = offset + SBE_BLOCK_LENGTH as usize;
+            self.buf = buf;
+            self.initial_offset = offset;
+            self.offset = offset;
+            self.limit = limit;
+            self.set_fields = 0;
+            self
+        }
+
+        #[inline]
+        pub fn encoded_length(&self) -> usize {
+            self.limit - self.offset
+        }
+
+        pub fn header(self, offset: usize) -> MessageHeaderEncoder<Self> {
+            let mut header = MessageHeaderEncoder::default().wrap(self, offset);
+            header.block_length(SBE_BLOCK_LENGTH);
+            header.template_id(SBE_TEMPLATE_ID);
+            header.schema_id(SBE_SCHEMA_ID);
+            header.version(SBE_SCHEMA_VERSION);
+            header
+        }
+
+        /// Returns the encoded length and the buffer, or the first REQUIRED field that was never set.
+        pub fn finish(self) -> SbeResult<(usize, WriteBuf<'a, O>)> {
+            if let Some(index) = (0..REQUIRED_FIELDS.len()).find(|index| self.set_fields & (1 << index) == 0) {
+                return Err(SbeErr::MissingRequiredField(REQUIRED_FIELDS[index]));
+            }
+            Ok((self.encoded_length(), self.buf))
+        }
+
+        /// primitive field 'mdReqId'
+        /// - min value: 0
+        /// - max value: -2
+        /// - null value: -1
+        /// - characterEncoding: null
+        /// - semanticType: null
+        /// - encodedOffset: 0
+        /// - encodedLength: 8
+        #[inline]
+        pub fn md_req_id(&mut self, value: u64) {
+            self.set_fields |= 1 << 0;
+            let offset = self.offset;
+            self.get_buf_mut().put_u64_at(offset, value);
+        }
+
+        /// primitive field 'targetCompId'
+        /// - min value: 0
+        /// - max value: 4294967294
+        /// - null value: 4294967295
+        /// - characterEncoding: null
+        /// - semanticType: null
+        /// - encodedOffset: 8
+        /// - encodedLength: 4
+        #[inline]
+        pub fn target_comp_id(&mut self, value: u32) {
+            self.set_fields |= 1 << 1;
+            let offset = self.offset + 8;
+            self.get_buf_mut().put_u32_at(offset, value);
+        }
+
+        /// primitive field 'sourceCompId'
+        /// - min value: 0
+        /// - max value: 4294967294
+        /// - null value: 4294967295
+        /// - characterEncoding: null
+        /// - semanticType: null
+        /// - encodedOffset: 12
+        /// - encodedLength: 4
+        #[inline]
+        pub fn source_comp_id(&mut self, value: u32) {
+            self.set_fields |= 1 << 2;
+            let offset = self.offset + 12;
+            self.get_buf_mut().put_u32_at(offset, value);
+        }
+
+        /// REQUIRED enum
+        #[inline]
+        pub fn subscription_request_type(&mut self, value: SubscriptionRequestType) {
+            self.set_fields |= 1 << 3;
+            let offset = self.offset + 16;
+            self.get_buf_mut().put_u8_at(offset, value as u8)
+        }
+
+        /// primitive field 'transactTime'
+        /// - min value: 0
+        /// - max value: -2
+        /// - null value: -1
+        /// - characterEncoding: null
+        /// - semanticType: null
+        /// - encodedOffset: 17
+        /// - encodedLength: 8
+        #[inline]
+        pub fn transact_time(&mut self, value: u64) {
+            self.set_fields |= 1 << 4;
+            let offset = self.offset + 17;
+            self.get_buf_mut().put_u64_at(offset, value);
+        }
+
+    }
+
+} // end encoder
+
+pub mod decoder {
+    use super::*;
+
+    #[derive(Debug, Default)]
+    pub struct MarketDataRequestAckDecoder<'a, O: ByteOrder = SchemaByteOrder> {
+        buf: ReadBuf<'a, O>,
+        initial_offset: usize,
+        offset: usize,
+        limit: usize,
+        pub acting_block_length: u16,
+        pub acting_version: u16,
+    }
+
+    impl<'a, O: ByteOrder> Reader<'a> for MarketDataRequestAckDecoder<'a, O> {
+        type ByteOrder = O;
+
+        #[inline]
+        fn get_buf(&self) -> &ReadBuf<'a, O> {
+            &self.buf
+        }
+    }
+
+    impl<'a, O: ByteOrder> Decoder<'a> for MarketDataRequestAckDecoder<'a, O> {
+        #[inline]
+        fn get_limit(&self) -> usize {
+            self.limit
+        }
+
+        #[inline]
+        fn set_limit(&mut self, limit: usize) {
+            self.limit = limit;
+        }
+    }
+
+    impl<'a, O: ByteOrder> MarketDataRequestAckDecoder<'a, O> {
+        pub fn wrap(
+            mut self,
+            buf: ReadBuf<'a, O>,
+            offset: usize,
+            acting_block_length: u16,
+            acting_version: u16,
+        ) -> Self {
+            let limit = offset + acting_block_length as usize;
+            self.buf = buf;
+            self.initial_offset = offset;
+            self.offset = offset;
+            self.limit = limit;
+            self.acting_block_length = acting_block_length;
+            self.acting_version = acting_version;
+            self
+        }
+
+        #[inline]
+        pub fn encoded_length(&self) -> usize {
+            self.limit - self.offset
+        }
+
+        pub fn header(self, mut header: MessageHeaderDecoder<ReadBuf<'a, O>>) -> Self {
+            debug_assert_eq!(SBE_TEMPLATE_ID, header.template_id());
+            let acting_block_length = header.block_length();
+            let acting_version = header.version();
+
+            self.wrap(
+                header.parent().unwrap(),
+                message_header_codec::ENCODED_LENGTH,
+                acting_block_length,
+                acting_version,
+            )
+        }
+
+        /// primitive field - 'REQUIRED'
+        #[inline]
+        pub fn md_req_id(&self) -> u64 {
+            self.get_buf().get_u64_at(self.offset)
+        }
+
+        /// primitive field - 'REQUIRED'
+        #[inline]
+        pub fn target_comp_id(&self) -> u32 {
+            self.get_buf().get_u32_at(self.offset + 8)
+        }
+
+        /// primitive field - 'REQUIRED'
+        #[inline]
+        pub fn source_comp_id(&self) -> u32 {
+            self.get_buf().get_u32_at(self.offset + 12)
+        }
+
+        /// REQUIRED enum
+        #[inline]
+        pub fn subscription_request_type(&self) -> SubscriptionRequestType {
+            self.get_buf().get_u8_at(self.offset + 16).into()
+        }
+
+        /// primitive field - 'REQUIRED'
+        #[inline]
+        pub fn transact_time(&self) -> u64 {
+            self.get_buf().get_u64_at(self.offset + 17)
+        }
+
+    }
+
+} // end decoder
@@ -0,0 +1,348 @@
+use crate::*;
+
+pub use encoder::*;
+pub use decoder::*;
+
+pub const SBE_BLOCK_LENGTH: u16 = 27;
+pub const SBE_TEMPLATE_ID: u16 = 16;
+pub const SBE_SCHEMA_ID: u16 = 1;
+pub const SBE_SCHEMA_VERSION: u16 = 4;
+/// encoded length of an 'instruments' entry, a single 'instrumentId'
+pub const INSTRUMENTS_ENTRY_LENGTH: usize = 4;
+
+/// names of the REQUIRED fields, in the bit order of the encoder's set-field mask
+pub const REQUIRED_FIELDS: &[&str] = &[
+    "mdReqId",
+    "targetCompId",
+    "sourceCompId",
+    "subscriptionRequestType",
+    "marketDepth",
+    "transactTime",
+];
+
+pub mod encoder {
+    use super::*;
+
+    #[derive(Debug, Default)]
+    pub struct MarketDataRequestEncoder<'a, O: ByteOrder = SchemaByteOrder> {
+        buf: WriteBuf<'a, O>,
+        initial_offset: usize,
+        offset: usize,
+        limit: usize,
+        set_fields: u64,
+    }
+
+    impl<'a, O: ByteOrder> Writer<'a> for MarketDataRequestEncoder<'a, O> {
+        type ByteOrder = O;
+
+        #[inline]
+        fn get_buf_mut(&mut self) -> &mut WriteBuf<'a, O> {
+            &mut self.buf
+        }
+    }
+
+    impl<'a, O: ByteOrder> Encoder<'a> for MarketDataRequestEncoder<'a, O> {
+        #[inline]
+        fn get_limit(&self) -> usize {
+            self.limit
+        }
+
+        #[inline]
+        fn set_limit(&mut self, limit: usize) {
+            self.limit = limit;
+        }
+    }
+
+    impl<'a, O: ByteOrder> MarketDataRequestEncoder<'a, O> {
+        pub fn wrap(mut self, buf: WriteBuf<'a, O>, offset: usize) -> Self {
+            let limit = offset + SBE_BLOCK_LENGTH as usize;
+            self.buf = buf;
+            self.initial_offset = offset;
+            self.offset = offset;
+            self.limit = limit;
+            self.set_fields = 0;
+            self
+        }
+
+        #[inline]
+        pub fn encoded_length(&self) -> usize {
+            self.limit - self.offset
+        }
+
+        pub fn header(self, offset: usize) -> MessageHeaderEncoder<Self> {
+            let mut header = MessageHeaderEncoder::default().wrap(self, offset);
+            header.block_length(SBE_BLOCK_LENGTH);
+            header.template_id(SBE_TEMPLATE_ID);
+            header.schema_id(SBE_SCHEMA_ID);
+            header.version(SBE_SCHEMA_VERSION);
+            header
+        }
+
+        /// Returns the encoded length and the buffer, or the first REQUIRED field that was never set.
+        pub fn finish(self) -> SbeResult<(usize, WriteBuf<'a, O>)> {
+            if let Some(index) = (0..REQUIRED_FIELDS.len()).find(|index| self.set_fields & (1 << index) == 0) {
+                return Err(SbeErr::MissingRequiredField(REQUIRED_FIELDS[index]));
+            }
+            Ok((self.encoded_length(), self.buf))
+        }
+
+        /// primitive field 'mdReqId'
+        /// - min value: 0
+        /// - max value: -2
+        /// - null value: -1
+        /// - characterEncoding: null
+        /// - semanticType: null
+        /// - encodedOffset: 0
+        /// - encodedLength: 8
+        #[inline]
+        pub fn md_req_id(&mut self, value: u64) {
+            self.set_fields |= 1 << 0;
+            let offset = self.offset;
+            self.get_buf_mut().put_u64_at(offset, value);
+        }
+
+        /// primitive field 'targetCompId'
+        /// - min value: 0
+        /// - max value: 4294967294
+        /// - null value: 4294967295
+        /// - characterEncoding: null
+        /// - semanticType: null
+        /// - encodedOffset: 8
+        /// - encodedLength: 4
+        #[inline]
+        pub fn target_comp_id(&mut self, value: u32) {
+            self.set_fields |= 1 << 1;
+            let offset = self.offset + 8;
+            self.get_buf_mut().put_u32_at(offset, value);
+        }
+
+        /// primitive field 'sourceCompId'
+        /// - min value: 0
+        /// - max value: 4294967294
+        /// - null value: 4294967295
+        /// - characterEncoding: null
+        /// - semanticType: null
+        /// - encodedOffset: 12
+        /// - encodedLength: 4
+        #[inline]
+        pub fn source_comp_id(&mut self, value: u32) {
+            self.set_fields |= 1 << 2;
+            let offset = self.offset + 12;
+            self.get_buf_mut().put_u32_at(offset, value);
+        }
+
+        /// REQUIRED enum
+        #[inline]
+        pub fn subscription_request_type(&mut self, value: SubscriptionRequestType) {
+            self.set_fields |= 1 << 3;
+            let offset = self.offset + 16;
+            self.get_buf_mut().put_u8_at(offset, value as u8)
+        }
+
+        /// primitive field 'marketDepth'
+        /// - min value: 0
+        /// - max value: 65534
+        /// - null value: 65535
+        /// - characterEncoding: null
+        /// - semanticType: null
+        /// - encodedOffset: 17
+        /// - encodedLength: 2
+        #[inline]
+        pub fn market_depth(&mut self, value: u16) {
+            self.set_fields |= 1 << 4;
+            let offset = self.offset + 17;
+            self.get_buf_mut().put_u16_at(offset, value);
+        }
+
+        /// primitive field 'transactTime'
+        /// - min value: 0
+        /// - max value: -2
+        /// - null value: -1
+        /// - characterEncoding: null
+        /// - semanticType: null
+        /// - encodedOffset: 19
+        /// - encodedLength: 8
+        #[inline]
+        pub fn transact_time(&mut self, value: u64) {
+            self.set_fields |= 1 << 5;
+            let offset = self.offset + 19;
+            self.get_buf_mut().put_u64_at(offset, value);
+        }
+
+        /// GROUP ENCODER - writes the whole 'instruments' group; returns the number of entries written
+        pub fn encode_instruments<I>(&mut self, instrument_ids: I) -> SbeResult<usize>
+        where
+            I: IntoIterator<Item = u32>,
+        {
+            let (limit, count) = group_size_encoding_codec::encode_group(
+                &mut self.buf,
+                self.limit,
+                INSTRUMENTS_ENTRY_LENGTH,
+                instrument_ids,
+                |mut buf, offset, instrument_id| {
+                    buf.put_u32_at(offset, instrument_id);
+                    buf
+                },
+            )?;
+            self.limit = limit;
+            Ok(count)
+        }
+
+    }
+
+} // end encoder
+
+pub mod decoder {
+    use super::*;
+
+    #[derive(Debug, Default)]
+    pub struct MarketDataRequestDecoder<'a, O: ByteOrder = SchemaByteOrder> {
+        buf: ReadBuf<'a, O>,
+        initial_offset: usize,
+        offset: usize,
+        limit: usize,
+        pub acting_block_length: u16,
+        pub acting_version: u16,
+    }
+
+    impl<'a, O: ByteOrder> Reader<'a> for MarketDataRequestDecoder<'a, O> {
+        type ByteOrder = O;
+
+        #[inline]
+        fn get_buf(&self) -> &ReadBuf<'a, O> {
+            &self.buf
+        }
+    }
+
+    impl<'a, O: ByteOrder> Decoder<'a> for MarketDataRequestDecoder<'a, O> {
+        #[inline]
+        fn get_limit(&self) -> usize {
+            self.limit
+        }
+
+        #[inline]
+        fn set_limit(&mut self, limit: usize) {
+            self.limit = limit;
+        }
+    }
+
+    impl<'a, O: ByteOrder> MarketDataRequestDecoder<'a, O> {
+        pub fn wrap(
+            mut self,
+            buf: ReadBuf<'a, O>,
+            offset: usize,
+            acting_block_length: u16,
+            acting_version: u16,
+        ) -> Self {
+            let limit = offset + acting_block_length as usize;
+            self.buf = buf;
+            self.initial_offset = offset;
+            self.offset = offset;
+            self.limit = limit;
+            self.acting_block_length = acting_block_length;
+            self.acting_version = acting_version;
+            self
+        }
+
+        #[inline]
+        pub fn encoded_length(&self) -> usize {
+            self.limit - self.offset
+        }
+
+        pub fn header(self, mut header: MessageHeaderDecoder<ReadBuf<'a, O>>) -> Self {
+            debug_assert_eq!(SBE_TEMPLATE_ID, header.template_id());
+            let acting_block_length = header.block_length();
+            let acting_version = header.version();
+
+            self.wrap(
+                header.parent().unwrap(),
+                message_header_codec::ENCODED_LENGTH,
+                acting_block_length,
+                acting_version,
+            )
+        }
+
+        /// primitive field - 'REQUIRED'
+        #[inline]
+        pub fn md_req_id(&self) -> u64 {
+            self.get_buf().get_u64_at(self.offset)
+        }
+
+        /// primitive field - 'REQUIRED'
+        #[inline]
+        pub fn target_comp_id(&self) -> u32 {
+            self.get_buf().get_u32_at(self.offset + 8)
+        }
+
+        /// primitive field - 'REQUIRED'
+        #[inline]
+        pub fn source_comp_id(&self) -> u32 {
+            self.get_buf().get_u32_at(self.offset + 12)
+        }
+
+        /// REQUIRED enum
+        #[inline]
+        pub fn subscription_request_type(&self) -> SubscriptionRequestType {
+            self.get_buf().get_u8_at(self.offset + 16).into()
+        }
+
+        /// primitive field - 'REQUIRED'
+        #[inline]
+        pub fn market_depth(&self) -> u16 {
+            self.get_buf().get_u16_at(self.offset + 17)
+        }
+
+        /// primitive field - 'REQUIRED'
+        #[inline]
+        pub fn transact_time(&self) -> u64 {
+            self.get_buf().get_u64_at(self.offset + 19)
+        }
+
+        /// GROUP ITERATOR - reads the 'instruments' group and moves the limit past it
+        pub fn instruments(&mut self) -> SbeResult<InstrumentIds<'a, O>> {
+            let (offset, block_length, count) =
+                group_size_encoding_codec::group_extent(&self.buf, self.limit, INSTRUMENTS_ENTRY_LENGTH)?;
+            self.limit = offset + block_length * count;
+            Ok(InstrumentIds { data: self.buf.data, offset, block_length, remaining: count, byte_order: PhantomData })
+        }
+
+        /// A decoder over the same message with the limit back at the end of the block.
+        pub(crate) fn rewound(&self) -> Self {
+            let buf = ReadBuf::with_byte_order(self.buf.data, O::default());
+            Self::default().wrap(buf, self.offset, self.acting_block_length, self.acting_version)
+        }
+
+    }
+
+    /// Iterator over the 'instrumentId' of each entry of the 'instruments' group.
+    #[derive(Clone, Debug, Default)]
+    pub struct InstrumentIds<'a, O: ByteOrder = SchemaByteOrder> {
+        data: &'a [u8],
+        offset: usize,
+        block_length: usize,
+        remaining: usize,
+        byte_order: PhantomData<O>,
+    }
+
+    impl<'a, O: ByteOrder> Iterator for InstrumentIds<'a, O> {
+        type Item = u32;
+
+        #[inline]
+        fn next(&mut self) -> Option<u32> {
+            if self.remaining == 0 {
+                return None;
+            }
+            let instrument_id = ReadBuf::with_byte_order(self.data, O::default()).get_u32_at(self.offset);
+            self.offset += self.block_length;
+            self.remaining -= 1;
+            Some(instrument_id)
+        }
+
+        #[inline]
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            (self.remaining, Some(self.remaining))
+        }
+    }
+
+    impl<'a, O: ByteOrder> ExactSizeIterator for InstrumentIds<'a, O> {}
+} // end decoder
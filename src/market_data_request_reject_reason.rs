@@ -0,0 +1,31 @@
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(u8)]
+pub enum MarketDataRequestRejectReason {
+    UNKNOWN_SYMBOL = 48_u8, 
+    DUPLICATE_MD_REQ_ID = 49_u8, 
+    INSUFFICIENT_BANDWIDTH = 50_u8, 
+    INSUFFICIENT_PERMISSIONS = 51_u8, 
+    UNSUPPORTED_SUBSCRIPTION_REQUEST_TYPE = 52_u8, 
+    UNSUPPORTED_MARKET_DEPTH = 53_u8, 
+    UNKNOWN_MD_REQ_ID = 54_u8, 
+    NullVal = 0_u8, 
+}
+impl Default for MarketDataRequestRejectReason {
+    #[inline]
+    fn default() -> Self { MarketDataRequestRejectReason::NullVal }
+}
+impl From<u8> for MarketDataRequestRejectReason {
+    #[inline]
+    fn from(v: u8) -> Self {
+        match v {
+            48_u8 => Self::UNKNOWN_SYMBOL, 
+            49_u8 => Self::DUPLICATE_MD_REQ_ID, 
+            50_u8 => Self::INSUFFICIENT_BANDWIDTH, 
+            51_u8 => Self::INSUFFICIENT_PERMISSIONS, 
+            52_u8 => Self::UNSUPPORTED_SUBSCRIPTION_REQUEST_TYPE, 
+            53_u8 => Self::UNSUPPORTED_MARKET_DEPTH, 
+            54_u8 => Self::UNKNOWN_MD_REQ_ID, 
+            _ => Self::NullVal,
+        }
+    }
+}
@@ -0,0 +1,27 @@
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(u8)]
+pub enum MassCancelRejectReason {
+    NOT_SUPPORTED = 48_u8, 
+    UNKNOWN_INSTRUMENT = 49_u8, 
+    INVALID_SIDE = 50_u8, 
+    INVALID_STRATEGY = 51_u8, 
+    OTHER = 57_u8, 
+    NullVal = 0_u8, 
+}
+impl Default for MassCancelRejectReason {
+    #[inline]
+    fn default() -> Self { MassCancelRejectReason::NullVal }
+}
+impl From<u8> for MassCancelRejectReason {
+    #[inline]
+    fn from(v: u8) -> Self {
+        match v {
+            48_u8 => Self::NOT_SUPPORTED, 
+            49_u8 => Self::UNKNOWN_INSTRUMENT, 
+            50_u8 => Self::INVALID_SIDE, 
+            51_u8 => Self::INVALID_STRATEGY, 
+            57_u8 => Self::OTHER, 
+            _ => Self::NullVal,
+        }
+    }
+}
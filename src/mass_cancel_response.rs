@@ -0,0 +1,21 @@
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(u8)]
+pub enum MassCancelResponse {
+    REJECTED = 48_u8, 
+    ACCEPTED = 49_u8, 
+    NullVal = 0_u8, 
+}
+impl Default for MassCancelResponse {
+    #[inline]
+    fn default() -> Self { MassCancelResponse::NullVal }
+}
+impl From<u8> for MassCancelResponse {
+    #[inline]
+    fn from(v: u8) -> Self {
+        match v {
+            48_u8 => Self::REJECTED, 
+            49_u8 => Self::ACCEPTED, 
+            _ => Self::NullVal,
+        }
+    }
+}
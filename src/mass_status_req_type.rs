@@ -0,0 +1,23 @@
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(u8)]
+pub enum MassStatusReqType {
+    INSTRUMENT = 49_u8, 
+    ALL_ORDERS = 55_u8, 
+    STRATEGY = 56_u8, 
+    NullVal = 0_u8, 
+}
+impl Default for MassStatusReqType {
+    #[inline]
+    fn default() -> Self { MassStatusReqType::NullVal }
+}
+impl From<u8> for MassStatusReqType {
+    #[inline]
+    fn from(v: u8) -> Self {
+        match v {
+            49_u8 => Self::INSTRUMENT, 
+            55_u8 => Self::ALL_ORDERS, 
+            56_u8 => Self::STRATEGY, 
+            _ => Self::NullVal,
+        }
+    }
+}
@@ -15,8 +15,10 @@ pub mod encoder {
     }
 
     impl<'a, P> Writer<'a> for MessageHeaderEncoder<P> where P: Writer<'a> + Default {
+        type ByteOrder = P::ByteOrder;
+
         #[inline]
-        fn get_buf_mut(&mut self) -> &mut WriteBuf<'a> {
+        fn get_buf_mut(&mut self) -> &mut WriteBuf<'a, P::ByteOrder> {
             if let Some(parent) = self.parent.as_mut() {
                 parent.get_buf_mut()
             } else {
@@ -106,8 +108,10 @@ pub mod decoder {
     }
 
     impl<'a, P> Reader<'a> for MessageHeaderDecoder<P> where P: Reader<'a> + Default {
+        type ByteOrder = P::ByteOrder;
+
         #[inline]
-        fn get_buf(&self) -> &ReadBuf<'a> {
+        fn get_buf(&self) -> &ReadBuf<'a, P::ByteOrder> {
             self.parent.as_ref().expect("parent missing").get_buf()
         }
     }
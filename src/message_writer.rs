@@ -0,0 +1,128 @@
+//! Reusable writer encoding framed messages into a buffer it owns.
+//!
+//! Each message method writes the message header at the current position, hands the closure a
+//! `&mut` encoder wrapped in place over the owned buffer, and returns the framed message bytes.
+//! Successive messages are written back to back until [`MessageWriter::reset`] rewinds the
+//! writer, so one pre-sized buffer serves any number of messages without allocating.
+use crate::*;
+
+macro_rules! message_methods {
+    ($($(#[$doc:meta])* $name:ident => $encoder:ident, $codec:ident;)*) => {
+        $(
+            $(#[$doc])*
+            pub fn $name<F>(&mut self, encode: F) -> SbeResult<&[u8]>
+            where
+                F: FnOnce(&mut $encoder<'_>),
+            {
+                let start = self.limit;
+                let body_offset = start + message_header_codec::ENCODED_LENGTH;
+                let data = self.buffer.as_mut();
+                if data.len() < body_offset + $codec::SBE_BLOCK_LENGTH as usize {
+                    return Err(SbeErr::BufferTooShort);
+                }
+                let mut encoder = $encoder::default()
+                    .wrap(WriteBuf::new(data), body_offset)
+                    .header(start)
+                    .parent()?;
+                encode(&mut encoder);
+                self.limit = encoder.get_limit();
+                Ok(&self.buffer.as_ref()[start..self.limit])
+            }
+        )*
+    };
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct MessageWriter<B> {
+    buffer: B,
+    limit: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl MessageWriter<alloc::vec::Vec<u8>> {
+    /// Writer over a zeroed heap buffer of `capacity` bytes, allocated once here.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::new(alloc::vec![0; capacity])
+    }
+}
+
+impl<B> MessageWriter<B>
+where
+    B: AsRef<[u8]> + AsMut<[u8]>,
+{
+    pub fn new(buffer: B) -> Self {
+        Self { buffer, limit: 0 }
+    }
+
+    /// Rewinds to the start of the buffer; previously written bytes are overwritten by the next message.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.limit = 0;
+    }
+
+    /// Number of bytes written since the last reset.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.limit
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.limit == 0
+    }
+
+    /// All messages written since the last reset.
+    #[inline]
+    pub fn as_slice(&self) -> &[u8] {
+        &self.buffer.as_ref()[..self.limit]
+    }
+
+    #[inline]
+    pub fn into_inner(self) -> B {
+        self.buffer
+    }
+
+    /// Writes the `BusinessMessageReject` answering `message`, which failed with `error`. The
+    /// closure sets the ref id, comp ids and transact time; the referenced template id, reason and
+    /// text are derived from `message` and `error`. Nothing is written if the reject does not fit.
+    pub fn business_message_reject_for<F>(&mut self, message: &[u8], error: &SbeErr, encode: F) -> SbeResult<&[u8]>
+    where
+        F: FnOnce(&mut BusinessMessageRejectEncoder<'_>),
+    {
+        let start = self.limit;
+        let mut result = Ok(());
+        self.business_message_reject(|reject| {
+            encode(reject);
+            result = reject.reject_for(message, error);
+        })?;
+        if let Err(error) = result {
+            self.limit = start;
+            return Err(error);
+        }
+        Ok(&self.buffer.as_ref()[start..self.limit])
+    }
+
+    message_methods! {
+        price_increment => PriceIncrementEncoder, price_increment_codec;
+        price_snapshot => PriceSnapshotEncoder, price_snapshot_codec;
+        price_snapshot_request => PriceSnapshotRequestEncoder, price_snapshot_request_codec;
+        last_market_trade => LastMarketTradeEncoder, last_market_trade_codec;
+        new_order_single => NewOrderSingleEncoder, new_order_single_codec;
+        execution_report => ExecutionReportEncoder, execution_report_codec;
+        order_cancel_request => OrderCancelRequestEncoder, order_cancel_request_codec;
+        order_cancel_reject => OrderCancelRejectEncoder, order_cancel_reject_codec;
+        logon_request => LogonRequestEncoder, logon_request_codec;
+        logon_response => LogonResponseEncoder, logon_response_codec;
+        logout_request => LogoutRequestEncoder, logout_request_codec;
+        logout_response => LogoutResponseEncoder, logout_response_codec;
+        instrument_definition => InstrumentDefinitionEncoder, instrument_definition_codec;
+        market_data_request => MarketDataRequestEncoder, market_data_request_codec;
+        market_data_request_reject => MarketDataRequestRejectEncoder, market_data_request_reject_codec;
+        market_data_request_ack => MarketDataRequestAckEncoder, market_data_request_ack_codec;
+        order_status_request => OrderStatusRequestEncoder, order_status_request_codec;
+        order_mass_status_request => OrderMassStatusRequestEncoder, order_mass_status_request_codec;
+        order_mass_cancel_request => OrderMassCancelRequestEncoder, order_mass_cancel_request_codec;
+        order_mass_cancel_report => OrderMassCancelReportEncoder, order_mass_cancel_report_codec;
+        business_message_reject => BusinessMessageRejectEncoder, business_message_reject_codec;
+    }
+}
@@ -6,27 +6,49 @@ pub use decoder::*;
 pub const SBE_BLOCK_LENGTH: u16 = 73;
 pub const SBE_TEMPLATE_ID: u16 = 7;
 pub const SBE_SCHEMA_ID: u16 = 1;
-pub const SBE_SCHEMA_VERSION: u16 = 0;
+pub const SBE_SCHEMA_VERSION: u16 = 4;
+
+/// names of the REQUIRED fields, in the bit order of the encoder's set-field mask
+pub const REQUIRED_FIELDS: &[&str] = &[
+    "clOrdId",
+    "clOrdLinkId",
+    "instrumentId",
+    "targetCompId",
+    "sourceCompId",
+    "side",
+    "orderType",
+    "price",
+    "orderQty",
+    "timeInForce",
+    "transactTime",
+    "effectiveTime",
+    "expireTime",
+    "targetStrategy",
+    "sourceStrategy",
+];
 
 pub mod encoder {
     use super::*;
 
     #[derive(Debug, Default)]
-    pub struct NewOrderSingleEncoder<'a> {
-        buf: WriteBuf<'a>,
+    pub struct NewOrderSingleEncoder<'a, O: ByteOrder = SchemaByteOrder> {
+        buf: WriteBuf<'a, O>,
         initial_offset: usize,
         offset: usize,
         limit: usize,
+        set_fields: u64,
     }
 
-    impl<'a> Writer<'a> for NewOrderSingleEncoder<'a> {
+    impl<'a, O: ByteOrder> Writer<'a> for NewOrderSingleEncoder<'a, O> {
+        type ByteOrder = O;
+
         #[inline]
-        fn get_buf_mut(&mut self) -> &mut WriteBuf<'a> {
+        fn get_buf_mut(&mut self) -> &mut WriteBuf<'a, O> {
             &mut self.buf
         }
     }
 
-    impl<'a> Encoder<'a> for NewOrderSingleEncoder<'a> {
+    impl<'a, O: ByteOrder> Encoder<'a> for NewOrderSingleEncoder<'a, O> {
         #[inline]
         fn get_limit(&self) -> usize {
             self.limit
@@ -38,13 +60,14 @@ pub mod encoder {
         }
     }
 
-    impl<'a> NewOrderSingleEncoder<'a> {
-        pub fn wrap(mut self, buf: WriteBuf<'a>, offset: usize) -> Self {
+    impl<'a, O: ByteOrder> NewOrderSingleEncoder<'a, O> {
+        pub fn wrap(mut self, buf: WriteBuf<'a, O>, offset: usize) -> Self {
             let limit = offset + SBE_BLOCK_LENGTH as usize;
             self.buf = buf;
             self.initial_offset = offset;
             self.offset = offset;
             self.limit = limit;
+            self.set_fields = 0;
             self
         }
 
@@ -62,6 +85,14 @@ pub mod encoder {
             header
         }
 
+        /// Returns the encoded length and the buffer, or the first REQUIRED field that was never set.
+        pub fn finish(self) -> SbeResult<(usize, WriteBuf<'a, O>)> {
+            if let Some(index) = (0..REQUIRED_FIELDS.len()).find(|index| self.set_fields & (1 << index) == 0) {
+                return Err(SbeErr::MissingRequiredField(REQUIRED_FIELDS[index]));
+            }
+            Ok((self.encoded_length(), self.buf))
+        }
+
         /// primitive field 'clOrdId'
         /// - min value: 0
         /// - max value: -2
@@ -72,6 +103,7 @@ pub mod encoder {
         /// - encodedLength: 8
         #[inline]
         pub fn cl_ord_id(&mut self, value: u64) {
+            self.set_fields |= 1 << 0;
             let offset = self.offset;
             self.get_buf_mut().put_u64_at(offset, value);
         }
@@ -86,6 +118,7 @@ pub mod encoder {
         /// - encodedLength: 8
         #[inline]
         pub fn cl_ord_link_id(&mut self, value: u64) {
+            self.set_fields |= 1 << 1;
             let offset = self.offset + 8;
             self.get_buf_mut().put_u64_at(offset, value);
         }
@@ -100,6 +133,7 @@ pub mod encoder {
         /// - encodedLength: 4
         #[inline]
         pub fn instrument_id(&mut self, value: u32) {
+            self.set_fields |= 1 << 2;
             let offset = self.offset + 16;
             self.get_buf_mut().put_u32_at(offset, value);
         }
@@ -114,6 +148,7 @@ pub mod encoder {
         /// - encodedLength: 4
         #[inline]
         pub fn target_comp_id(&mut self, value: u32) {
+            self.set_fields |= 1 << 3;
             let offset = self.offset + 20;
             self.get_buf_mut().put_u32_at(offset, value);
         }
@@ -128,6 +163,7 @@ pub mod encoder {
         /// - encodedLength: 4
         #[inline]
         pub fn source_comp_id(&mut self, value: u32) {
+            self.set_fields |= 1 << 4;
             let offset = self.offset + 24;
             self.get_buf_mut().put_u32_at(offset, value);
         }
@@ -135,6 +171,7 @@ pub mod encoder {
         /// REQUIRED enum
         #[inline]
         pub fn side(&mut self, value: OrderSide) {
+            self.set_fields |= 1 << 5;
             let offset = self.offset + 28;
             self.get_buf_mut().put_u8_at(offset, value as u8)
         }
@@ -142,6 +179,7 @@ pub mod encoder {
         /// REQUIRED enum
         #[inline]
         pub fn order_type(&mut self, value: OrderType) {
+            self.set_fields |= 1 << 6;
             let offset = self.offset + 29;
             self.get_buf_mut().put_u8_at(offset, value as u8)
         }
@@ -156,6 +194,7 @@ pub mod encoder {
         /// - encodedLength: 8
         #[inline]
         pub fn price(&mut self, value: f64) {
+            self.set_fields |= 1 << 7;
             let offset = self.offset + 30;
             self.get_buf_mut().put_f64_at(offset, value);
         }
@@ -170,6 +209,7 @@ pub mod encoder {
         /// - encodedLength: 8
         #[inline]
         pub fn order_qty(&mut self, value: f64) {
+            self.set_fields |= 1 << 8;
             let offset = self.offset + 38;
             self.get_buf_mut().put_f64_at(offset, value);
         }
@@ -177,6 +217,7 @@ pub mod encoder {
         /// REQUIRED enum
         #[inline]
         pub fn time_in_force(&mut self, value: TimeInForce) {
+            self.set_fields |= 1 << 9;
             let offset = self.offset + 46;
             self.get_buf_mut().put_u8_at(offset, value as u8)
         }
@@ -191,6 +232,7 @@ pub mod encoder {
         /// - encodedLength: 8
         #[inline]
         pub fn transact_time(&mut self, value: u64) {
+            self.set_fields |= 1 << 10;
             let offset = self.offset + 47;
             self.get_buf_mut().put_u64_at(offset, value);
         }
@@ -205,6 +247,7 @@ pub mod encoder {
         /// - encodedLength: 8
         #[inline]
         pub fn effective_time(&mut self, value: u64) {
+            self.set_fields |= 1 << 11;
             let offset = self.offset + 55;
             self.get_buf_mut().put_u64_at(offset, value);
         }
@@ -219,6 +262,7 @@ pub mod encoder {
         /// - encodedLength: 8
         #[inline]
         pub fn expire_time(&mut self, value: u64) {
+            self.set_fields |= 1 << 12;
             let offset = self.offset + 63;
             self.get_buf_mut().put_u64_at(offset, value);
         }
@@ -226,6 +270,7 @@ pub mod encoder {
         /// REQUIRED enum
         #[inline]
         pub fn target_strategy(&mut self, value: Strategy) {
+            self.set_fields |= 1 << 13;
             let offset = self.offset + 71;
             self.get_buf_mut().put_u8_at(offset, value as u8)
         }
@@ -233,6 +278,7 @@ pub mod encoder {
         /// REQUIRED enum
         #[inline]
         pub fn source_strategy(&mut self, value: Strategy) {
+            self.set_fields |= 1 << 14;
             let offset = self.offset + 72;
             self.get_buf_mut().put_u8_at(offset, value as u8)
         }
@@ -245,8 +291,8 @@ pub mod decoder {
     use super::*;
 
     #[derive(Debug, Default)]
-    pub struct NewOrderSingleDecoder<'a> {
-        buf: ReadBuf<'a>,
+    pub struct NewOrderSingleDecoder<'a, O: ByteOrder = SchemaByteOrder> {
+        buf: ReadBuf<'a, O>,
         initial_offset: usize,
         offset: usize,
         limit: usize,
@@ -254,14 +300,16 @@ pub mod decoder {
         pub acting_version: u16,
     }
 
-    impl<'a> Reader<'a> for NewOrderSingleDecoder<'a> {
+    impl<'a, O: ByteOrder> Reader<'a> for NewOrderSingleDecoder<'a, O> {
+        type ByteOrder = O;
+
         #[inline]
-        fn get_buf(&self) -> &ReadBuf<'a> {
+        fn get_buf(&self) -> &ReadBuf<'a, O> {
             &self.buf
         }
     }
 
-    impl<'a> Decoder<'a> for NewOrderSingleDecoder<'a> {
+    impl<'a, O: ByteOrder> Decoder<'a> for NewOrderSingleDecoder<'a, O> {
         #[inline]
         fn get_limit(&self) -> usize {
             self.limit
@@ -273,10 +321,10 @@ pub mod decoder {
         }
     }
 
-    impl<'a> NewOrderSingleDecoder<'a> {
+    impl<'a, O: ByteOrder> NewOrderSingleDecoder<'a, O> {
         pub fn wrap(
             mut self,
-            buf: ReadBuf<'a>,
+            buf: ReadBuf<'a, O>,
             offset: usize,
             acting_block_length: u16,
             acting_version: u16,
@@ -296,7 +344,7 @@ pub mod decoder {
             self.limit - self.offset
         }
 
-        pub fn header(self, mut header: MessageHeaderDecoder<ReadBuf<'a>>) -> Self {
+        pub fn header(self, mut header: MessageHeaderDecoder<ReadBuf<'a, O>>) -> Self {
             debug_assert_eq!(SBE_TEMPLATE_ID, header.template_id());
             let acting_block_length = header.block_length();
             let acting_version = header.version();
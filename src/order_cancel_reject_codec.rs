@@ -6,27 +6,49 @@ pub use decoder::*;
 pub const SBE_BLOCK_LENGTH: u16 = 49;
 pub const SBE_TEMPLATE_ID: u16 = 10;
 pub const SBE_SCHEMA_ID: u16 = 1;
-pub const SBE_SCHEMA_VERSION: u16 = 0;
+pub const SBE_SCHEMA_VERSION: u16 = 4;
+/// schema version from which the message ends with the 'text' var data
+pub const TEXT_SINCE_VERSION: u16 = 2;
+
+/// names of the REQUIRED fields, in the bit order of the encoder's set-field mask
+pub const REQUIRED_FIELDS: &[&str] = &[
+    "origClOrdId",
+    "orderId",
+    "clOrdId",
+    "orderStatus",
+    "instrumentId",
+    "targetCompId",
+    "sourceCompId",
+    "targetStrategy",
+    "sourceStrategy",
+    "cancelRejResponseTo",
+    "cancelRejReason",
+    "transactTime",
+    "text",
+];
 
 pub mod encoder {
     use super::*;
 
     #[derive(Debug, Default)]
-    pub struct OrderCancelRejectEncoder<'a> {
-        buf: WriteBuf<'a>,
+    pub struct OrderCancelRejectEncoder<'a, O: ByteOrder = SchemaByteOrder> {
+        buf: WriteBuf<'a, O>,
         initial_offset: usize,
         offset: usize,
         limit: usize,
+        set_fields: u64,
     }
 
-    impl<'a> Writer<'a> for OrderCancelRejectEncoder<'a> {
+    impl<'a, O: ByteOrder> Writer<'a> for OrderCancelRejectEncoder<'a, O> {
+        type ByteOrder = O;
+
         #[inline]
-        fn get_buf_mut(&mut self) -> &mut WriteBuf<'a> {
+        fn get_buf_mut(&mut self) -> &mut WriteBuf<'a, O> {
             &mut self.buf
         }
     }
 
-    impl<'a> Encoder<'a> for OrderCancelRejectEncoder<'a> {
+    impl<'a, O: ByteOrder> Encoder<'a> for OrderCancelRejectEncoder<'a, O> {
         #[inline]
         fn get_limit(&self) -> usize {
             self.limit
@@ -38,13 +60,14 @@ pub mod encoder {
         }
     }
 
-    impl<'a> OrderCancelRejectEncoder<'a> {
-        pub fn wrap(mut self, buf: WriteBuf<'a>, offset: usize) -> Self {
+    impl<'a, O: ByteOrder> OrderCancelRejectEncoder<'a, O> {
+        pub fn wrap(mut self, buf: WriteBuf<'a, O>, offset: usize) -> Self {
             let limit = offset + SBE_BLOCK_LENGTH as usize;
             self.buf = buf;
             self.initial_offset = offset;
             self.offset = offset;
             self.limit = limit;
+            self.set_fields = 0;
             self
         }
 
@@ -62,6 +85,14 @@ pub mod encoder {
             header
         }
 
+        /// Returns the encoded length and the buffer, or the first REQUIRED field that was never set.
+        pub fn finish(self) -> SbeResult<(usize, WriteBuf<'a, O>)> {
+            if let Some(index) = (0..REQUIRED_FIELDS.len()).find(|index| self.set_fields & (1 << index) == 0) {
+                return Err(SbeErr::MissingRequiredField(REQUIRED_FIELDS[index]));
+            }
+            Ok((self.encoded_length(), self.buf))
+        }
+
         /// primitive field 'origClOrdId'
         /// - min value: 0
         /// - max value: -2
@@ -72,6 +103,7 @@ pub mod encoder {
         /// - encodedLength: 8
         #[inline]
         pub fn orig_cl_ord_id(&mut self, value: u64) {
+            self.set_fields |= 1 << 0;
             let offset = self.offset;
             self.get_buf_mut().put_u64_at(offset, value);
         }
@@ -86,6 +118,7 @@ pub mod encoder {
         /// - encodedLength: 8
         #[inline]
         pub fn order_id(&mut self, value: u64) {
+            self.set_fields |= 1 << 1;
             let offset = self.offset + 8;
             self.get_buf_mut().put_u64_at(offset, value);
         }
@@ -100,6 +133,7 @@ pub mod encoder {
         /// - encodedLength: 8
         #[inline]
         pub fn cl_ord_id(&mut self, value: u64) {
+            self.set_fields |= 1 << 2;
             let offset = self.offset + 16;
             self.get_buf_mut().put_u64_at(offset, value);
         }
@@ -107,6 +141,7 @@ pub mod encoder {
         /// REQUIRED enum
         #[inline]
         pub fn order_status(&mut self, value: OrderStatus) {
+            self.set_fields |= 1 << 3;
             let offset = self.offset + 24;
             self.get_buf_mut().put_u8_at(offset, value as u8)
         }
@@ -121,6 +156,7 @@ pub mod encoder {
         /// - encodedLength: 4
         #[inline]
         pub fn instrument_id(&mut self, value: u32) {
+            self.set_fields |= 1 << 4;
             let offset = self.offset + 25;
             self.get_buf_mut().put_u32_at(offset, value);
         }
@@ -135,6 +171,7 @@ pub mod encoder {
         /// - encodedLength: 4
         #[inline]
         pub fn target_comp_id(&mut self, value: u32) {
+            self.set_fields |= 1 << 5;
             let offset = self.offset + 29;
             self.get_buf_mut().put_u32_at(offset, value);
         }
@@ -149,6 +186,7 @@ pub mod encoder {
         /// - encodedLength: 4
         #[inline]
         pub fn source_comp_id(&mut self, value: u32) {
+            self.set_fields |= 1 << 6;
             let offset = self.offset + 33;
             self.get_buf_mut().put_u32_at(offset, value);
         }
@@ -156,6 +194,7 @@ pub mod encoder {
         /// REQUIRED enum
         #[inline]
         pub fn target_strategy(&mut self, value: Strategy) {
+            self.set_fields |= 1 << 7;
             let offset = self.offset + 37;
             self.get_buf_mut().put_u8_at(offset, value as u8)
         }
@@ -163,6 +202,7 @@ pub mod encoder {
         /// REQUIRED enum
         #[inline]
         pub fn source_strategy(&mut self, value: Strategy) {
+            self.set_fields |= 1 << 8;
             let offset = self.offset + 38;
             self.get_buf_mut().put_u8_at(offset, value as u8)
         }
@@ -170,6 +210,7 @@ pub mod encoder {
         /// REQUIRED enum
         #[inline]
         pub fn cancel_rej_response_to(&mut self, value: CancelRejectResponseTo) {
+            self.set_fields |= 1 << 9;
             let offset = self.offset + 39;
             self.get_buf_mut().put_u8_at(offset, value as u8)
         }
@@ -177,6 +218,7 @@ pub mod encoder {
         /// REQUIRED enum
         #[inline]
         pub fn cancel_rej_reason(&mut self, value: CancelRejectReason) {
+            self.set_fields |= 1 << 10;
             let offset = self.offset + 40;
             self.get_buf_mut().put_u8_at(offset, value as u8)
         }
@@ -191,10 +233,23 @@ pub mod encoder {
         /// - encodedLength: 8
         #[inline]
         pub fn transact_time(&mut self, value: u64) {
+            self.set_fields |= 1 << 11;
             let offset = self.offset + 41;
             self.get_buf_mut().put_u64_at(offset, value);
         }
 
+        /// VAR_DATA ENCODER - character encoding: 'UTF-8'; writes at the limit, so it must be called
+        /// after every group, and only once: a second call would append a second field
+        pub fn text(&mut self, value: &[u8]) -> SbeResult<()> {
+            if self.set_fields & (1 << 12) != 0 {
+                return Err(SbeErr::DuplicateField("text"));
+            }
+            let limit = var_string_encoding_codec::encode_var_data(&mut self.buf, self.limit, value)?;
+            self.set_fields |= 1 << 12;
+            self.limit = limit;
+            Ok(())
+        }
+
     }
 
 } // end encoder
@@ -203,8 +258,8 @@ pub mod decoder {
     use super::*;
 
     #[derive(Debug, Default)]
-    pub struct OrderCancelRejectDecoder<'a> {
-        buf: ReadBuf<'a>,
+    pub struct OrderCancelRejectDecoder<'a, O: ByteOrder = SchemaByteOrder> {
+        buf: ReadBuf<'a, O>,
         initial_offset: usize,
         offset: usize,
         limit: usize,
@@ -212,14 +267,16 @@ pub mod decoder {
         pub acting_version: u16,
     }
 
-    impl<'a> Reader<'a> for OrderCancelRejectDecoder<'a> {
+    impl<'a, O: ByteOrder> Reader<'a> for OrderCancelRejectDecoder<'a, O> {
+        type ByteOrder = O;
+
         #[inline]
-        fn get_buf(&self) -> &ReadBuf<'a> {
+        fn get_buf(&self) -> &ReadBuf<'a, O> {
             &self.buf
         }
     }
 
-    impl<'a> Decoder<'a> for OrderCancelRejectDecoder<'a> {
+    impl<'a, O: ByteOrder> Decoder<'a> for OrderCancelRejectDecoder<'a, O> {
         #[inline]
         fn get_limit(&self) -> usize {
             self.limit
@@ -231,10 +288,10 @@ pub mod decoder {
         }
     }
 
-    impl<'a> OrderCancelRejectDecoder<'a> {
+    impl<'a, O: ByteOrder> OrderCancelRejectDecoder<'a, O> {
         pub fn wrap(
             mut self,
-            buf: ReadBuf<'a>,
+            buf: ReadBuf<'a, O>,
             offset: usize,
             acting_block_length: u16,
             acting_version: u16,
@@ -254,7 +311,7 @@ pub mod decoder {
             self.limit - self.offset
         }
 
-        pub fn header(self, mut header: MessageHeaderDecoder<ReadBuf<'a>>) -> Self {
+        pub fn header(self, mut header: MessageHeaderDecoder<ReadBuf<'a, O>>) -> Self {
             debug_assert_eq!(SBE_TEMPLATE_ID, header.template_id());
             let acting_block_length = header.block_length();
             let acting_version = header.version();
@@ -339,6 +396,24 @@ pub mod decoder {
             self.get_buf().get_u64_at(self.offset + 41)
         }
 
+        /// VAR_DATA DECODER - character encoding: 'UTF-8'; reads at the limit and moves it past the
+        /// data, so it must be called after every group. Empty when the sender predates the field.
+        pub fn text(&mut self) -> SbeResult<&'a [u8]> {
+            if self.acting_version < TEXT_SINCE_VERSION {
+                return Ok(&[]);
+            }
+            let (offset, length) = var_string_encoding_codec::var_data_extent(&self.buf, self.limit)?;
+            self.limit = offset + length;
+            let data: &'a [u8] = self.buf.data;
+            Ok(&data[offset..offset + length])
+        }
+
+        /// A decoder over the same message with the limit back at the end of the block.
+        pub(crate) fn rewound(&self) -> Self {
+            let buf = ReadBuf::with_byte_order(self.buf.data, O::default());
+            Self::default().wrap(buf, self.offset, self.acting_block_length, self.acting_version)
+        }
+
     }
 
 } // end decoder
@@ -6,27 +6,45 @@ pub use decoder::*;
 pub const SBE_BLOCK_LENGTH: u16 = 55;
 pub const SBE_TEMPLATE_ID: u16 = 9;
 pub const SBE_SCHEMA_ID: u16 = 1;
-pub const SBE_SCHEMA_VERSION: u16 = 0;
+pub const SBE_SCHEMA_VERSION: u16 = 4;
+
+/// names of the REQUIRED fields, in the bit order of the encoder's set-field mask
+pub const REQUIRED_FIELDS: &[&str] = &[
+    "origClOrdId",
+    "orderId",
+    "clOrdId",
+    "instrumentId",
+    "targetCompId",
+    "sourceCompId",
+    "targetStrategy",
+    "sourceStrategy",
+    "side",
+    "price",
+    "transactTime",
+];
 
 pub mod encoder {
     use super::*;
 
     #[derive(Debug, Default)]
-    pub struct OrderCancelRequestEncoder<'a> {
-        buf: WriteBuf<'a>,
+    pub struct OrderCancelRequestEncoder<'a, O: ByteOrder = SchemaByteOrder> {
+        buf: WriteBuf<'a, O>,
         initial_offset: usize,
         offset: usize,
         limit: usize,
+        set_fields: u64,
     }
 
-    impl<'a> Writer<'a> for OrderCancelRequestEncoder<'a> {
+    impl<'a, O: ByteOrder> Writer<'a> for OrderCancelRequestEncoder<'a, O> {
+        type ByteOrder = O;
+
         #[inline]
-        fn get_buf_mut(&mut self) -> &mut WriteBuf<'a> {
+        fn get_buf_mut(&mut self) -> &mut WriteBuf<'a, O> {
             &mut self.buf
         }
     }
 
-    impl<'a> Encoder<'a> for OrderCancelRequestEncoder<'a> {
+    impl<'a, O: ByteOrder> Encoder<'a> for OrderCancelRequestEncoder<'a, O> {
         #[inline]
         fn get_limit(&self) -> usize {
             self.limit
@@ -38,13 +56,14 @@ pub mod encoder {
         }
     }
 
-    impl<'a> OrderCancelRequestEncoder<'a> {
-        pub fn wrap(mut self, buf: WriteBuf<'a>, offset: usize) -> Self {
+    impl<'a, O: ByteOrder> OrderCancelRequestEncoder<'a, O> {
+        pub fn wrap(mut self, buf: WriteBuf<'a, O>, offset: usize) -> Self {
             let limit = offset + SBE_BLOCK_LENGTH as usize;
             self.buf = buf;
             self.initial_offset = offset;
             self.offset = offset;
             self.limit = limit;
+            self.set_fields = 0;
             self
         }
 
@@ -62,6 +81,14 @@ pub mod encoder {
             header
         }
 
+        /// Returns the encoded length and the buffer, or the first REQUIRED field that was never set.
+        pub fn finish(self) -> SbeResult<(usize, WriteBuf<'a, O>)> {
+            if let Some(index) = (0..REQUIRED_FIELDS.len()).find(|index| self.set_fields & (1 << index) == 0) {
+                return Err(SbeErr::MissingRequiredField(REQUIRED_FIELDS[index]));
+            }
+            Ok((self.encoded_length(), self.buf))
+        }
+
         /// primitive field 'origClOrdId'
         /// - min value: 0
         /// - max value: -2
@@ -72,6 +99,7 @@ pub mod encoder {
         /// - encodedLength: 8
         #[inline]
         pub fn orig_cl_ord_id(&mut self, value: u64) {
+            self.set_fields |= 1 << 0;
             let offset = self.offset;
             self.get_buf_mut().put_u64_at(offset, value);
         }
@@ -86,6 +114,7 @@ pub mod encoder {
         /// - encodedLength: 8
         #[inline]
         pub fn order_id(&mut self, value: u64) {
+            self.set_fields |= 1 << 1;
             let offset = self.offset + 8;
             self.get_buf_mut().put_u64_at(offset, value);
         }
@@ -100,6 +129,7 @@ pub mod encoder {
         /// - encodedLength: 8
         #[inline]
         pub fn cl_ord_id(&mut self, value: u64) {
+            self.set_fields |= 1 << 2;
             let offset = self.offset + 16;
             self.get_buf_mut().put_u64_at(offset, value);
         }
@@ -114,6 +144,7 @@ pub mod encoder {
         /// - encodedLength: 4
         #[inline]
         pub fn instrument_id(&mut self, value: u32) {
+            self.set_fields |= 1 << 3;
             let offset = self.offset + 24;
             self.get_buf_mut().put_u32_at(offset, value);
         }
@@ -128,6 +159,7 @@ pub mod encoder {
         /// - encodedLength: 4
         #[inline]
         pub fn target_comp_id(&mut self, value: u32) {
+            self.set_fields |= 1 << 4;
             let offset = self.offset + 28;
             self.get_buf_mut().put_u32_at(offset, value);
         }
@@ -142,6 +174,7 @@ pub mod encoder {
         /// - encodedLength: 4
         #[inline]
         pub fn source_comp_id(&mut self, value: u32) {
+            self.set_fields |= 1 << 5;
             let offset = self.offset + 32;
             self.get_buf_mut().put_u32_at(offset, value);
         }
@@ -149,6 +182,7 @@ pub mod encoder {
         /// REQUIRED enum
         #[inline]
         pub fn target_strategy(&mut self, value: Strategy) {
+            self.set_fields |= 1 << 6;
             let offset = self.offset + 36;
             self.get_buf_mut().put_u8_at(offset, value as u8)
         }
@@ -156,6 +190,7 @@ pub mod encoder {
         /// REQUIRED enum
         #[inline]
         pub fn source_strategy(&mut self, value: Strategy) {
+            self.set_fields |= 1 << 7;
             let offset = self.offset + 37;
             self.get_buf_mut().put_u8_at(offset, value as u8)
         }
@@ -163,6 +198,7 @@ pub mod encoder {
         /// REQUIRED enum
         #[inline]
         pub fn side(&mut self, value: OrderSide) {
+            self.set_fields |= 1 << 8;
             let offset = self.offset + 38;
             self.get_buf_mut().put_u8_at(offset, value as u8)
         }
@@ -177,6 +213,7 @@ pub mod encoder {
         /// - encodedLength: 8
         #[inline]
         pub fn price(&mut self, value: f64) {
+            self.set_fields |= 1 << 9;
             let offset = self.offset + 39;
             self.get_buf_mut().put_f64_at(offset, value);
         }
@@ -191,6 +228,7 @@ pub mod encoder {
         /// - encodedLength: 8
         #[inline]
         pub fn transact_time(&mut self, value: u64) {
+            self.set_fields |= 1 << 10;
             let offset = self.offset + 47;
             self.get_buf_mut().put_u64_at(offset, value);
         }
@@ -203,8 +241,8 @@ pub mod decoder {
     use super::*;
 
     #[derive(Debug, Default)]
-    pub struct OrderCancelRequestDecoder<'a> {
-        buf: ReadBuf<'a>,
+    pub struct OrderCancelRequestDecoder<'a, O: ByteOrder = SchemaByteOrder> {
+        buf: ReadBuf<'a, O>,
         initial_offset: usize,
         offset: usize,
         limit: usize,
@@ -212,14 +250,16 @@ pub mod decoder {
         pub acting_version: u16,
     }
 
-    impl<'a> Reader<'a> for OrderCancelRequestDecoder<'a> {
+    impl<'a, O: ByteOrder> Reader<'a> for OrderCancelRequestDecoder<'a, O> {
+        type ByteOrder = O;
+
         #[inline]
-        fn get_buf(&self) -> &ReadBuf<'a> {
+        fn get_buf(&self) -> &ReadBuf<'a, O> {
             &self.buf
         }
     }
 
-    impl<'a> Decoder<'a> for OrderCancelRequestDecoder<'a> {
+    impl<'a, O: ByteOrder> Decoder<'a> for OrderCancelRequestDecoder<'a, O> {
         #[inline]
         fn get_limit(&self) -> usize {
             self.limit
@@ -231,10 +271,10 @@ pub mod decoder {
         }
     }
 
-    impl<'a> OrderCancelRequestDecoder<'a> {
+    impl<'a, O: ByteOrder> OrderCancelRequestDecoder<'a, O> {
         pub fn wrap(
             mut self,
-            buf: ReadBuf<'a>,
+            buf: ReadBuf<'a, O>,
             offset: usize,
             acting_block_length: u16,
             acting_version: u16,
@@ -254,7 +294,7 @@ pub mod decoder {
             self.limit - self.offset
         }
 
-        pub fn header(self, mut header: MessageHeaderDecoder<ReadBuf<'a>>) -> Self {
+        pub fn header(self, mut header: MessageHeaderDecoder<ReadBuf<'a, O>>) -> Self {
             debug_assert_eq!(SBE_TEMPLATE_ID, header.template_id());
             let acting_block_length = header.block_length();
             let acting_version = header.version();
@@ -0,0 +1,422 @@
+use crate::*;
+
+pub use encoder::*;
+pub use decoder::*;
+
+pub const SBE_BLOCK_LENGTH: u16 = 39;
+pub const SBE_TEMPLATE_ID: u16 = 22;
+pub const SBE_SCHEMA_ID: u16 = 1;
+pub const SBE_SCHEMA_VERSION: u16 = 4;
+
+/// names of the REQUIRED fields, in the bit order of the encoder's set-field mask
+pub const REQUIRED_FIELDS: &[&str] = &[
+    "clOrdId",
+    "massCancelRequestType",
+    "massCancelResponse",
+    "massCancelRejectReason",
+    "totalAffectedOrders",
+    "instrumentId",
+    "side",
+    "strategy",
+    "targetCompId",
+    "sourceCompId",
+    "targetStrategy",
+    "sourceStrategy",
+    "transactTime",
+    "text",
+];
+
+pub mod encoder {
+    use super::*;
+
+    #[derive(Debug, Default)]
+    pub struct OrderMassCancelReportEncoder<'a, O: ByteOrder = SchemaByteOrder> {
+        buf: WriteBuf<'a, O>,
+        initial_offset: usize,
+        offset: usize,
+        limit: usize,
+        set_fields: u64,
+    }
+
+    impl<'a, O: ByteOrder> Writer<'a> for OrderMassCancelReportEncoder<'a, O> {
+        type ByteOrder = O;
+
+        #[inline]
+        fn get_buf_mut(&mut self) -> &mut WriteBuf<'a, O> {
+            &mut self.buf
+        }
+    }
+
+    impl<'a, O: ByteOrder> Encoder<'a> for OrderMassCancelReportEncoder<'a, O> {
+        #[inline]
+        fn get_limit(&self) -> usize {
+            self.limit
+        }
+
+        #[inline]
+        fn set_limit(&mut self, limit: usize) {
+            self.limit = limit;
+        }
+    }
+
+    impl<'a, O: ByteOrder> OrderMassCancelReportEncoder<'a, O> {
+        pub fn wrap(mut self, buf: WriteBuf<'a, O>, offset: usize) -> Self {
+            let limit = offset + SBE_BLOCK_LENGTH as usize;
+            self.buf = buf;
+            self.initial_offset = offset;
+            self.offset = offset;
+            self.limit = limit;
+            self.set_fields = 0;
+            self
+        }
+
+        #[inline]
+        pub fn encoded_length(&self) -> usize {
+            self.limit - self.offset
+        }
+
+        pub fn header(self, offset: usize) -> MessageHeaderEncoder<Self> {
+            let mut header = MessageHeaderEncoder::default().wrap(self, offset);
+            header.block_length(SBE_BLOCK_LENGTH);
+            header.template_id(SBE_TEMPLATE_ID);
+            header.schema_id(SBE_SCHEMA_ID);
+            header.version(SBE_SCHEMA_VERSION);
+            header
+        }
+
+        /// Returns the encoded length and the buffer, or the first REQUIRED field that was never set.
+        pub fn finish(self) -> SbeResult<(usize, WriteBuf<'a, O>)> {
+            if let Some(index) = (0..REQUIRED_FIELDS.len()).find(|index| self.set_fields & (1 << index) == 0) {
+                return Err(SbeErr::MissingRequiredField(REQUIRED_FIELDS[index]));
+            }
+            Ok((self.encoded_length(), self.buf))
+        }
+
+        /// primitive field 'clOrdId'
+        /// - min value: 0
+        /// - max value: -2
+        /// - null value: -1
+        /// - characterEncoding: null
+        /// - semanticType: null
+        /// - encodedOffset: 0
+        /// - encodedLength: 8
+        #[inline]
+        pub fn cl_ord_id(&mut self, value: u64) {
+            self.set_fields |= 1 << 0;
+            let offset = self.offset;
+            self.get_buf_mut().put_u64_at(offset, value);
+        }
+
+        /// REQUIRED enum
+        #[inline]
+        pub fn mass_cancel_request_type(&mut self, value: MassCancelRequestType) {
+            self.set_fields |= 1 << 1;
+            let offset = self.offset + 8;
+            self.get_buf_mut().put_u8_at(offset, value as u8)
+        }
+
+        /// REQUIRED enum
+        #[inline]
+        pub fn mass_cancel_response(&mut self, value: MassCancelResponse) {
+            self.set_fields |= 1 << 2;
+            let offset = self.offset + 9;
+            self.get_buf_mut().put_u8_at(offset, value as u8)
+        }
+
+        /// REQUIRED enum
+        #[inline]
+        pub fn mass_cancel_reject_reason(&mut self, value: MassCancelRejectReason) {
+            self.set_fields |= 1 << 3;
+            let offset = self.offset + 10;
+            self.get_buf_mut().put_u8_at(offset, value as u8)
+        }
+
+        /// primitive field 'totalAffectedOrders'
+        /// - min value: 0
+        /// - max value: 4294967294
+        /// - null value: 4294967295
+        /// - characterEncoding: null
+        /// - semanticType: null
+        /// - encodedOffset: 11
+        /// - encodedLength: 4
+        #[inline]
+        pub fn total_affected_orders(&mut self, value: u32) {
+            self.set_fields |= 1 << 4;
+            let offset = self.offset + 11;
+            self.get_buf_mut().put_u32_at(offset, value);
+        }
+
+        /// primitive field 'instrumentId'
+        /// - min value: 0
+        /// - max value: 4294967294
+        /// - null value: 4294967295
+        /// - characterEncoding: null
+        /// - semanticType: null
+        /// - encodedOffset: 15
+        /// - encodedLength: 4
+        #[inline]
+        pub fn instrument_id(&mut self, value: u32) {
+            self.set_fields |= 1 << 5;
+            let offset = self.offset + 15;
+            self.get_buf_mut().put_u32_at(offset, value);
+        }
+
+        /// REQUIRED enum
+        #[inline]
+        pub fn side(&mut self, value: OrderSide) {
+            self.set_fields |= 1 << 6;
+            let offset = self.offset + 19;
+            self.get_buf_mut().put_u8_at(offset, value as u8)
+        }
+
+        /// REQUIRED enum
+        #[inline]
+        pub fn strategy(&mut self, value: Strategy) {
+            self.set_fields |= 1 << 7;
+            let offset = self.offset + 20;
+            self.get_buf_mut().put_u8_at(offset, value as u8)
+        }
+
+        /// primitive field 'targetCompId'
+        /// - min value: 0
+        /// - max value: 4294967294
+        /// - null value: 4294967295
+        /// - characterEncoding: null
+        /// - semanticType: null
+        /// - encodedOffset: 21
+        /// - encodedLength: 4
+        #[inline]
+        pub fn target_comp_id(&mut self, value: u32) {
+            self.set_fields |= 1 << 8;
+            let offset = self.offset + 21;
+            self.get_buf_mut().put_u32_at(offset, value);
+        }
+
+        /// primitive field 'sourceCompId'
+        /// - min value: 0
+        /// - max value: 4294967294
+        /// - null value: 4294967295
+        /// - characterEncoding: null
+        /// - semanticType: null
+        /// - encodedOffset: 25
+        /// - encodedLength: 4
+        #[inline]
+        pub fn source_comp_id(&mut self, value: u32) {
+            self.set_fields |= 1 << 9;
+            let offset = self.offset + 25;
+            self.get_buf_mut().put_u32_at(offset, value);
+        }
+
+        /// REQUIRED enum
+        #[inline]
+        pub fn target_strategy(&mut self, value: Strategy) {
+            self.set_fields |= 1 << 10;
+            let offset = self.offset + 29;
+            self.get_buf_mut().put_u8_at(offset, value as u8)
+        }
+
+        /// REQUIRED enum
+        #[inline]
+        pub fn source_strategy(&mut self, value: Strategy) {
+            self.set_fields |= 1 << 11;
+            let offset = self.offset + 30;
+            self.get_buf_mut().put_u8_at(offset, value as u8)
+        }
+
+        /// primitive field 'transactTime'
+        /// - min value: 0
+        /// - max value: -2
+        /// - null value: -1
+        /// - characterEncoding: null
+        /// - semanticType: null
+        /// - encodedOffset: 31
+        /// - encodedLength: 8
+        #[inline]
+        pub fn transact_time(&mut self, value: u64) {
+            self.set_fields |= 1 << 12;
+            let offset = self.offset + 31;
+            self.get_buf_mut().put_u64_at(offset, value);
+        }
+
+        /// VAR_DATA ENCODER - character encoding: 'UTF-8'; writes at the limit, so it must be called
+        /// after every group, and only once: a second call would append a second field
+        pub fn text(&mut self, value: &[u8]) -> SbeResult<()> {
+            if self.set_fields & (1 << 13) != 0 {
+                return Err(SbeErr::DuplicateField("text"));
+            }
+            let limit = var_string_encoding_codec::encode_var_data(&mut self.buf, self.limit, value)?;
+            self.set_fields |= 1 << 13;
+            self.limit = limit;
+            Ok(())
+        }
+
+    }
+
+} // end encoder
+
+pub mod decoder {
+    use super::*;
+
+    #[derive(Debug, Default)]
+    pub struct OrderMassCancelReportDecoder<'a, O: ByteOrder = SchemaByteOrder> {
+        buf: ReadBuf<'a, O>,
+        initial_offset: usize,
+        offset: usize,
+        limit: usize,
+        pub acting_block_length: u16,
+        pub acting_version: u16,
+    }
+
+    impl<'a, O: ByteOrder> Reader<'a> for OrderMassCancelReportDecoder<'a, O> {
+        type ByteOrder = O;
+
+        #[inline]
+        fn get_buf(&self) -> &ReadBuf<'a, O> {
+            &self.buf
+        }
+    }
+
+    impl<'a, O: ByteOrder> Decoder<'a> for OrderMassCancelReportDecoder<'a, O> {
+        #[inline]
+        fn get_limit(&self) -> usize {
+            self.limit
+        }
+
+        #[inline]
+        fn set_limit(&mut self, limit: usize) {
+            self.limit = limit;
+        }
+    }
+
+    impl<'a, O: ByteOrder> OrderMassCancelReportDecoder<'a, O> {
+        pub fn wrap(
+            mut self,
+            buf: ReadBuf<'a, O>,
+            offset: usize,
+            acting_block_length: u16,
+            acting_version: u16,
+        ) -> Self {
+            let limit = offset + acting_block_length as usize;
+            self.buf = buf;
+            self.initial_offset = offset;
+            self.offset = offset;
+            self.limit = limit;
+            self.acting_block_length = acting_block_length;
+            self.acting_version = acting_version;
+            self
+        }
+
+        #[inline]
+        pub fn encoded_length(&self) -> usize {
+            self.limit - self.offset
+        }
+
+        pub fn header(self, mut header: MessageHeaderDecoder<ReadBuf<'a, O>>) -> Self {
+            debug_assert_eq!(SBE_TEMPLATE_ID, header.template_id());
+            let acting_block_length = header.block_length();
+            let acting_version = header.version();
+
+            self.wrap(
+                header.parent().unwrap(),
+                message_header_codec::ENCODED_LENGTH,
+                acting_block_length,
+                acting_version,
+            )
+        }
+
+        /// primitive field - 'REQUIRED'
+        #[inline]
+        pub fn cl_ord_id(&self) -> u64 {
+            self.get_buf().get_u64_at(self.offset)
+        }
+
+        /// REQUIRED enum
+        #[inline]
+        pub fn mass_cancel_request_type(&self) -> MassCancelRequestType {
+            self.get_buf().get_u8_at(self.offset + 8).into()
+        }
+
+        /// REQUIRED enum
+        #[inline]
+        pub fn mass_cancel_response(&self) -> MassCancelResponse {
+            self.get_buf().get_u8_at(self.offset + 9).into()
+        }
+
+        /// REQUIRED enum
+        #[inline]
+        pub fn mass_cancel_reject_reason(&self) -> MassCancelRejectReason {
+            self.get_buf().get_u8_at(self.offset + 10).into()
+        }
+
+        /// primitive field - 'REQUIRED'
+        #[inline]
+        pub fn total_affected_orders(&self) -> u32 {
+            self.get_buf().get_u32_at(self.offset + 11)
+        }
+
+        /// primitive field - 'REQUIRED'
+        #[inline]
+        pub fn instrument_id(&self) -> u32 {
+            self.get_buf().get_u32_at(self.offset + 15)
+        }
+
+        /// REQUIRED enum
+        #[inline]
+        pub fn side(&self) -> OrderSide {
+            self.get_buf().get_u8_at(self.offset + 19).into()
+        }
+
+        /// REQUIRED enum
+        #[inline]
+        pub fn strategy(&self) -> Strategy {
+            self.get_buf().get_u8_at(self.offset + 20).into()
+        }
+
+        /// primitive field - 'REQUIRED'
+        #[inline]
+        pub fn target_comp_id(&self) -> u32 {
+            self.get_buf().get_u32_at(self.offset + 21)
+        }
+
+        /// primitive field - 'REQUIRED'
+        #[inline]
+        pub fn source_comp_id(&self) -> u32 {
+            self.get_buf().get_u32_at(self.offset + 25)
+        }
+
+        /// REQUIRED enum
+        #[inline]
+        pub fn target_strategy(&self) -> Strategy {
+            self.get_buf().get_u8_at(self.offset + 29).into()
+        }
+
+        /// REQUIRED enum
+        #[inline]
+        pub fn source_strategy(&self) -> Strategy {
+            self.get_buf().get_u8_at(self.offset + 30).into()
+        }
+
+        /// primitive field - 'REQUIRED'
+        #[inline]
+        pub fn transact_time(&self) -> u64 {
+            self.get_buf().get_u64_at(self.offset + 31)
+        }
+
+        /// VAR_DATA DECODER - character encoding: 'UTF-8'; reads at the limit and moves it past the
+        /// data, so it must be called after every group
+        pub fn text(&mut self) -> SbeResult<&'a [u8]> {
+            let (offset, length) = var_string_encoding_codec::var_data_extent(&self.buf, self.limit)?;
+            self.limit = offset + length;
+            let data: &'a [u8] = self.buf.data;
+            Ok(&data[offset..offset + length])
+        }
+
+        /// A decoder over the same message with the limit back at the end of the block.
+        pub(crate) fn rewound(&self) -> Self {
+            let buf = ReadBuf::with_byte_order(self.buf.data, O::default());
+            Self::default().wrap(buf, self.offset, self.acting_block_length, self.acting_version)
+        }
+
+    }
+
+} // end decoder
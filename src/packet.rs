@@ -0,0 +1,256 @@
+//! Packets batching several framed messages behind one packet header.
+//!
+//! A packet is a [`packet_header_codec`] header holding the sequence number, sending time, total
+//! length and message count, followed by the messages back to back. [`PacketEncoder`] writes
+//! messages straight into its buffer with the [`MessageWriter`] methods and fills in the header
+//! on [`PacketEncoder::finish`]. [`PacketDecoder`] checks the header and walks the messages by
+//! their message headers, group sizes and var data lengths, skipping messages of this schema with
+//! a template id it does not know by their block length.
+use crate::*;
+
+/// Writes packets into a buffer it owns, one at a time.
+#[derive(Clone, Debug, Default)]
+pub struct PacketEncoder<B> {
+    buffer: B,
+    limit: usize,
+    message_count: u16,
+    sequence_number: u64,
+}
+
+#[cfg(feature = "alloc")]
+impl PacketEncoder<alloc::vec::Vec<u8>> {
+    /// Encoder over a zeroed heap buffer of `capacity` bytes, the largest packet it writes.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::new(alloc::vec![0; capacity])
+    }
+}
+
+impl<B> PacketEncoder<B>
+where
+    B: AsRef<[u8]> + AsMut<[u8]>,
+{
+    /// Encoder whose first packet is number 1; the length of `buffer` bounds the packet length.
+    pub fn new(buffer: B) -> Self {
+        Self { buffer, limit: packet_header_codec::ENCODED_LENGTH, message_count: 0, sequence_number: 1 }
+    }
+
+    /// Sequence number the packet being written will carry.
+    #[inline]
+    pub fn sequence_number(&self) -> u64 {
+        self.sequence_number
+    }
+
+    #[inline]
+    pub fn message_count(&self) -> u16 {
+        self.message_count
+    }
+
+    /// Length of the packet written so far, packet header included.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.limit
+    }
+
+    /// Whether no message was written to the packet yet.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.message_count == 0
+    }
+
+    #[inline]
+    pub fn into_inner(self) -> B {
+        self.buffer
+    }
+
+    /// Appends one message encoded by `write`, which calls one message method of the writer and
+    /// returns its result, e.g. `packet.message(|writer| writer.new_order_single(|order| { ... }))`.
+    /// Nothing is appended if the message does not fit.
+    pub fn message<W>(&mut self, write: W) -> SbeResult<&[u8]>
+    where
+        W: for<'w> FnOnce(&'w mut MessageWriter<&'w mut [u8]>) -> SbeResult<&'w [u8]>,
+    {
+        if self.message_count == u16::MAX {
+            return Err(SbeErr::GroupCountOverflow);
+        }
+        let start = self.limit;
+        let room = self.buffer.as_mut().get_mut(start..).ok_or(SbeErr::BufferTooShort)?;
+        let length = write(&mut MessageWriter::new(room))?.len();
+        self.limit += length;
+        self.message_count += 1;
+        Ok(&self.buffer.as_ref()[start..self.limit])
+    }
+
+    /// Appends one already framed message, e.g. from a [`MessageWriter`], byte-for-byte.
+    pub fn push(&mut self, message: &[u8]) -> SbeResult<()> {
+        if self.message_count == u16::MAX {
+            return Err(SbeErr::GroupCountOverflow);
+        }
+        let end = self.limit + message.len();
+        self.buffer.as_mut().get_mut(self.limit..end).ok_or(SbeErr::BufferTooShort)?.copy_from_slice(message);
+        self.limit = end;
+        self.message_count += 1;
+        Ok(())
+    }
+
+    /// Writes the packet header and returns the packet; the next message starts the packet with
+    /// the next sequence number.
+    pub fn finish(&mut self, sending_time: u64) -> SbeResult<&[u8]> {
+        let data = self.buffer.as_mut();
+        if data.len() < self.limit || self.limit > u32::MAX as usize {
+            return Err(SbeErr::BufferTooShort);
+        }
+        let mut header = PacketHeaderEncoder::<WriteBuf<'_>>::default().wrap(WriteBuf::new(data), 0);
+        header.sequence_number(self.sequence_number);
+        header.sending_time(sending_time);
+        header.packet_length(self.limit as u32);
+        header.message_count(self.message_count);
+
+        let end = self.limit;
+        self.limit = packet_header_codec::ENCODED_LENGTH;
+        self.message_count = 0;
+        self.sequence_number += 1;
+        Ok(&self.buffer.as_ref()[..end])
+    }
+}
+
+/// Reads a packet written by a [`PacketEncoder`].
+#[derive(Debug)]
+pub struct PacketDecoder<'a> {
+    data: &'a [u8],
+    header: PacketHeaderDecoder<ReadBuf<'a>>,
+}
+
+impl<'a> PacketDecoder<'a> {
+    /// Reads the packet header at the start of `data`, which must hold the whole packet. Bytes
+    /// past the packet length, such as the next packet, are not part of this one.
+    pub fn wrap(data: &'a [u8]) -> SbeResult<Self> {
+        if data.len() < packet_header_codec::ENCODED_LENGTH {
+            return Err(SbeErr::BufferTooShort);
+        }
+        let header = PacketHeaderDecoder::default().wrap(ReadBuf::new(data), 0);
+        let length = header.packet_length() as usize;
+        if length < packet_header_codec::ENCODED_LENGTH || data.len() < length {
+            return Err(SbeErr::BufferTooShort);
+        }
+        let data = &data[..length];
+        Ok(Self { data, header: PacketHeaderDecoder::default().wrap(ReadBuf::new(data), 0) })
+    }
+
+    #[inline]
+    pub fn sequence_number(&self) -> u64 {
+        self.header.sequence_number()
+    }
+
+    #[inline]
+    pub fn sending_time(&self) -> u64 {
+        self.header.sending_time()
+    }
+
+    #[inline]
+    pub fn message_count(&self) -> u16 {
+        self.header.message_count()
+    }
+
+    /// The whole packet, packet header included.
+    #[inline]
+    pub fn bytes(&self) -> &'a [u8] {
+        self.data
+    }
+
+    /// Iterates the `messageCount` messages of the packet, skipping those with an unknown template
+    /// id, which are taken to be a block without groups or var data. Iteration ends after the
+    /// first error: a message that cannot be decoded or that overruns the packet, or
+    /// [`SbeErr::TrailingBytes`] when the last message ends before the packet length.
+    pub fn messages(&self) -> PacketMessages<'a> {
+        let offset = packet_header_codec::ENCODED_LENGTH;
+        PacketMessages { data: self.data, offset, remaining: self.message_count() }
+    }
+}
+
+/// One framed message of a packet.
+#[derive(Clone, Copy, Debug)]
+pub struct PacketMessage<'a> {
+    pub offset: usize,
+    bytes: &'a [u8],
+}
+
+impl<'a> PacketMessage<'a> {
+    #[inline]
+    pub fn header(&self) -> MessageHeaderDecoder<ReadBuf<'a>> {
+        MessageHeaderDecoder::default().wrap(ReadBuf::new(self.bytes), 0)
+    }
+
+    /// The framed message bytes, message header included.
+    #[inline]
+    pub fn bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    #[inline]
+    pub fn message(&self) -> SbeResult<AnyMessage<'a>> {
+        AnyMessage::decode(self.bytes, 0)
+    }
+}
+
+/// Iterator over the messages of a packet, see [`PacketDecoder::messages`].
+#[derive(Clone, Debug)]
+pub struct PacketMessages<'a> {
+    data: &'a [u8],
+    offset: usize,
+    remaining: u16,
+}
+
+impl<'a> Iterator for PacketMessages<'a> {
+    type Item = SbeResult<PacketMessage<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.remaining == 0 {
+                if self.offset == self.data.len() {
+                    return None;
+                }
+                self.offset = self.data.len();
+                return Some(Err(SbeErr::TrailingBytes));
+            }
+            match self.message_end() {
+                Ok((end, known)) => {
+                    let offset = core::mem::replace(&mut self.offset, end);
+                    self.remaining -= 1;
+                    if known {
+                        return Some(Ok(PacketMessage { offset, bytes: &self.data[offset..end] }));
+                    }
+                }
+                Err(err) => {
+                    self.remaining = 0;
+                    self.offset = self.data.len();
+                    return Some(Err(err));
+                }
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let trailing = (self.offset != self.data.len()) as usize;
+        (0, Some(self.remaining as usize + trailing))
+    }
+}
+
+impl<'a> PacketMessages<'a> {
+    /// End offset of the message at the current offset and whether its template id is known. A
+    /// message of this schema with an unknown template id ends after its block.
+    fn message_end(&self) -> SbeResult<(usize, bool)> {
+        match AnyMessage::decode(self.data, self.offset).and_then(AnyMessage::end_offset) {
+            Ok(end) => Ok((end, true)),
+            Err(SbeErr::UnknownTemplateId(_)) => {
+                let header = MessageHeaderDecoder::default().wrap(ReadBuf::new(self.data), self.offset);
+                let end = self.offset + message_header_codec::ENCODED_LENGTH + header.block_length() as usize;
+                if self.data.len() < end {
+                    return Err(SbeErr::BufferTooShort);
+                }
+                Ok((end, false))
+            }
+            Err(err) => Err(err),
+        }
+    }
+}
@@ -0,0 +1,156 @@
+use crate::*;
+
+pub use encoder::*;
+pub use decoder::*;
+
+pub const ENCODED_LENGTH: usize = 22;
+
+pub mod encoder {
+    use super::*;
+
+    #[derive(Debug, Default)]
+    pub struct PacketHeaderEncoder<P> {
+        parent: Option<P>,
+        offset: usize,
+    }
+
+    impl<'a, P> Writer<'a> for PacketHeaderEncoder<P> where P: Writer<'a> + Default {
+        type ByteOrder = P::ByteOrder;
+
+        #[inline]
+        fn get_buf_mut(&mut self) -> &mut WriteBuf<'a, P::ByteOrder> {
+            if let Some(parent) = self.parent.as_mut() {
+                parent.get_buf_mut()
+            } else {
+                panic!("parent was None")
+            }
+        }
+    }
+
+    impl<'a, P> PacketHeaderEncoder<P> where P: Writer<'a> + Default {
+        pub fn wrap(mut self, parent: P, offset: usize) -> Self {
+            self.parent = Some(parent);
+            self.offset = offset;
+            self
+        }
+
+        #[inline]
+        pub fn parent(&mut self) -> SbeResult<P> {
+            self.parent.take().ok_or(SbeErr::ParentNotSet)
+        }
+
+        /// primitive field 'sequenceNumber'
+        /// - min value: 0
+        /// - max value: -2
+        /// - null value: -1
+        /// - characterEncoding: null
+        /// - semanticType: null
+        /// - encodedOffset: 0
+        /// - encodedLength: 8
+        #[inline]
+        pub fn sequence_number(&mut self, value: u64) {
+            let offset = self.offset;
+            self.get_buf_mut().put_u64_at(offset, value);
+        }
+
+        /// primitive field 'sendingTime'
+        /// - min value: 0
+        /// - max value: -2
+        /// - null value: -1
+        /// - characterEncoding: null
+        /// - semanticType: null
+        /// - encodedOffset: 8
+        /// - encodedLength: 8
+        #[inline]
+        pub fn sending_time(&mut self, value: u64) {
+            let offset = self.offset + 8;
+            self.get_buf_mut().put_u64_at(offset, value);
+        }
+
+        /// primitive field 'packetLength'
+        /// - min value: 0
+        /// - max value: 4294967294
+        /// - null value: 4294967295
+        /// - characterEncoding: null
+        /// - semanticType: null
+        /// - encodedOffset: 16
+        /// - encodedLength: 4
+        #[inline]
+        pub fn packet_length(&mut self, value: u32) {
+            let offset = self.offset + 16;
+            self.get_buf_mut().put_u32_at(offset, value);
+        }
+
+        /// primitive field 'messageCount'
+        /// - min value: 0
+        /// - max value: 65534
+        /// - null value: 65535
+        /// - characterEncoding: null
+        /// - semanticType: null
+        /// - encodedOffset: 20
+        /// - encodedLength: 2
+        #[inline]
+        pub fn message_count(&mut self, value: u16) {
+            let offset = self.offset + 20;
+            self.get_buf_mut().put_u16_at(offset, value);
+        }
+
+    }
+} // end encoder mod 
+
+pub mod decoder {
+    use super::*;
+
+    #[derive(Debug, Default)]
+    pub struct PacketHeaderDecoder<P> {
+        parent: Option<P>,
+        offset: usize,
+    }
+
+    impl<'a, P> Reader<'a> for PacketHeaderDecoder<P> where P: Reader<'a> + Default {
+        type ByteOrder = P::ByteOrder;
+
+        #[inline]
+        fn get_buf(&self) -> &ReadBuf<'a, P::ByteOrder> {
+            self.parent.as_ref().expect("parent missing").get_buf()
+        }
+    }
+
+    impl<'a, P> PacketHeaderDecoder<P> where P: Reader<'a> + Default {
+        pub fn wrap(mut self, parent: P, offset: usize) -> Self {
+            self.parent = Some(parent);
+            self.offset = offset;
+            self
+        }
+
+        #[inline]
+        pub fn parent(&mut self) -> SbeResult<P> {
+            self.parent.take().ok_or(SbeErr::ParentNotSet)
+        }
+
+        /// primitive field - 'REQUIRED'
+        #[inline]
+        pub fn sequence_number(&self) -> u64 {
+            self.get_buf().get_u64_at(self.offset)
+        }
+
+        /// primitive field - 'REQUIRED'
+        #[inline]
+        pub fn sending_time(&self) -> u64 {
+            self.get_buf().get_u64_at(self.offset + 8)
+        }
+
+        /// primitive field - 'REQUIRED'
+        #[inline]
+        pub fn packet_length(&self) -> u32 {
+            self.get_buf().get_u32_at(self.offset + 16)
+        }
+
+        /// primitive field - 'REQUIRED'
+        #[inline]
+        pub fn message_count(&self) -> u16 {
+            self.get_buf().get_u16_at(self.offset + 20)
+        }
+
+    }
+} // end decoder mod 
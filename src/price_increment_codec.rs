@@ -3,30 +3,44 @@ use crate::*;
 pub use encoder::*;
 pub use decoder::*;
 
-pub const SBE_BLOCK_LENGTH: u16 = 8;
+pub const SBE_BLOCK_LENGTH: u16 = 16;
 pub const SBE_TEMPLATE_ID: u16 = 2;
 pub const SBE_SCHEMA_ID: u16 = 1;
-pub const SBE_SCHEMA_VERSION: u16 = 0;
+pub const SBE_SCHEMA_VERSION: u16 = 4;
+/// schema version from which the root block carries 'mdReqId'
+pub const MD_REQ_ID_SINCE_VERSION: u16 = 3;
+/// block length of senders predating 'mdReqId'
+pub const BLOCK_LENGTH_BEFORE_MD_REQ_ID: u16 = 8;
+
+/// names of the REQUIRED fields, in the bit order of the encoder's set-field mask
+pub const REQUIRED_FIELDS: &[&str] = &[
+    "compId",
+    "instrumentId",
+];
 
 pub mod encoder {
     use super::*;
+    use core::borrow::Borrow;
 
     #[derive(Debug, Default)]
-    pub struct PriceIncrementEncoder<'a> {
-        buf: WriteBuf<'a>,
+    pub struct PriceIncrementEncoder<'a, O: ByteOrder = SchemaByteOrder> {
+        buf: WriteBuf<'a, O>,
         initial_offset: usize,
         offset: usize,
         limit: usize,
+        set_fields: u64,
     }
 
-    impl<'a> Writer<'a> for PriceIncrementEncoder<'a> {
+    impl<'a, O: ByteOrder> Writer<'a> for PriceIncrementEncoder<'a, O> {
+        type ByteOrder = O;
+
         #[inline]
-        fn get_buf_mut(&mut self) -> &mut WriteBuf<'a> {
+        fn get_buf_mut(&mut self) -> &mut WriteBuf<'a, O> {
             &mut self.buf
         }
     }
 
-    impl<'a> Encoder<'a> for PriceIncrementEncoder<'a> {
+    impl<'a, O: ByteOrder> Encoder<'a> for PriceIncrementEncoder<'a, O> {
         #[inline]
         fn get_limit(&self) -> usize {
             self.limit
@@ -38,13 +52,16 @@ pub mod encoder {
         }
     }
 
-    impl<'a> PriceIncrementEncoder<'a> {
-        pub fn wrap(mut self, buf: WriteBuf<'a>, offset: usize) -> Self {
+    impl<'a, O: ByteOrder> PriceIncrementEncoder<'a, O> {
+        pub fn wrap(mut self, buf: WriteBuf<'a, O>, offset: usize) -> Self {
             let limit = offset + SBE_BLOCK_LENGTH as usize;
             self.buf = buf;
             self.initial_offset = offset;
             self.offset = offset;
             self.limit = limit;
+            self.set_fields = 0;
+            // optional 'mdReqId' stays null unless the caller sets it
+            self.buf.put_u64_at(offset + 8, u64::MAX);
             self
         }
 
@@ -62,6 +79,14 @@ pub mod encoder {
             header
         }
 
+        /// Returns the encoded length and the buffer, or the first REQUIRED field that was never set.
+        pub fn finish(self) -> SbeResult<(usize, WriteBuf<'a, O>)> {
+            if let Some(index) = (0..REQUIRED_FIELDS.len()).find(|index| self.set_fields & (1 << index) == 0) {
+                return Err(SbeErr::MissingRequiredField(REQUIRED_FIELDS[index]));
+            }
+            Ok((self.encoded_length(), self.buf))
+        }
+
         /// primitive field 'compId'
         /// - min value: 0
         /// - max value: 4294967294
@@ -72,6 +97,7 @@ pub mod encoder {
         /// - encodedLength: 4
         #[inline]
         pub fn comp_id(&mut self, value: u32) {
+            self.set_fields |= 1 << 0;
             let offset = self.offset;
             self.get_buf_mut().put_u32_at(offset, value);
         }
@@ -86,19 +112,76 @@ pub mod encoder {
         /// - encodedLength: 4
         #[inline]
         pub fn instrument_id(&mut self, value: u32) {
+            self.set_fields |= 1 << 1;
             let offset = self.offset + 4;
             self.get_buf_mut().put_u32_at(offset, value);
         }
 
+        /// primitive field 'mdReqId' - the 'MarketDataRequest' this message answers, null when unsolicited
+        /// - min value: 0
+        /// - max value: -2
+        /// - null value: -1
+        /// - characterEncoding: null
+        /// - semanticType: null
+        /// - encodedOffset: 8
+        /// - encodedLength: 8
+        /// - sinceVersion: 3
+        #[inline]
+        pub fn md_req_id(&mut self, value: u64) {
+            self.set_fields |= 1 << 2;
+            let offset = self.offset + 8;
+            self.get_buf_mut().put_u64_at(offset, value);
+        }
+
+        /// GROUP ENCODER - writes the whole 'bids' group, so it must be called before `encode_offers`;
+        /// returns the number of entries written
+        pub fn encode_bids<I>(&mut self, levels: I) -> SbeResult<usize>
+        where
+            I: IntoIterator,
+            I::Item: Borrow<PriceLevelIncrement>,
+        {
+            self.encode_group(levels)
+        }
+
+        /// GROUP ENCODER - writes the whole 'offers' group, so it must be called after `encode_bids`;
+        /// returns the number of entries written
+        pub fn encode_offers<I>(&mut self, levels: I) -> SbeResult<usize>
+        where
+            I: IntoIterator,
+            I::Item: Borrow<PriceLevelIncrement>,
+        {
+            self.encode_group(levels)
+        }
+
+        fn encode_group<I>(&mut self, levels: I) -> SbeResult<usize>
+        where
+            I: IntoIterator,
+            I::Item: Borrow<PriceLevelIncrement>,
+        {
+            let (limit, count) = group_size_encoding_codec::encode_group(
+                &mut self.buf,
+                self.limit,
+                price_level_increment_codec::ENCODED_LENGTH,
+                levels,
+                |buf, offset, level| {
+                    let mut encoder = PriceLevelIncrementEncoder::default().wrap(buf, offset);
+                    encoder.copy_from(level.borrow());
+                    encoder.parent().expect("parent set")
+                },
+            )?;
+            self.limit = limit;
+            Ok(count)
+        }
+
         /// GROUP ENCODER
         #[inline]
-        pub fn bids_encoder(self, count: u8, bids_encoder: BidsEncoder<Self>) -> BidsEncoder<Self> {
+        pub fn bids_encoder(self, count: u16, bids_encoder: BidsEncoder<Self>) -> BidsEncoder<Self> {
             bids_encoder.wrap(self, count)
         }
 
         /// GROUP ENCODER
         #[inline]
-        pub fn offers_encoder(self, count: u8, offers_encoder: OffersEncoder<Self>) -> OffersEncoder<Self> {
+        pub fn offers_encoder(self, count: u16, offers_encoder: OffersEncoder<Self>) -> OffersEncoder<Self> {
             offers_encoder.wrap(self, count)
         }
 
@@ -107,15 +190,17 @@ pub mod encoder {
     #[derive(Debug, Default)]
     pub struct BidsEncoder<P> {
         parent: Option<P>,
-        count: u8,
+        count: u16,
         index: usize,
         offset: usize,
         initial_limit: usize,
     }
 
     impl<'a, P> Writer<'a> for BidsEncoder<P> where P: Writer<'a> + Default {
+        type ByteOrder = P::ByteOrder;
+
         #[inline]
-        fn get_buf_mut(&mut self) -> &mut WriteBuf<'a> {
+        fn get_buf_mut(&mut self) -> &mut WriteBuf<'a, P::ByteOrder> {
             if let Some(parent) = self.parent.as_mut() {
                 parent.get_buf_mut()
             } else {
@@ -141,12 +226,12 @@ pub mod encoder {
         pub fn wrap(
             mut self,
             mut parent: P,
-            count: u8,
+            count: u16,
         ) -> Self {
             let initial_limit = parent.get_limit();
-            parent.set_limit(initial_limit + 2);
-            parent.get_buf_mut().put_u8_at(initial_limit, Self::block_length());
-            parent.get_buf_mut().put_u8_at(initial_limit + 1, count);
+            parent.set_limit(initial_limit + group_size_encoding_codec::ENCODED_LENGTH);
+            parent.get_buf_mut().put_u16_at(initial_limit, Self::block_length());
+            parent.get_buf_mut().put_u16_at(initial_limit + 2, count);
             self.parent = Some(parent);
             self.count = count;
             self.index = usize::MAX;
@@ -156,7 +241,7 @@ pub mod encoder {
         }
 
         #[inline]
-        pub fn block_length() -> u8 {
+        pub fn block_length() -> u16 {
             29
         }
 
@@ -194,15 +279,17 @@ pub mod encoder {
     #[derive(Debug, Default)]
     pub struct OffersEncoder<P> {
         parent: Option<P>,
-        count: u8,
+        count: u16,
         index: usize,
         offset: usize,
         initial_limit: usize,
     }
 
     impl<'a, P> Writer<'a> for OffersEncoder<P> where P: Writer<'a> + Default {
+        type ByteOrder = P::ByteOrder;
+
         #[inline]
-        fn get_buf_mut(&mut self) -> &mut WriteBuf<'a> {
+        fn get_buf_mut(&mut self) -> &mut WriteBuf<'a, P::ByteOrder> {
             if let Some(parent) = self.parent.as_mut() {
                 parent.get_buf_mut()
             } else {
@@ -228,12 +315,12 @@ pub mod encoder {
         pub fn wrap(
             mut self,
             mut parent: P,
-            count: u8,
+            count: u16,
         ) -> Self {
             let initial_limit = parent.get_limit();
-            parent.set_limit(initial_limit + 2);
-            parent.get_buf_mut().put_u8_at(initial_limit, Self::block_length());
-            parent.get_buf_mut().put_u8_at(initial_limit + 1, count);
+            parent.set_limit(initial_limit + group_size_encoding_codec::ENCODED_LENGTH);
+            parent.get_buf_mut().put_u16_at(initial_limit, Self::block_length());
+            parent.get_buf_mut().put_u16_at(initial_limit + 2, count);
             self.parent = Some(parent);
             self.count = count;
             self.index = usize::MAX;
@@ -243,7 +330,7 @@ pub mod encoder {
         }
 
         #[inline]
-        pub fn block_length() -> u8 {
+        pub fn block_length() -> u16 {
             29
         }
 
@@ -284,8 +371,8 @@ pub mod decoder {
     use super::*;
 
     #[derive(Debug, Default)]
-    pub struct PriceIncrementDecoder<'a> {
-        buf: ReadBuf<'a>,
+    pub struct PriceIncrementDecoder<'a, O: ByteOrder = SchemaByteOrder> {
+        buf: ReadBuf<'a, O>,
         initial_offset: usize,
         offset: usize,
         limit: usize,
@@ -293,14 +380,16 @@ pub mod decoder {
         pub acting_version: u16,
     }
 
-    impl<'a> Reader<'a> for PriceIncrementDecoder<'a> {
+    impl<'a, O: ByteOrder> Reader<'a> for PriceIncrementDecoder<'a, O> {
+        type ByteOrder = O;
+
         #[inline]
-        fn get_buf(&self) -> &ReadBuf<'a> {
+        fn get_buf(&self) -> &ReadBuf<'a, O> {
             &self.buf
         }
     }
 
-    impl<'a> Decoder<'a> for PriceIncrementDecoder<'a> {
+    impl<'a, O: ByteOrder> Decoder<'a> for PriceIncrementDecoder<'a, O> {
         #[inline]
         fn get_limit(&self) -> usize {
             self.limit
@@ -312,10 +401,10 @@ pub mod decoder {
         }
     }
 
-    impl<'a> PriceIncrementDecoder<'a> {
+    impl<'a, O: ByteOrder> PriceIncrementDecoder<'a, O> {
         pub fn wrap(
             mut self,
-            buf: ReadBuf<'a>,
+            buf: ReadBuf<'a, O>,
             offset: usize,
             acting_block_length: u16,
             acting_version: u16,
@@ -335,7 +424,7 @@ pub mod decoder {
             self.limit - self.offset
         }
 
-        pub fn header(self, mut header: MessageHeaderDecoder<ReadBuf<'a>>) -> Self {
+        pub fn header(self, mut header: MessageHeaderDecoder<ReadBuf<'a, O>>) -> Self {
             debug_assert_eq!(SBE_TEMPLATE_ID, header.template_id());
             let acting_block_length = header.block_length();
             let acting_version = header.version();
@@ -360,6 +449,47 @@ pub mod decoder {
             self.get_buf().get_u32_at(self.offset + 4)
         }
 
+        /// primitive field - 'OPTIONAL'; null when unsolicited or for senders predating the field
+        #[inline]
+        pub fn md_req_id(&self) -> u64 {
+            if self.acting_version < MD_REQ_ID_SINCE_VERSION {
+                return u64::MAX;
+            }
+            self.get_buf().get_u64_at(self.offset + 8)
+        }
+
+        /// GROUP ITERATOR - reads the 'bids' group and moves the limit past it, so it must be
+        /// called before `offers`
+        pub fn bids(&mut self) -> SbeResult<PriceLevelIncrements<'a, O>> {
+            self.group(price_level_increment_codec::ENCODED_LENGTH)
+        }
+
+        /// GROUP ITERATOR - reads the 'offers' group and moves the limit past it, so it must be
+        /// called after `bids`
+        pub fn offers(&mut self) -> SbeResult<PriceLevelIncrements<'a, O>> {
+            self.group(price_level_increment_codec::ENCODED_LENGTH)
+        }
+
+        #[inline]
+        fn group(&mut self, entry_length: usize) -> SbeResult<PriceLevelIncrements<'a, O>> {
+            let (offset, block_length, count) =
+                if self.acting_version >= group_size_encoding_codec::GROUP_SIZE_ENCODING_SINCE_VERSION {
+                    group_size_encoding_codec::group_extent(&self.buf, self.limit, entry_length)?
+                } else {
+                    small_group_size_encoding_codec::group_extent(&self.buf, self.limit, entry_length)?
+                };
+            self.limit = offset + block_length * count;
+            Ok(PriceLevelIncrements::new(self.buf.data, offset, block_length, count))
+        }
+
+        /// Both groups read from the end of the root block, whether or not `bids`/`offers` were
+        /// already called on this decoder, which is left untouched.
+        pub(crate) fn groups(&self) -> SbeResult<(PriceLevelIncrements<'a, O>, PriceLevelIncrements<'a, O>)> {
+            let buf = ReadBuf::with_byte_order(self.buf.data, O::default());
+            let mut decoder = Self::default().wrap(buf, self.offset, self.acting_block_length, self.acting_version);
+            Ok((decoder.bids()?, decoder.offers()?))
+        }
+
         /// GROUP DECODER
         #[inline]
         pub fn bids_decoder(self) -> BidsDecoder<Self> {
@@ -381,14 +511,17 @@ pub mod decoder {
         parent: Option<P>,
         block_length: usize,
         acting_version: usize,
-        count: u8,
+        count: u16,
         index: usize,
         offset: usize,
+        error: Option<SbeErr>,
     }
 
     impl<'a, P> Reader<'a> for BidsDecoder<P> where P: Reader<'a> + Default {
+        type ByteOrder = P::ByteOrder;
+
         #[inline]
-        fn get_buf(&self) -> &ReadBuf<'a> {
+        fn get_buf(&self) -> &ReadBuf<'a, P::ByteOrder> {
             self.parent.as_ref().expect("parent missing").get_buf()
         }
     }
@@ -406,21 +539,35 @@ pub mod decoder {
     }
 
     impl<'a, P> BidsDecoder<P> where P: Decoder<'a> + Default {
+        /// Checks once that the group header and every entry fit in the buffer, as the group
+        /// iterators do. A group that does not fit is empty and `advance` fails with the error.
         pub fn wrap(
             mut self,
             mut parent: P,
             acting_version: usize,
         ) -> Self {
             let initial_offset = parent.get_limit();
-            let block_length = parent.get_buf().get_u8_at(initial_offset) as usize;
-            let count = parent.get_buf().get_u8_at(initial_offset + 1);
-            parent.set_limit(initial_offset + 2);
+            let entry_length = price_level_increment_codec::ENCODED_LENGTH;
+            let extent =
+                if acting_version >= group_size_encoding_codec::GROUP_SIZE_ENCODING_SINCE_VERSION as usize {
+                    group_size_encoding_codec::group_extent(parent.get_buf(), initial_offset, entry_length)
+                } else {
+                    small_group_size_encoding_codec::group_extent(parent.get_buf(), initial_offset, entry_length)
+                };
+            let (block_length, count) = match extent {
+                Ok((first_offset, block_length, count)) => {
+                    parent.set_limit(first_offset);
+                    (block_length, count as u16)
+                }
+                Err(_) => (0, 0),
+            };
             self.parent = Some(parent);
             self.block_length = block_length;
             self.acting_version = acting_version;
             self.count = count;
             self.index = usize::MAX;
             self.offset = 0;
+            self.error = extent.err();
             self
         }
 
@@ -431,19 +578,22 @@ pub mod decoder {
         }
 
         #[inline]
-        pub fn count(&self) -> u8 {
+        pub fn count(&self) -> u16 {
             self.count
         }
 
         /// will return Some(current index) when successful otherwise None
         pub fn advance(&mut self) -> SbeResult<Option<usize>> {
+            if let Some(error) = self.error {
+                return Err(error);
+            }
             let index = self.index.wrapping_add(1);
             if index >= self.count as usize {
                  return Ok(None);
             }
             if let Some(parent) = self.parent.as_mut() {
                 self.offset = parent.get_limit();
-                parent.set_limit(self.offset + self.block_length as usize);
+                parent.set_limit(self.offset + self.block_length);
                 self.index = index;
                 Ok(Some(index))
             } else {
@@ -451,6 +601,29 @@ pub mod decoder {
             }
         }
 
+        /// Copies the next `increments.len()` entries, or all remaining ones if fewer, into `increments` and
+        /// advances past them. The extent of the whole run is checked once rather than per field.
+        /// Returns the number of entries copied.
+        pub fn read_all_into(&mut self, increments: &mut [PriceLevelIncrement]) -> SbeResult<usize> {
+            let remaining = (self.count as usize).wrapping_sub(self.index.wrapping_add(1));
+            let count = remaining.min(increments.len());
+            if let Some(error) = self.error {
+                return Err(error);
+            }
+            let parent = self.parent.as_mut().ok_or(SbeErr::ParentNotSet)?;
+            let offset = parent.get_limit();
+            let entries = group_size_encoding_codec::entries(parent.get_buf(), offset, self.block_length, count)?;
+            for (value, entry) in increments.iter_mut().zip(entries) {
+                *value = price_level_increment_codec::read_price_level_increment::<P::ByteOrder>(entry);
+            }
+            if count > 0 {
+                self.index = self.index.wrapping_add(count);
+                self.offset = offset + (count - 1) * self.block_length;
+                parent.set_limit(offset + count * self.block_length);
+            }
+            Ok(count)
+        }
+
         /// COMPOSITE DECODER
         #[inline]
         pub fn bid_decoder(self) -> PriceLevelIncrementDecoder<Self> {
@@ -465,14 +638,17 @@ pub mod decoder {
         parent: Option<P>,
         block_length: usize,
         acting_version: usize,
-        count: u8,
+        count: u16,
         index: usize,
         offset: usize,
+        error: Option<SbeErr>,
     }
 
     impl<'a, P> Reader<'a> for OffersDecoder<P> where P: Reader<'a> + Default {
+        type ByteOrder = P::ByteOrder;
+
         #[inline]
-        fn get_buf(&self) -> &ReadBuf<'a> {
+        fn get_buf(&self) -> &ReadBuf<'a, P::ByteOrder> {
             self.parent.as_ref().expect("parent missing").get_buf()
         }
     }
@@ -490,21 +666,35 @@ pub mod decoder {
     }
 
     impl<'a, P> OffersDecoder<P> where P: Decoder<'a> + Default {
+        /// Checks once that the group header and every entry fit in the buffer, as the group
+        /// iterators do. A group that does not fit is empty and `advance` fails with the error.
         pub fn wrap(
             mut self,
             mut parent: P,
             acting_version: usize,
         ) -> Self {
             let initial_offset = parent.get_limit();
-            let block_length = parent.get_buf().get_u8_at(initial_offset) as usize;
-            let count = parent.get_buf().get_u8_at(initial_offset + 1);
-            parent.set_limit(initial_offset + 2);
+            let entry_length = price_level_increment_codec::ENCODED_LENGTH;
+            let extent =
+                if acting_version >= group_size_encoding_codec::GROUP_SIZE_ENCODING_SINCE_VERSION as usize {
+                    group_size_encoding_codec::group_extent(parent.get_buf(), initial_offset, entry_length)
+                } else {
+                    small_group_size_encoding_codec::group_extent(parent.get_buf(), initial_offset, entry_length)
+                };
+            let (block_length, count) = match extent {
+                Ok((first_offset, block_length, count)) => {
+                    parent.set_limit(first_offset);
+                    (block_length, count as u16)
+                }
+                Err(_) => (0, 0),
+            };
             self.parent = Some(parent);
             self.block_length = block_length;
             self.acting_version = acting_version;
             self.count = count;
             self.index = usize::MAX;
             self.offset = 0;
+            self.error = extent.err();
             self
         }
 
@@ -515,19 +705,22 @@ pub mod decoder {
         }
 
         #[inline]
-        pub fn count(&self) -> u8 {
+        pub fn count(&self) -> u16 {
             self.count
         }
 
         /// will return Some(current index) when successful otherwise None
         pub fn advance(&mut self) -> SbeResult<Option<usize>> {
+            if let Some(error) = self.error {
+                return Err(error);
+            }
             let index = self.index.wrapping_add(1);
             if index >= self.count as usize {
                  return Ok(None);
             }
             if let Some(parent) = self.parent.as_mut() {
                 self.offset = parent.get_limit();
-                parent.set_limit(self.offset + self.block_length as usize);
+                parent.set_limit(self.offset + self.block_length);
                 self.index = index;
                 Ok(Some(index))
             } else {
@@ -535,6 +728,29 @@ pub mod decoder {
             }
         }
 
+        /// Copies the next `increments.len()` entries, or all remaining ones if fewer, into `increments` and
+        /// advances past them. The extent of the whole run is checked once rather than per field.
+        /// Returns the number of entries copied.
+        pub fn read_all_into(&mut self, increments: &mut [PriceLevelIncrement]) -> SbeResult<usize> {
+            let remaining = (self.count as usize).wrapping_sub(self.index.wrapping_add(1));
+            let count = remaining.min(increments.len());
+            if let Some(error) = self.error {
+                return Err(error);
+            }
+            let parent = self.parent.as_mut().ok_or(SbeErr::ParentNotSet)?;
+            let offset = parent.get_limit();
+            let entries = group_size_encoding_codec::entries(parent.get_buf(), offset, self.block_length, count)?;
+            for (value, entry) in increments.iter_mut().zip(entries) {
+                *value = price_level_increment_codec::read_price_level_increment::<P::ByteOrder>(entry);
+            }
+            if count > 0 {
+                self.index = self.index.wrapping_add(count);
+                self.offset = offset + (count - 1) * self.block_length;
+                parent.set_limit(offset + count * self.block_length);
+            }
+            Ok(count)
+        }
+
         /// COMPOSITE DECODER
         #[inline]
         pub fn offer_decoder(self) -> PriceLevelIncrementDecoder<Self> {
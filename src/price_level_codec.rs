@@ -5,6 +5,15 @@ pub use decoder::*;
 
 pub const ENCODED_LENGTH: usize = 28;
 
+/// Copy of a decoded 'PriceLevel' composite.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PriceLevel {
+    pub id: u32,
+    pub price: f64,
+    pub leaves_qty: f64,
+    pub transact_time: i64,
+}
+
 pub mod encoder {
     use super::*;
 
@@ -15,8 +24,10 @@ pub mod encoder {
     }
 
     impl<'a, P> Writer<'a> for PriceLevelEncoder<P> where P: Writer<'a> + Default {
+        type ByteOrder = P::ByteOrder;
+
         #[inline]
-        fn get_buf_mut(&mut self) -> &mut WriteBuf<'a> {
+        fn get_buf_mut(&mut self) -> &mut WriteBuf<'a, P::ByteOrder> {
             if let Some(parent) = self.parent.as_mut() {
                 parent.get_buf_mut()
             } else {
@@ -93,6 +104,14 @@ pub mod encoder {
             self.get_buf_mut().put_i64_at(offset, value);
         }
 
+        #[inline]
+        pub fn copy_from(&mut self, value: &PriceLevel) {
+            self.id(value.id);
+            self.price(value.price);
+            self.leaves_qty(value.leaves_qty);
+            self.transact_time(value.transact_time);
+        }
+
     }
 } // end encoder mod 
 
@@ -106,8 +125,10 @@ pub mod decoder {
     }
 
     impl<'a, P> Reader<'a> for PriceLevelDecoder<P> where P: Reader<'a> + Default {
+        type ByteOrder = P::ByteOrder;
+
         #[inline]
-        fn get_buf(&self) -> &ReadBuf<'a> {
+        fn get_buf(&self) -> &ReadBuf<'a, P::ByteOrder> {
             self.parent.as_ref().expect("parent missing").get_buf()
         }
     }
@@ -148,5 +169,77 @@ pub mod decoder {
             self.get_buf().get_i64_at(self.offset + 20)
         }
 
+        #[inline]
+        pub fn to_price_level(&self) -> PriceLevel {
+            PriceLevel {
+                id: self.id(),
+                price: self.price(),
+                leaves_qty: self.leaves_qty(),
+                transact_time: self.transact_time(),
+            }
+        }
+
+    }
+
+    /// Decodes one entry of a group of 'PriceLevel' composites. Offsets are constant within the
+    /// fixed-size entry, so the per-field bounds checks compile away.
+    #[inline]
+    pub(crate) fn read_price_level<O: ByteOrder>(entry: &[u8; ENCODED_LENGTH]) -> PriceLevel {
+        let buf = ReadBuf::with_byte_order(entry, O::default());
+        PriceLevel {
+            id: buf.get_u32_at(0),
+            price: buf.get_f64_at(4),
+            leaves_qty: buf.get_f64_at(12),
+            transact_time: buf.get_i64_at(20),
+        }
     }
+
+    /// Iterator over the entries of a group of 'PriceLevel' composites, yielding copies.
+    #[derive(Clone, Debug, Default)]
+    pub struct PriceLevels<'a, O: ByteOrder = SchemaByteOrder> {
+        data: &'a [u8],
+        offset: usize,
+        block_length: usize,
+        remaining: usize,
+        byte_order: core::marker::PhantomData<O>,
+    }
+
+    impl<'a, O: ByteOrder> PriceLevels<'a, O> {
+        /// `data` must hold `count` entries of `block_length` bytes starting at `offset`.
+        #[inline]
+        pub(crate) fn new(data: &'a [u8], offset: usize, block_length: usize, count: usize) -> Self {
+            Self { data, offset, block_length, remaining: count, byte_order: core::marker::PhantomData }
+        }
+
+        /// Offsets in the message of the entries not yet yielded, e.g. to update them in place.
+        #[inline]
+        pub fn offsets(&self) -> impl Iterator<Item = usize> {
+            let (offset, block_length) = (self.offset, self.block_length);
+            (0..self.remaining).map(move |index| offset + index * block_length)
+        }
+    }
+
+    impl<'a, O: ByteOrder> Iterator for PriceLevels<'a, O> {
+        type Item = PriceLevel;
+
+        #[inline]
+        fn next(&mut self) -> Option<PriceLevel> {
+            if self.remaining == 0 {
+                return None;
+            }
+            let level = PriceLevelDecoder::default()
+                .wrap(ReadBuf::with_byte_order(self.data, O::default()), self.offset)
+                .to_price_level();
+            self.offset += self.block_length;
+            self.remaining -= 1;
+            Some(level)
+        }
+
+        #[inline]
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            (self.remaining, Some(self.remaining))
+        }
+    }
+
+    impl<'a, O: ByteOrder> ExactSizeIterator for PriceLevels<'a, O> {}
 } // end decoder mod 
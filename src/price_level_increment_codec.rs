@@ -5,6 +5,13 @@ pub use decoder::*;
 
 pub const ENCODED_LENGTH: usize = 29;
 
+/// Copy of a decoded 'PriceLevelIncrement' composite.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PriceLevelIncrement {
+    pub price_level: PriceLevel,
+    pub update_action: UpdateAction,
+}
+
 pub mod encoder {
     use super::*;
 
@@ -15,8 +22,10 @@ pub mod encoder {
     }
 
     impl<'a, P> Writer<'a> for PriceLevelIncrementEncoder<P> where P: Writer<'a> + Default {
+        type ByteOrder = P::ByteOrder;
+
         #[inline]
-        fn get_buf_mut(&mut self) -> &mut WriteBuf<'a> {
+        fn get_buf_mut(&mut self) -> &mut WriteBuf<'a, P::ByteOrder> {
             if let Some(parent) = self.parent.as_mut() {
                 parent.get_buf_mut()
             } else {
@@ -51,6 +60,15 @@ pub mod encoder {
             self.get_buf_mut().put_u8_at(offset, value as u8)
         }
 
+        #[inline]
+        pub fn copy_from(&mut self, value: &PriceLevelIncrement) {
+            self.update_action(value.update_action);
+            let offset = self.offset;
+            let mut price_level = PriceLevelEncoder::default().wrap(core::mem::take(self.get_buf_mut()), offset);
+            price_level.copy_from(&value.price_level);
+            *self.get_buf_mut() = price_level.parent().expect("parent set");
+        }
+
     }
 } // end encoder mod 
 
@@ -64,8 +82,10 @@ pub mod decoder {
     }
 
     impl<'a, P> Reader<'a> for PriceLevelIncrementDecoder<P> where P: Reader<'a> + Default {
+        type ByteOrder = P::ByteOrder;
+
         #[inline]
-        fn get_buf(&self) -> &ReadBuf<'a> {
+        fn get_buf(&self) -> &ReadBuf<'a, P::ByteOrder> {
             self.parent.as_ref().expect("parent missing").get_buf()
         }
     }
@@ -95,5 +115,75 @@ pub mod decoder {
             self.get_buf().get_u8_at(self.offset + 28).into()
         }
 
+        #[inline]
+        pub fn to_price_level_increment(&self) -> PriceLevelIncrement {
+            PriceLevelIncrement {
+                price_level: PriceLevelDecoder::default()
+                    .wrap(ReadBuf::with_byte_order(self.get_buf().data, P::ByteOrder::default()), self.offset)
+                    .to_price_level(),
+                update_action: self.update_action(),
+            }
+        }
+
+    }
+
+    /// Decodes one entry of a group of 'PriceLevelIncrement' composites. Offsets are constant
+    /// within the fixed-size entry, so the per-field bounds checks compile away.
+    #[inline]
+    pub(crate) fn read_price_level_increment<O: ByteOrder>(entry: &[u8; ENCODED_LENGTH]) -> PriceLevelIncrement {
+        let price_level = entry[..price_level_codec::ENCODED_LENGTH].try_into().expect("composite within entry");
+        PriceLevelIncrement {
+            price_level: price_level_codec::read_price_level::<O>(price_level),
+            update_action: ReadBuf::with_byte_order(entry, O::default()).get_u8_at(28).into(),
+        }
+    }
+
+    /// Iterator over the entries of a group of 'PriceLevelIncrement' composites, yielding copies.
+    #[derive(Clone, Debug, Default)]
+    pub struct PriceLevelIncrements<'a, O: ByteOrder = SchemaByteOrder> {
+        data: &'a [u8],
+        offset: usize,
+        block_length: usize,
+        remaining: usize,
+        byte_order: core::marker::PhantomData<O>,
+    }
+
+    impl<'a, O: ByteOrder> PriceLevelIncrements<'a, O> {
+        /// `data` must hold `count` entries of `block_length` bytes starting at `offset`.
+        #[inline]
+        pub(crate) fn new(data: &'a [u8], offset: usize, block_length: usize, count: usize) -> Self {
+            Self { data, offset, block_length, remaining: count, byte_order: core::marker::PhantomData }
+        }
+
+        /// Offsets in the message of the entries not yet yielded, e.g. to update them in place.
+        #[inline]
+        pub fn offsets(&self) -> impl Iterator<Item = usize> {
+            let (offset, block_length) = (self.offset, self.block_length);
+            (0..self.remaining).map(move |index| offset + index * block_length)
+        }
     }
+
+    impl<'a, O: ByteOrder> Iterator for PriceLevelIncrements<'a, O> {
+        type Item = PriceLevelIncrement;
+
+        #[inline]
+        fn next(&mut self) -> Option<PriceLevelIncrement> {
+            if self.remaining == 0 {
+                return None;
+            }
+            let increment = PriceLevelIncrementDecoder::default()
+                .wrap(ReadBuf::with_byte_order(self.data, O::default()), self.offset)
+                .to_price_level_increment();
+            self.offset += self.block_length;
+            self.remaining -= 1;
+            Some(increment)
+        }
+
+        #[inline]
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            (self.remaining, Some(self.remaining))
+        }
+    }
+
+    impl<'a, O: ByteOrder> ExactSizeIterator for PriceLevelIncrements<'a, O> {}
 } // end decoder mod 
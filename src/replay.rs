@@ -0,0 +1,241 @@
+//! Deterministic replay of recorded market data.
+//!
+//! [`MarketDataReplayer`] reads `PriceSnapshot`, `PriceIncrement` and `LastMarketTrade` records
+//! from a [`journal`](crate::journal) and re-emits their bytes paced by a [`ReplayClock`].
+//! Journal timestamps and clock readings are both nanoseconds. Other messages are skipped.
+use crate::*;
+use crate::journal::{JournalPosition, JournalReader, JournalRecord};
+
+use std::io;
+use std::time::{Duration, Instant};
+
+/// Time source driving the replay pacing.
+pub trait ReplayClock {
+    /// Current time in nanoseconds.
+    fn now(&self) -> u64;
+
+    /// Blocks (or jumps, for virtual clocks) until `now() >= time`.
+    fn sleep_until(&mut self, time: u64);
+}
+
+/// Monotonic wall clock; `now()` counts nanoseconds since the clock was created.
+#[derive(Clone, Copy, Debug)]
+pub struct SystemClock {
+    origin: Instant,
+}
+
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self { origin: Instant::now() }
+    }
+}
+
+impl ReplayClock for SystemClock {
+    #[inline]
+    fn now(&self) -> u64 {
+        self.origin.elapsed().as_nanos() as u64
+    }
+
+    fn sleep_until(&mut self, time: u64) {
+        let now = self.now();
+        if time > now {
+            std::thread::sleep(Duration::from_nanos(time - now));
+        }
+    }
+}
+
+/// Clock that only moves when told to, so replays take no wall time and are reproducible.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct VirtualClock {
+    now: u64,
+}
+
+impl VirtualClock {
+    pub fn new(now: u64) -> Self {
+        Self { now }
+    }
+
+    #[inline]
+    pub fn advance(&mut self, nanos: u64) {
+        self.now += nanos;
+    }
+}
+
+impl ReplayClock for VirtualClock {
+    #[inline]
+    fn now(&self) -> u64 {
+        self.now
+    }
+
+    #[inline]
+    fn sleep_until(&mut self, time: u64) {
+        self.now = self.now.max(time);
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ReplaySpeed {
+    /// Keep the recorded gaps between messages.
+    Original,
+    /// Divide the recorded gaps by the given factor, which must be finite and positive.
+    Multiplier(f64),
+    /// Emit messages back to back without waiting on the clock.
+    AsFastAsPossible,
+}
+
+impl Default for ReplaySpeed {
+    #[inline]
+    fn default() -> Self { ReplaySpeed::Original }
+}
+
+/// A message handed out by the replayer.
+#[derive(Clone, Copy, Debug)]
+pub struct ReplayEvent<'a> {
+    /// Clock time at which the message was emitted.
+    pub replay_time: u64,
+    /// Receive timestamp stored in the journal.
+    pub recorded_time: u64,
+    pub bytes: &'a [u8],
+}
+
+impl<'a> ReplayEvent<'a> {
+    #[inline]
+    pub fn message(&self) -> SbeResult<AnyMessage<'a>> {
+        AnyMessage::decode(self.bytes, 0)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ReplayStats {
+    pub emitted: u64,
+    pub skipped: u64,
+}
+
+#[derive(Debug)]
+pub struct MarketDataReplayer<C> {
+    clock: C,
+    speed: ReplaySpeed,
+    rewrite_transact_time: bool,
+    scratch: Vec<u8>,
+    level_offsets: Vec<usize>,
+}
+
+impl<C: ReplayClock> MarketDataReplayer<C> {
+    /// Fails with `InvalidInput` for a [`ReplaySpeed::Multiplier`] that is not finite and positive.
+    pub fn new(clock: C, speed: ReplaySpeed) -> io::Result<Self> {
+        if let ReplaySpeed::Multiplier(factor) = speed {
+            if !(factor.is_finite() && factor > 0.0) {
+                let message = "replay multiplier must be finite and positive";
+                return Err(io::Error::new(io::ErrorKind::InvalidInput, message));
+            }
+        }
+        Ok(Self {
+            clock,
+            speed,
+            rewrite_transact_time: false,
+            scratch: Vec::new(),
+            level_offsets: Vec::new(),
+        })
+    }
+
+    /// When set, `transactTime` of trades and of every price level is replaced by the replay time.
+    pub fn rewrite_transact_time(mut self, rewrite: bool) -> Self {
+        self.rewrite_transact_time = rewrite;
+        self
+    }
+
+    #[inline]
+    pub fn clock(&self) -> &C {
+        &self.clock
+    }
+
+    /// Replays the whole journal.
+    #[inline]
+    pub fn replay<F>(&mut self, reader: &JournalReader, on_event: F) -> io::Result<ReplayStats>
+    where
+        F: FnMut(&ReplayEvent<'_>),
+    {
+        self.replay_from(reader, JournalPosition::default(), on_event)
+    }
+
+    /// Replays the journal from `position`, pacing relative to the first market data record.
+    pub fn replay_from<F>(
+        &mut self,
+        reader: &JournalReader,
+        position: JournalPosition,
+        mut on_event: F,
+    ) -> io::Result<ReplayStats>
+    where
+        F: FnMut(&ReplayEvent<'_>),
+    {
+        let mut stats = ReplayStats::default();
+        let mut origin: Option<(u64, u64)> = None;
+        reader.replay_from(position, |record| {
+            if !self.collect_level_offsets(record) {
+                stats.skipped += 1;
+                return;
+            }
+            let (recorded_origin, replay_origin) = *origin.get_or_insert((record.timestamp, self.clock.now()));
+            let replay_time = match self.speed {
+                ReplaySpeed::AsFastAsPossible => self.clock.now(),
+                speed => {
+                    let elapsed = record.timestamp.saturating_sub(recorded_origin);
+                    let scaled = match speed {
+                        ReplaySpeed::Multiplier(factor) => (elapsed as f64 / factor) as u64,
+                        _ => elapsed,
+                    };
+                    let target = replay_origin.saturating_add(scaled);
+                    self.clock.sleep_until(target);
+                    target
+                }
+            };
+
+            let bytes = if self.rewrite_transact_time {
+                self.rewrite(record.bytes(), replay_time)
+            } else {
+                record.bytes()
+            };
+            on_event(&ReplayEvent { replay_time, recorded_time: record.timestamp, bytes });
+            stats.emitted += 1;
+        })?;
+        Ok(stats)
+    }
+
+    /// Returns false for records that are not replayable market data. For price messages the
+    /// offsets of all price levels are left in `level_offsets`.
+    fn collect_level_offsets(&mut self, record: &JournalRecord<'_>) -> bool {
+        self.level_offsets.clear();
+        let offsets = &mut self.level_offsets;
+        let collected = match record.message() {
+            Ok(AnyMessage::LastMarketTrade(_)) => Ok(()),
+            Ok(AnyMessage::PriceSnapshot(snapshot)) => {
+                snapshot.groups().map(|(bids, offers)| offsets.extend(bids.offsets().chain(offers.offsets())))
+            }
+            // `PriceLevelIncrement` starts with its `PriceLevel`, so entry offsets are level offsets.
+            Ok(AnyMessage::PriceIncrement(increment)) => {
+                increment.groups().map(|(bids, offers)| offsets.extend(bids.offsets().chain(offers.offsets())))
+            }
+            _ => return false,
+        };
+        collected.is_ok()
+    }
+
+    fn rewrite(&mut self, bytes: &[u8], replay_time: u64) -> &[u8] {
+        self.scratch.clear();
+        self.scratch.extend_from_slice(bytes);
+        let body_offset = message_header_codec::ENCODED_LENGTH;
+        let template_id = MessageHeaderDecoder::default().wrap(ReadBuf::new(bytes), 0).template_id();
+        let mut buf = WriteBuf::new(&mut self.scratch);
+        if template_id == last_market_trade_codec::SBE_TEMPLATE_ID {
+            let mut trade = LastMarketTradeEncoder::default().wrap(buf, body_offset);
+            trade.transact_time(replay_time);
+        } else {
+            for &offset in &self.level_offsets {
+                let mut level = PriceLevelEncoder::default().wrap(buf, offset);
+                level.transact_time(replay_time as i64);
+                buf = level.parent().expect("parent set");
+            }
+        }
+        &self.scratch
+    }
+}
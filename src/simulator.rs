@@ -0,0 +1,661 @@
+//! In-process venue simulator.
+//!
+//! [`VenueSimulator`] consumes encoded `PriceSnapshot`, `NewOrderSingle` and `OrderCancelRequest`
+//! messages and answers orders and cancels with encoded `ExecutionReport` and `OrderCancelReject`
+//! messages. Each `PriceSnapshot` replaces the simulated book of its instrument; orders take
+//! liquidity from that book, which is consumed by fills until the next snapshot arrives.
+//! Resting orders are matched again whenever a new snapshot arrives.
+//!
+//! `OrderStatusRequest` and `OrderMassStatusRequest` are answered with `ExecutionReport`s of
+//! `ExecType::ORDER_STATUS` echoing the request id; the last report of each answer has
+//! `lastRptRequested` set. An unknown order, or a mass status request matching no working order,
+//! is answered with a single report of `OrderStatus::REJECTED`.
+//!
+//! An `OrderMassCancelRequest` cancels every working order of the requesting `sourceCompId` that
+//! matches its instrument, side or `targetStrategy`, or all of them. It is answered with an
+//! `OrderMassCancelReport` carrying the number of affected orders, followed by a `CANCELLED`
+//! `ExecutionReport` per order.
+use crate::*;
+
+use std::collections::HashMap;
+
+/// longest 'text' the simulator sends
+const MAX_TEXT_LENGTH: usize = 64;
+const RESPONSE_BUFFER_LENGTH: usize = message_header_codec::ENCODED_LENGTH
+    + execution_report_codec::SBE_BLOCK_LENGTH as usize
+    + var_string_encoding_codec::LENGTH_ENCODED_LENGTH
+    + MAX_TEXT_LENGTH;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Level {
+    price: f64,
+    qty: f64,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Book {
+    /// best (highest) price first
+    bids: Vec<Level>,
+    /// best (lowest) price first
+    offers: Vec<Level>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct Order {
+    cl_ord_id: u64,
+    cl_ord_link_id: u64,
+    instrument_id: u32,
+    target_comp_id: u32,
+    source_comp_id: u32,
+    side: OrderSide,
+    order_type: OrderType,
+    price: f64,
+    order_qty: f64,
+    time_in_force: TimeInForce,
+    effective_time: u64,
+    expire_time: u64,
+    target_strategy: Strategy,
+    source_strategy: Strategy,
+    order_id: u64,
+    status: OrderStatus,
+    leaves_qty: f64,
+    cum_qty: f64,
+    notional: f64,
+}
+
+impl Order {
+    #[inline]
+    fn is_working(&self) -> bool {
+        matches!(self.status, OrderStatus::NEW | OrderStatus::PARTIALLY_FILLED)
+    }
+
+    #[inline]
+    fn crosses(&self, level_price: f64) -> bool {
+        match (self.order_type, self.side) {
+            (OrderType::MARKET, _) => true,
+            (_, OrderSide::BUY) => level_price <= self.price,
+            (_, _) => level_price >= self.price,
+        }
+    }
+
+    #[inline]
+    fn avg_price(&self) -> f64 {
+        if self.cum_qty > 0.0 { self.notional / self.cum_qty } else { f64::NAN }
+    }
+}
+
+/// Fields of an `ExecutionReport` that vary per report.
+#[derive(Clone, Copy, Debug)]
+struct Execution {
+    exec_type: ExecType,
+    orig_cl_ord_id: u64,
+    reject_reason: OrderRejectReason,
+    text: &'static str,
+    last_qty: f64,
+    last_price: f64,
+    ord_status_req_id: u64,
+    mass_status_req_id: u64,
+    last_rpt_requested: BooleanType,
+}
+
+impl Execution {
+    #[inline]
+    fn new(exec_type: ExecType) -> Self {
+        Self {
+            exec_type,
+            orig_cl_ord_id: u64::MAX,
+            reject_reason: OrderRejectReason::NullVal,
+            text: "",
+            last_qty: f64::NAN,
+            last_price: f64::NAN,
+            ord_status_req_id: u64::MAX,
+            mass_status_req_id: u64::MAX,
+            last_rpt_requested: BooleanType::NullVal,
+        }
+    }
+}
+
+/// Matching simulator answering orders against books seeded from `PriceSnapshot`s.
+#[derive(Debug)]
+pub struct VenueSimulator {
+    books: HashMap<u32, Book>,
+    orders: HashMap<u64, Order>,
+    next_order_id: u64,
+    next_exec_id: u64,
+    buffer: [u8; RESPONSE_BUFFER_LENGTH],
+}
+
+impl Default for VenueSimulator {
+    fn default() -> Self {
+        Self {
+            books: HashMap::new(),
+            orders: HashMap::new(),
+            next_order_id: 1,
+            next_exec_id: 1,
+            buffer: [0; RESPONSE_BUFFER_LENGTH],
+        }
+    }
+}
+
+impl VenueSimulator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of orders that are still resting on the simulated venue.
+    pub fn working_orders(&self) -> usize {
+        self.orders.values().filter(|order| order.is_working()).count()
+    }
+
+    /// Processes one framed message received at `now`, passing every framed response to
+    /// `on_response`. Messages the simulator does not act on are ignored; a message that fails to
+    /// decode is answered with a `BusinessMessageReject` before its error is returned.
+    pub fn handle<F>(&mut self, now: u64, message: &[u8], mut on_response: F) -> SbeResult<()>
+    where
+        F: FnMut(&[u8]),
+    {
+        self.expire(now, &mut on_response);
+        let result = self.dispatch(now, message, &mut on_response);
+        if let Err(error) = &result {
+            self.reject(now, message, error, &mut on_response);
+        }
+        result
+    }
+
+    fn dispatch<F>(&mut self, now: u64, message: &[u8], mut on_response: F) -> SbeResult<()>
+    where
+        F: FnMut(&[u8]),
+    {
+        match AnyMessage::decode(message, 0)? {
+            AnyMessage::PriceSnapshot(snapshot) => self.on_price_snapshot(now, snapshot, &mut on_response),
+            AnyMessage::NewOrderSingle(order) => {
+                self.on_new_order_single(now, &order, &mut on_response);
+                Ok(())
+            }
+            AnyMessage::OrderCancelRequest(cancel) => {
+                self.on_order_cancel_request(now, &cancel, &mut on_response);
+                Ok(())
+            }
+            AnyMessage::OrderStatusRequest(request) => {
+                self.on_order_status_request(now, &request, &mut on_response);
+                Ok(())
+            }
+            AnyMessage::OrderMassStatusRequest(request) => {
+                self.on_order_mass_status_request(now, &request, &mut on_response);
+                Ok(())
+            }
+            AnyMessage::OrderMassCancelRequest(request) => {
+                self.on_order_mass_cancel_request(now, &request, &mut on_response);
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Expires working GTD orders whose `expireTime` is at or before `now`.
+    pub fn expire<F>(&mut self, now: u64, mut on_response: F)
+    where
+        F: FnMut(&[u8]),
+    {
+        let mut expired: Vec<u64> = self.orders.values()
+            .filter(|order| order.is_working() && order.time_in_force == TimeInForce::GTD && order.expire_time <= now)
+            .map(|order| order.cl_ord_id)
+            .collect();
+        expired.sort_unstable();
+        for cl_ord_id in expired {
+            let mut order = self.orders[&cl_ord_id];
+            order.status = OrderStatus::EXPIRED;
+            order.leaves_qty = 0.0;
+            self.orders.insert(cl_ord_id, order);
+            self.report(now, &order, Execution::new(ExecType::EXPIRED), &mut on_response);
+        }
+    }
+
+    fn on_price_snapshot<F>(&mut self, now: u64, mut snapshot: PriceSnapshotDecoder<'_>, on_response: &mut F) -> SbeResult<()>
+    where
+        F: FnMut(&[u8]),
+    {
+        let instrument_id = snapshot.instrument_id();
+        let mut book = Book::default();
+        for level in snapshot.bids()? {
+            push_level(&mut book.bids, level.price, level.leaves_qty);
+        }
+        for level in snapshot.offers()? {
+            push_level(&mut book.offers, level.price, level.leaves_qty);
+        }
+        book.bids.sort_by(|a, b| b.price.total_cmp(&a.price));
+        book.offers.sort_by(|a, b| a.price.total_cmp(&b.price));
+        self.books.insert(instrument_id, book);
+
+        let mut resting: Vec<(u64, u64)> = self.orders.values()
+            .filter(|order| order.is_working() && order.instrument_id == instrument_id)
+            .map(|order| (order.order_id, order.cl_ord_id))
+            .collect();
+        resting.sort_unstable();
+        for (_, cl_ord_id) in resting {
+            let mut order = self.orders[&cl_ord_id];
+            self.execute(now, &mut order, on_response);
+            self.orders.insert(cl_ord_id, order);
+        }
+        Ok(())
+    }
+
+    fn on_new_order_single<F>(&mut self, now: u64, decoder: &NewOrderSingleDecoder<'_>, on_response: &mut F)
+    where
+        F: FnMut(&[u8]),
+    {
+        let mut order = Order {
+            cl_ord_id: decoder.cl_ord_id(),
+            cl_ord_link_id: decoder.cl_ord_link_id(),
+            instrument_id: decoder.instrument_id(),
+            target_comp_id: decoder.target_comp_id(),
+            source_comp_id: decoder.source_comp_id(),
+            side: decoder.side(),
+            order_type: decoder.order_type(),
+            price: decoder.price(),
+            order_qty: decoder.order_qty(),
+            time_in_force: decoder.time_in_force(),
+            effective_time: decoder.effective_time(),
+            expire_time: decoder.expire_time(),
+            target_strategy: decoder.target_strategy(),
+            source_strategy: decoder.source_strategy(),
+            order_id: self.next_order_id,
+            status: OrderStatus::NEW,
+            leaves_qty: decoder.order_qty(),
+            cum_qty: 0.0,
+            notional: 0.0,
+        };
+        self.next_order_id += 1;
+
+        if let Some((reason, text)) = self.validate(now, &order) {
+            order.status = OrderStatus::REJECTED;
+            order.leaves_qty = 0.0;
+            let mut execution = Execution::new(ExecType::REJECTED);
+            execution.reject_reason = reason;
+            execution.text = text;
+            self.report(now, &order, execution, on_response);
+            return;
+        }
+
+        self.report(now, &order, Execution::new(ExecType::NEW), on_response);
+        self.execute(now, &mut order, on_response);
+        if order.is_working() && (order.order_type == OrderType::MARKET || order.time_in_force == TimeInForce::IOC) {
+            order.status = OrderStatus::CANCELLED;
+            order.leaves_qty = 0.0;
+            self.report(now, &order, Execution::new(ExecType::CANCELLED), on_response);
+        }
+        self.orders.insert(order.cl_ord_id, order);
+    }
+
+    fn validate(&self, now: u64, order: &Order) -> Option<(OrderRejectReason, &'static str)> {
+        if !self.books.contains_key(&order.instrument_id) {
+            return Some((OrderRejectReason::UNKNOWN_SYMBOL, "no price snapshot for instrument"));
+        }
+        if self.orders.contains_key(&order.cl_ord_id) {
+            return Some((OrderRejectReason::DUPLICATE_ORDER, "duplicate clOrdId"));
+        }
+        if order.order_type == OrderType::LIMIT && (order.price.is_nan() || order.price <= 0.0) {
+            return Some((OrderRejectReason::BROKER_OPTION, "invalid price"));
+        }
+        if order.order_qty.is_nan() || order.order_qty <= 0.0 {
+            return Some((OrderRejectReason::BROKER_OPTION, "invalid order quantity"));
+        }
+        if order.side == OrderSide::NullVal
+            || order.order_type == OrderType::NullVal
+            || order.time_in_force == TimeInForce::NullVal
+        {
+            return Some((OrderRejectReason::BROKER_OPTION, "missing side, order type or time in force"));
+        }
+        if order.time_in_force == TimeInForce::GTD && order.expire_time <= now {
+            return Some((OrderRejectReason::STALE_ORDER, "expire time has passed"));
+        }
+        None
+    }
+
+    /// Fills `order` against the opposite side of its book. An FOK order that cannot be filled
+    /// completely is cancelled without trading.
+    fn execute<F>(&mut self, now: u64, order: &mut Order, on_response: &mut F)
+    where
+        F: FnMut(&[u8]),
+    {
+        let book = match self.books.get_mut(&order.instrument_id) {
+            Some(book) => book,
+            None => return,
+        };
+        let levels = if order.side == OrderSide::BUY { &mut book.offers } else { &mut book.bids };
+
+        if order.time_in_force == TimeInForce::FOK {
+            let available: f64 = levels.iter()
+                .take_while(|level| order.crosses(level.price))
+                .map(|level| level.qty)
+                .sum();
+            if available < order.leaves_qty {
+                order.status = OrderStatus::CANCELLED;
+                order.leaves_qty = 0.0;
+                self.report(now, order, Execution::new(ExecType::CANCELLED), on_response);
+                return;
+            }
+        }
+
+        let mut fills = Vec::new();
+        let mut leaves_qty = order.leaves_qty;
+        for level in levels.iter_mut() {
+            if leaves_qty <= 0.0 || !order.crosses(level.price) {
+                break;
+            }
+            let qty = level.qty.min(leaves_qty);
+            level.qty -= qty;
+            leaves_qty = if qty == leaves_qty { 0.0 } else { leaves_qty - qty };
+            fills.push((Level { price: level.price, qty }, leaves_qty));
+        }
+        levels.retain(|level| level.qty > 0.0);
+
+        for (fill, leaves_qty) in fills {
+            order.leaves_qty = leaves_qty;
+            order.cum_qty += fill.qty;
+            order.notional += fill.qty * fill.price;
+            let exec_type = if leaves_qty > 0.0 {
+                order.status = OrderStatus::PARTIALLY_FILLED;
+                ExecType::PARTIAL_FILL
+            } else {
+                order.status = OrderStatus::FILLED;
+                ExecType::FILL
+            };
+            let mut execution = Execution::new(exec_type);
+            execution.last_qty = fill.qty;
+            execution.last_price = fill.price;
+            self.report(now, order, execution, on_response);
+        }
+    }
+
+    /// Orders of other sessions, by `sourceCompId`, are rejected as unknown.
+    fn on_order_cancel_request<F>(&mut self, now: u64, decoder: &OrderCancelRequestDecoder<'_>, on_response: &mut F)
+    where
+        F: FnMut(&[u8]),
+    {
+        let orig_cl_ord_id = decoder.orig_cl_ord_id();
+        let source_comp_id = decoder.source_comp_id();
+        let owned = |order: &&mut Order| order.source_comp_id == source_comp_id;
+        let (reason, text) = match self.orders.get_mut(&orig_cl_ord_id).filter(owned) {
+            Some(order) if order.is_working() => {
+                order.status = OrderStatus::CANCELLED;
+                order.leaves_qty = 0.0;
+                let mut order = *order;
+                order.cl_ord_id = decoder.cl_ord_id();
+                let mut execution = Execution::new(ExecType::CANCELLED);
+                execution.orig_cl_ord_id = orig_cl_ord_id;
+                self.report(now, &order, execution, on_response);
+                return;
+            }
+            Some(_) => (CancelRejectReason::TOO_LATE_TO_CANCEL, "order is no longer working"),
+            None => (CancelRejectReason::UNKNOWN_ORDER, "unknown origClOrdId"),
+        };
+
+        let (order_id, order_status) = self.orders.get(&orig_cl_ord_id)
+            .filter(|order| order.source_comp_id == source_comp_id)
+            .map_or((decoder.order_id(), OrderStatus::REJECTED), |order| (order.order_id, order.status));
+        let mut encoder = OrderCancelRejectEncoder::default()
+            .wrap(WriteBuf::new(&mut self.buffer), message_header_codec::ENCODED_LENGTH)
+            .header(0)
+            .parent()
+            .expect("parent set");
+        encoder.orig_cl_ord_id(orig_cl_ord_id);
+        encoder.order_id(order_id);
+        encoder.cl_ord_id(decoder.cl_ord_id());
+        encoder.order_status(order_status);
+        encoder.instrument_id(decoder.instrument_id());
+        encoder.target_comp_id(decoder.source_comp_id());
+        encoder.source_comp_id(decoder.target_comp_id());
+        encoder.target_strategy(decoder.source_strategy());
+        encoder.source_strategy(Strategy::SIMULATOR);
+        encoder.cancel_rej_response_to(CancelRejectResponseTo::ORDER_CANCEL_REQUEST);
+        encoder.cancel_rej_reason(reason);
+        encoder.transact_time(now);
+        encoder.text(text.as_bytes()).expect("text fits the response buffer");
+        let length = message_header_codec::ENCODED_LENGTH + encoder.encoded_length();
+        on_response(&self.buffer[..length]);
+    }
+
+    /// Reports the order with `clOrdId`, or with `orderId` when no order has that `clOrdId`.
+    fn on_order_status_request<F>(&mut self, now: u64, decoder: &OrderStatusRequestDecoder<'_>, on_response: &mut F)
+    where
+        F: FnMut(&[u8]),
+    {
+        let mut execution = Execution::new(ExecType::ORDER_STATUS);
+        execution.ord_status_req_id = decoder.ord_status_req_id();
+        execution.last_rpt_requested = BooleanType::YES;
+        let order_id = decoder.order_id();
+        let order = self.orders.get(&decoder.cl_ord_id()).copied().or_else(|| {
+            self.orders.values().find(|order| order_id != u64::MAX && order.order_id == order_id).copied()
+        });
+        match order {
+            Some(order) => self.report(now, &order, execution, on_response),
+            None => {
+                let order = Order {
+                    cl_ord_id: decoder.cl_ord_id(),
+                    order_id,
+                    instrument_id: decoder.instrument_id(),
+                    target_comp_id: decoder.target_comp_id(),
+                    source_comp_id: decoder.source_comp_id(),
+                    source_strategy: decoder.source_strategy(),
+                    ..unknown_order()
+                };
+                execution.reject_reason = OrderRejectReason::UNKNOWN_ORDER;
+                execution.text = "unknown order";
+                self.report(now, &order, execution, on_response);
+            }
+        }
+    }
+
+    /// Reports every working order of the requested instrument or strategy, or all of them, in
+    /// `orderId` order.
+    fn on_order_mass_status_request<F>(
+        &mut self,
+        now: u64,
+        decoder: &OrderMassStatusRequestDecoder<'_>,
+        on_response: &mut F,
+    ) where
+        F: FnMut(&[u8]),
+    {
+        let (instrument_id, strategy) = (decoder.instrument_id(), decoder.strategy());
+        let request_type = decoder.mass_status_req_type();
+        let mut matching: Vec<Order> = self.orders.values()
+            .filter(|order| order.is_working())
+            .filter(|order| match request_type {
+                MassStatusReqType::INSTRUMENT => order.instrument_id == instrument_id,
+                MassStatusReqType::STRATEGY => order.source_strategy == strategy,
+                MassStatusReqType::ALL_ORDERS => true,
+                MassStatusReqType::NullVal => false,
+            })
+            .copied()
+            .collect();
+        matching.sort_unstable_by_key(|order| order.order_id);
+
+        let mut execution = Execution::new(ExecType::ORDER_STATUS);
+        execution.mass_status_req_id = decoder.mass_status_req_id();
+        if matching.is_empty() {
+            let order = Order {
+                instrument_id,
+                target_comp_id: decoder.target_comp_id(),
+                source_comp_id: decoder.source_comp_id(),
+                source_strategy: decoder.source_strategy(),
+                ..unknown_order()
+            };
+            execution.last_rpt_requested = BooleanType::YES;
+            execution.text = "no matching working orders";
+            self.report(now, &order, execution, on_response);
+            return;
+        }
+        let last = matching.len() - 1;
+        for (index, order) in matching.iter().enumerate() {
+            execution.last_rpt_requested = if index == last { BooleanType::YES } else { BooleanType::NO };
+            self.report(now, order, execution, on_response);
+        }
+    }
+
+    fn on_order_mass_cancel_request<F>(
+        &mut self,
+        now: u64,
+        decoder: &OrderMassCancelRequestDecoder<'_>,
+        on_response: &mut F,
+    ) where
+        F: FnMut(&[u8]),
+    {
+        let request_type = decoder.mass_cancel_request_type();
+        let (instrument_id, side, strategy) = (decoder.instrument_id(), decoder.side(), decoder.strategy());
+        let rejection = match request_type {
+            MassCancelRequestType::NullVal => {
+                Some((MassCancelRejectReason::NOT_SUPPORTED, "unsupported massCancelRequestType"))
+            }
+            MassCancelRequestType::INSTRUMENT if !self.books.contains_key(&instrument_id) => {
+                Some((MassCancelRejectReason::UNKNOWN_INSTRUMENT, "no price snapshot for instrument"))
+            }
+            MassCancelRequestType::SIDE if side == OrderSide::NullVal => {
+                Some((MassCancelRejectReason::INVALID_SIDE, "missing side"))
+            }
+            MassCancelRequestType::STRATEGY if strategy == Strategy::NullVal => {
+                Some((MassCancelRejectReason::INVALID_STRATEGY, "missing strategy"))
+            }
+            _ => None,
+        };
+
+        let source_comp_id = decoder.source_comp_id();
+        let mut cancelled: Vec<(u64, u64)> = match rejection {
+            Some(_) => Vec::new(),
+            None => self.orders.values()
+                .filter(|order| order.is_working() && order.source_comp_id == source_comp_id)
+                .filter(|order| match request_type {
+                    MassCancelRequestType::INSTRUMENT => order.instrument_id == instrument_id,
+                    MassCancelRequestType::SIDE => order.side == side,
+                    MassCancelRequestType::STRATEGY => order.target_strategy == strategy,
+                    _ => true,
+                })
+                .map(|order| (order.order_id, order.cl_ord_id))
+                .collect(),
+        };
+        cancelled.sort_unstable();
+
+        let (response, reject_reason, text) = match rejection {
+            Some((reason, text)) => (MassCancelResponse::REJECTED, reason, text),
+            None => (MassCancelResponse::ACCEPTED, MassCancelRejectReason::NullVal, ""),
+        };
+        let mut encoder = OrderMassCancelReportEncoder::default()
+            .wrap(WriteBuf::new(&mut self.buffer), message_header_codec::ENCODED_LENGTH)
+            .header(0)
+            .parent()
+            .expect("parent set");
+        encoder.cl_ord_id(decoder.cl_ord_id());
+        encoder.mass_cancel_request_type(request_type);
+        encoder.mass_cancel_response(response);
+        encoder.mass_cancel_reject_reason(reject_reason);
+        encoder.total_affected_orders(cancelled.len() as u32);
+        encoder.instrument_id(instrument_id);
+        encoder.side(side);
+        encoder.strategy(strategy);
+        encoder.target_comp_id(source_comp_id);
+        encoder.source_comp_id(decoder.target_comp_id());
+        encoder.target_strategy(decoder.source_strategy());
+        encoder.source_strategy(Strategy::SIMULATOR);
+        encoder.transact_time(now);
+        encoder.text(text.as_bytes()).expect("text fits the response buffer");
+        let length = message_header_codec::ENCODED_LENGTH + encoder.encoded_length();
+        on_response(&self.buffer[..length]);
+
+        for (_, cl_ord_id) in cancelled {
+            let order = self.orders.get_mut(&cl_ord_id).expect("order is tracked");
+            order.status = OrderStatus::CANCELLED;
+            order.leaves_qty = 0.0;
+            let order = *order;
+            self.report(now, &order, Execution::new(ExecType::CANCELLED), on_response);
+        }
+    }
+
+    /// Comp ids are null: a message that failed to decode has no trustworthy sender.
+    fn reject<F>(&mut self, now: u64, message: &[u8], error: &SbeErr, on_response: &mut F)
+    where
+        F: FnMut(&[u8]),
+    {
+        let mut encoder = BusinessMessageRejectEncoder::default()
+            .wrap(WriteBuf::new(&mut self.buffer), message_header_codec::ENCODED_LENGTH)
+            .header(0)
+            .parent()
+            .expect("parent set");
+        encoder.business_reject_ref_id(u64::MAX);
+        encoder.target_comp_id(u32::MAX);
+        encoder.source_comp_id(u32::MAX);
+        encoder.transact_time(now);
+        encoder.reject_for(message, error).expect("text fits the response buffer");
+        let length = message_header_codec::ENCODED_LENGTH + encoder.encoded_length();
+        on_response(&self.buffer[..length]);
+    }
+
+    fn report<F>(&mut self, now: u64, order: &Order, execution: Execution, on_response: &mut F)
+    where
+        F: FnMut(&[u8]),
+    {
+        let exec_id = self.next_exec_id;
+        self.next_exec_id += 1;
+
+        let mut encoder = ExecutionReportEncoder::default()
+            .wrap(WriteBuf::new(&mut self.buffer), message_header_codec::ENCODED_LENGTH)
+            .header(0)
+            .parent()
+            .expect("parent set");
+        encoder.cl_ord_id(order.cl_ord_id);
+        encoder.cl_ord_link_id(order.cl_ord_link_id);
+        encoder.instrument_id(order.instrument_id);
+        encoder.target_comp_id(order.source_comp_id);
+        encoder.source_comp_id(order.target_comp_id);
+        encoder.side(order.side);
+        encoder.order_type(order.order_type);
+        encoder.price(order.price);
+        encoder.order_qty(order.order_qty);
+        encoder.time_in_force(order.time_in_force);
+        encoder.transact_time(now);
+        encoder.effective_time(order.effective_time);
+        encoder.expire_time(order.expire_time);
+        encoder.target_strategy(order.source_strategy);
+        encoder.source_strategy(Strategy::SIMULATOR);
+        encoder.orig_cl_ord_id(execution.orig_cl_ord_id);
+        encoder.exec_id(exec_id);
+        encoder.order_id(order.order_id);
+        encoder.exec_type(execution.exec_type);
+        encoder.order_status(order.status);
+        encoder.order_rej_reason(execution.reject_reason);
+        encoder.last_qty(execution.last_qty);
+        encoder.last_price(execution.last_price);
+        encoder.leaves_qty(order.leaves_qty);
+        encoder.cum_qty(order.cum_qty);
+        encoder.avg_price(order.avg_price());
+        encoder.text(execution.text.as_bytes()).expect("text fits the response buffer");
+        encoder.ord_status_req_id(execution.ord_status_req_id);
+        encoder.mass_status_req_id(execution.mass_status_req_id);
+        encoder.last_rpt_requested(execution.last_rpt_requested);
+        let length = message_header_codec::ENCODED_LENGTH + encoder.encoded_length();
+        on_response(&self.buffer[..length]);
+    }
+}
+
+/// Placeholder answering a status request that matches no order.
+#[inline]
+fn unknown_order() -> Order {
+    Order {
+        cl_ord_id: u64::MAX,
+        cl_ord_link_id: u64::MAX,
+        instrument_id: u32::MAX,
+        price: f64::NAN,
+        order_qty: f64::NAN,
+        effective_time: u64::MAX,
+        expire_time: u64::MAX,
+        order_id: u64::MAX,
+        status: OrderStatus::REJECTED,
+        ..Order::default()
+    }
+}
+
+#[inline]
+fn push_level(levels: &mut Vec<Level>, price: f64, qty: f64) {
+    if !price.is_nan() && qty > 0.0 {
+        levels.push(Level { price, qty });
+    }
+}
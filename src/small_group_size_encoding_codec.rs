@@ -5,6 +5,22 @@ pub use decoder::*;
 
 pub const ENCODED_LENGTH: usize = 2;
 
+/// Reads the group header at `offset` and checks that `count` entries of at least
+/// `entry_length` bytes fit in `buf`. Returns the offset of the first entry, the block length
+/// and the count.
+pub(crate) fn group_extent<O: ByteOrder>(
+    buf: &ReadBuf<'_, O>,
+    offset: usize,
+    entry_length: usize,
+) -> SbeResult<(usize, usize, usize)> {
+    if buf.data.len() < offset + ENCODED_LENGTH {
+        return Err(SbeErr::BufferTooShort);
+    }
+    let block_length = buf.get_u8_at(offset) as usize;
+    let count = buf.get_u8_at(offset + 1) as usize;
+    group_size_encoding_codec::checked_extent(buf, offset + ENCODED_LENGTH, block_length, count, entry_length)
+}
+
 pub mod encoder {
     use super::*;
 
@@ -15,8 +31,10 @@ pub mod encoder {
     }
 
     impl<'a, P> Writer<'a> for SmallGroupSizeEncodingEncoder<P> where P: Writer<'a> + Default {
+        type ByteOrder = P::ByteOrder;
+
         #[inline]
-        fn get_buf_mut(&mut self) -> &mut WriteBuf<'a> {
+        fn get_buf_mut(&mut self) -> &mut WriteBuf<'a, P::ByteOrder> {
             if let Some(parent) = self.parent.as_mut() {
                 parent.get_buf_mut()
             } else {
@@ -78,8 +96,10 @@ pub mod decoder {
     }
 
     impl<'a, P> Reader<'a> for SmallGroupSizeEncodingDecoder<P> where P: Reader<'a> + Default {
+        type ByteOrder = P::ByteOrder;
+
         #[inline]
-        fn get_buf(&self) -> &ReadBuf<'a> {
+        fn get_buf(&self) -> &ReadBuf<'a, P::ByteOrder> {
             self.parent.as_ref().expect("parent missing").get_buf()
         }
     }
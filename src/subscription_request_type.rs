@@ -0,0 +1,23 @@
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(u8)]
+pub enum SubscriptionRequestType {
+    SNAPSHOT = 48_u8, 
+    SNAPSHOT_AND_UPDATES = 49_u8, 
+    UNSUBSCRIBE = 50_u8, 
+    NullVal = 0_u8, 
+}
+impl Default for SubscriptionRequestType {
+    #[inline]
+    fn default() -> Self { SubscriptionRequestType::NullVal }
+}
+impl From<u8> for SubscriptionRequestType {
+    #[inline]
+    fn from(v: u8) -> Self {
+        match v {
+            48_u8 => Self::SNAPSHOT, 
+            49_u8 => Self::SNAPSHOT_AND_UPDATES, 
+            50_u8 => Self::UNSUBSCRIBE, 
+            _ => Self::NullVal,
+        }
+    }
+}
@@ -0,0 +1,135 @@
+//! `tokio_util::codec` framing of SBE messages, so `Framed<TcpStream, SbeCodec>` carries them.
+//!
+//! Messages are sent back to back with no extra length prefix: [`SbeCodec`] finds where each one
+//! ends from its message header, group sizes and var data lengths, and splits it off the read
+//! buffer as an [`SbeFrame`] sharing the received bytes. Messages are written by [`Encode`]
+//! closures running the [`MessageWriter`] methods into a scratch buffer owned by the codec, and
+//! only the encoded bytes are copied to the write buffer. Malformed or oversized messages are
+//! `InvalidData` I/O errors, wrapping the [`SbeErr`] if there is one.
+use crate::*;
+
+use bytes::{Bytes, BytesMut};
+use std::io;
+use tokio_util::codec;
+
+/// Default for [`SbeCodec::max_frame_length`].
+pub const DEFAULT_MAX_FRAME_LENGTH: usize = 64 * 1024;
+
+/// Frames SBE messages read from and written to a byte stream.
+#[derive(Clone, Debug)]
+pub struct SbeCodec {
+    max_frame_length: usize,
+    scratch: Vec<u8>,
+}
+
+impl Default for SbeCodec {
+    fn default() -> Self {
+        Self { max_frame_length: DEFAULT_MAX_FRAME_LENGTH, scratch: Vec::new() }
+    }
+}
+
+impl SbeCodec {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Largest message accepted or written, header included. Decoding fails once this many bytes
+    /// are buffered without completing a message, and [`Encode`] closures write into a scratch
+    /// buffer of this length, zeroed once when first needed.
+    pub fn max_frame_length(mut self, max_frame_length: usize) -> Self {
+        self.max_frame_length = max_frame_length;
+        self
+    }
+}
+
+/// One framed message, header included, backed by the bytes it was read from.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SbeFrame(Bytes);
+
+impl SbeFrame {
+    /// Frames already encoded message bytes, e.g. from a [`MessageWriter`], for sending.
+    pub fn new(bytes: Bytes) -> Self {
+        Self(bytes)
+    }
+
+    #[inline]
+    pub fn message(&self) -> SbeResult<AnyMessage<'_>> {
+        AnyMessage::decode(&self.0, 0)
+    }
+
+    #[inline]
+    pub fn bytes(&self) -> &Bytes {
+        &self.0
+    }
+
+    #[inline]
+    pub fn into_bytes(self) -> Bytes {
+        self.0
+    }
+}
+
+/// A message to send, written by running the closure on a [`MessageWriter`] over the codec's scratch buffer.
+pub struct Encode<F>(F);
+
+impl<F> Encode<F>
+where
+    F: for<'w> FnOnce(&'w mut MessageWriter<&'w mut [u8]>) -> SbeResult<&'w [u8]>,
+{
+    /// `encode` calls one message method of the writer and returns its result, e.g.
+    /// `Encode::new(|writer| writer.new_order_single(|order| { ... }))`.
+    pub fn new(encode: F) -> Self {
+        Self(encode)
+    }
+}
+
+fn invalid_data(err: SbeErr) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err)
+}
+
+fn frame_too_long() -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, "frame exceeds max_frame_length")
+}
+
+impl codec::Decoder for SbeCodec {
+    type Item = SbeFrame;
+    type Error = io::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> io::Result<Option<SbeFrame>> {
+        match AnyMessage::decode(src, 0).and_then(AnyMessage::end_offset) {
+            Ok(length) if length > self.max_frame_length => Err(frame_too_long()),
+            Ok(length) => Ok(Some(SbeFrame(src.split_to(length).freeze()))),
+            Err(SbeErr::BufferTooShort) if src.len() < self.max_frame_length => Ok(None),
+            Err(SbeErr::BufferTooShort) => Err(frame_too_long()),
+            Err(err) => Err(invalid_data(err)),
+        }
+    }
+}
+
+impl<F> codec::Encoder<Encode<F>> for SbeCodec
+where
+    F: for<'w> FnOnce(&'w mut MessageWriter<&'w mut [u8]>) -> SbeResult<&'w [u8]>,
+{
+    type Error = io::Error;
+
+    fn encode(&mut self, item: Encode<F>, dst: &mut BytesMut) -> io::Result<()> {
+        if self.scratch.len() != self.max_frame_length {
+            self.scratch = vec![0; self.max_frame_length];
+        }
+        let mut writer = MessageWriter::new(&mut self.scratch[..]);
+        let message = (item.0)(&mut writer).map_err(invalid_data)?;
+        dst.extend_from_slice(message);
+        Ok(())
+    }
+}
+
+impl codec::Encoder<SbeFrame> for SbeCodec {
+    type Error = io::Error;
+
+    fn encode(&mut self, item: SbeFrame, dst: &mut BytesMut) -> io::Result<()> {
+        if item.0.len() > self.max_frame_length {
+            return Err(frame_too_long());
+        }
+        dst.extend_from_slice(&item.0);
+        Ok(())
+    }
+}
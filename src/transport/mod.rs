@@ -0,0 +1,4 @@
+//! Transports carrying framed messages between processes.
+#[cfg(feature = "shm")]
+pub mod shm;
+pub mod udp;
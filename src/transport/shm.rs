@@ -0,0 +1,499 @@
+//! Ring buffer of framed messages in a memory-mapped file shared between processes, e.g. under
+//! `/dev/shm`.
+//!
+//! A [`RingPublisher`] claims room for one message, encodes it in place with the
+//! [`MessageWriter`] methods and commits it. Claims are refused with back-pressure until the
+//! consumer has freed enough space. Any number of publishers may share a ring: each reserves its
+//! room by moving the producer position with a compare-and-swap, so no lock is held while a
+//! message is encoded. The one consuming [`RingSubscriber`] decodes messages in place and frees
+//! their space once polled. Tailing subscribers follow the ring without consuming it; as they do
+//! not hold back publishers, they copy each message out and drop the copy if the consumer read
+//! past the message meanwhile, skipping ahead to it.
+//!
+//! The file starts with a header holding the magic, the capacity of the data region and the
+//! producer, consumer and read positions, which count bytes ever reserved, freed and read by the
+//! consumer. Each record is a header holding its end position, stored last to commit the record,
+//! and the message length, then the message, padded to 16 bytes. Records never wrap: the end of
+//! the data region is skipped with a padding record instead, and so is the room of a claim
+//! dropped without committing. The consumer zeroes records before freeing them, so a record
+//! header reads zero until it is claimed. A claim marks its end position pending at once and
+//! commits it with a compare-and-swap. Header words are in native byte order.
+//!
+//! Records are read and zeroed word by word with atomic accesses, as a tailing subscriber may
+//! copy a record while the consumer zeroes it and a publisher reuses its room; such a copy is
+//! discarded when the read position shows the record was freed meanwhile.
+//!
+//! A publisher that dies between claiming and committing blocks the consumer at its claim, and
+//! with it every later message. [`RingSubscriber::abort_stalled_claim`] turns a claim pending for
+//! longer than a timeout into padding, after which the consumer moves on; a publisher that was
+//! only slow then fails to commit. A publisher killed in the few instructions between reserving its
+//! room and marking it pending leaves no end position to skip to, and still blocks the ring.
+//!
+//! This is the one module of the crate using `unsafe`, to access the mapping.
+#![allow(unsafe_code)]
+
+use crate::*;
+
+use memmap2::MmapRaw;
+use std::fs::OpenOptions;
+use std::io;
+use std::path::Path;
+use std::slice;
+use std::sync::atomic::{fence, AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+pub const RING_HEADER_LENGTH: usize = 64;
+pub const RECORD_HEADER_LENGTH: usize = 16;
+const RECORD_ALIGNMENT: usize = 16;
+const MAGIC: u64 = u64::from_le_bytes(*b"SBERING2");
+const CAPACITY_OFFSET: usize = 8;
+const PRODUCER_POSITION_OFFSET: usize = 16;
+const CONSUMER_POSITION_OFFSET: usize = 24;
+const READ_POSITION_OFFSET: usize = 32;
+const MESSAGE_LENGTH_OFFSET: usize = 8;
+/// Message length of a padding record, which carries no message.
+const PADDING_LENGTH: u32 = u32::MAX;
+/// Flag of a record end position claimed but not committed yet.
+const PENDING: u64 = 1 << 63;
+/// Flag of a record end position whose claim was aborted by the consumer; the record is padding.
+const ABORTED: u64 = 1 << 62;
+
+#[inline]
+fn record_length(message_length: usize) -> u64 {
+    ((RECORD_HEADER_LENGTH + message_length + RECORD_ALIGNMENT - 1) & !(RECORD_ALIGNMENT - 1)) as u64
+}
+
+fn invalid_data(message: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+#[derive(Debug)]
+struct RingMap {
+    map: MmapRaw,
+    capacity: u64,
+}
+
+impl RingMap {
+    fn create(path: &Path, capacity: u64) -> io::Result<Self> {
+        if capacity == 0 || capacity & (RECORD_ALIGNMENT as u64 - 1) != 0 {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "ring capacity must be a non-zero multiple of 16"));
+        }
+        let file = OpenOptions::new().read(true).write(true).create(true).truncate(true).open(path)?;
+        file.set_len(RING_HEADER_LENGTH as u64 + capacity)?;
+        let ring = Self { map: MmapRaw::map_raw(&file)?, capacity };
+        ring.word(CAPACITY_OFFSET).store(capacity, Ordering::Relaxed);
+        ring.word(0).store(MAGIC, Ordering::Release);
+        Ok(ring)
+    }
+
+    fn open(path: &Path) -> io::Result<Self> {
+        let file = OpenOptions::new().read(true).write(true).open(path)?;
+        if file.metadata()?.len() < RING_HEADER_LENGTH as u64 {
+            return Err(invalid_data("not a message ring"));
+        }
+        let mut ring = Self { map: MmapRaw::map_raw(&file)?, capacity: 0 };
+        if ring.word(0).load(Ordering::Acquire) != MAGIC {
+            return Err(invalid_data("not a message ring"));
+        }
+        let capacity = ring.word(CAPACITY_OFFSET).load(Ordering::Relaxed);
+        let file_length = RING_HEADER_LENGTH as u64 + capacity;
+        if capacity == 0 || capacity & (RECORD_ALIGNMENT as u64 - 1) != 0 || ring.map.len() as u64 != file_length {
+            return Err(invalid_data("ring capacity does not match the file length"));
+        }
+        ring.capacity = capacity;
+        Ok(ring)
+    }
+
+    /// The 8-byte word at `offset` in the mapping, shared with the other processes.
+    #[inline]
+    fn word(&self, offset: usize) -> &AtomicU64 {
+        assert!(offset & 7 == 0 && offset + 8 <= self.map.len(), "word outside the ring");
+        // SAFETY: the word is in bounds and aligned, as the mapping is page aligned, and lives as
+        // long as `self`. Every process accesses it atomically.
+        unsafe { &*(self.map.as_ptr().add(offset) as *const AtomicU64) }
+    }
+
+    /// Offset in the mapping of the record at `position`.
+    #[inline]
+    fn record_offset(&self, position: u64) -> usize {
+        RING_HEADER_LENGTH + (position % self.capacity) as usize
+    }
+
+    /// End position and message length of the record at `position`, or `None` until it is
+    /// committed.
+    #[inline]
+    fn committed(&self, position: u64) -> Option<(u64, u32)> {
+        let offset = self.record_offset(position);
+        let end = self.word(offset).load(Ordering::Acquire);
+        if end & ABORTED != 0 {
+            return Some((end & !ABORTED, PADDING_LENGTH));
+        }
+        if end & PENDING != 0 || end <= position {
+            return None;
+        }
+        Some((end, self.word(offset + MESSAGE_LENGTH_OFFSET).load(Ordering::Relaxed) as u32))
+    }
+
+    /// End position of the record at `position` while it is claimed but not committed.
+    #[inline]
+    fn pending(&self, position: u64) -> Option<u64> {
+        let end = self.word(self.record_offset(position)).load(Ordering::Acquire);
+        if end & PENDING == 0 || end & !PENDING <= position {
+            return None;
+        }
+        Some(end & !PENDING)
+    }
+
+    /// Checks that the record at `position` lies within the data region and holds its message.
+    fn check_record(&self, position: u64, end: u64, message_length: u32) -> io::Result<()> {
+        let to_end = self.capacity - position % self.capacity;
+        let length = end - position;
+        let holds_message = message_length == PADDING_LENGTH || record_length(message_length as usize) <= length;
+        if length > to_end || !holds_message {
+            return Err(invalid_data("ring record overruns the data region"));
+        }
+        Ok(())
+    }
+
+    /// Commits the record at `position`, written in full, by storing its end position last.
+    #[inline]
+    fn commit(&self, position: u64, end: u64, message_length: u32) {
+        let offset = self.record_offset(position);
+        self.word(offset + MESSAGE_LENGTH_OFFSET).store(message_length as u64, Ordering::Relaxed);
+        self.word(offset).store(end, Ordering::Release);
+    }
+
+    /// Marks the claim of the record at `position` pending until it is committed.
+    #[inline]
+    fn claim(&self, position: u64, end: u64) {
+        self.word(self.record_offset(position)).store(end | PENDING, Ordering::Release);
+    }
+
+    /// Commits the claimed record at `position` unless the consumer aborted the claim first.
+    #[inline]
+    fn commit_claim(&self, position: u64, end: u64, message_length: u32) -> bool {
+        let offset = self.record_offset(position);
+        self.word(offset + MESSAGE_LENGTH_OFFSET).store(message_length as u64, Ordering::Relaxed);
+        self.word(offset).compare_exchange(end | PENDING, end, Ordering::Release, Ordering::Relaxed).is_ok()
+    }
+
+    /// Turns the pending claim of the record at `position` into padding unless it was committed first.
+    #[inline]
+    fn abort(&self, position: u64, end: u64) -> bool {
+        let word = self.word(self.record_offset(position));
+        word.compare_exchange(end | PENDING, end | ABORTED, Ordering::AcqRel, Ordering::Relaxed).is_ok()
+    }
+
+    /// The message of the committed record at `position`.
+    ///
+    /// # Safety
+    ///
+    /// The record must have been checked with [`RingMap::check_record`], and the bytes are only
+    /// stable until the consumer frees the record.
+    #[inline]
+    unsafe fn message(&self, position: u64, message_length: u32) -> &[u8] {
+        let offset = self.record_offset(position) + RECORD_HEADER_LENGTH;
+        slice::from_raw_parts(self.map.as_ptr().add(offset), message_length as usize)
+    }
+
+    /// Copies the message of the checked record at `position` into `buffer`, word by word as
+    /// the record may be zeroed and its room reused meanwhile; the words fit in the padded record.
+    fn copy_message(&self, position: u64, message_length: u32, buffer: &mut Vec<u8>) {
+        let offset = self.record_offset(position) + RECORD_HEADER_LENGTH;
+        for index in 0..(message_length as usize).div_ceil(8) {
+            buffer.extend_from_slice(&self.word(offset + 8 * index).load(Ordering::Relaxed).to_ne_bytes());
+        }
+        buffer.truncate(message_length as usize);
+    }
+
+    /// Zeroes the data region from `start` to `end`, which have been read by the consumer, word
+    /// by word as tailing subscribers may still be copying the records.
+    fn zero(&self, start: u64, end: u64) {
+        let mut position = start;
+        while position < end {
+            let length = (end - position).min(self.capacity - position % self.capacity);
+            let offset = self.record_offset(position);
+            for word in (offset..offset + length as usize).step_by(8) {
+                self.word(word).store(0, Ordering::Relaxed);
+            }
+            position += length;
+        }
+    }
+}
+
+/// Publishes messages into a ring.
+#[derive(Debug)]
+pub struct RingPublisher {
+    ring: RingMap,
+}
+
+impl RingPublisher {
+    /// Creates the ring file at `path`, replacing any existing one, with a data region of
+    /// `capacity` bytes, a non-zero multiple of 16.
+    pub fn create<P: AsRef<Path>>(path: P, capacity: u64) -> io::Result<Self> {
+        Ok(Self { ring: RingMap::create(path.as_ref(), capacity)? })
+    }
+
+    /// Attaches to the existing ring at `path`.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Ok(Self { ring: RingMap::open(path.as_ref())? })
+    }
+
+    /// Bytes of the data region.
+    #[inline]
+    pub fn capacity(&self) -> u64 {
+        self.ring.capacity
+    }
+
+    /// Reserves room in the ring for one message of up to `max_length` bytes, message header
+    /// included; the record keeps the whole room whatever the length of the message. `None`
+    /// reports back-pressure: the consumer has not freed enough space yet, so the claim should be
+    /// retried later.
+    pub fn try_claim(&mut self, max_length: usize) -> io::Result<Option<RingClaim<'_>>> {
+        let reserved = record_length(max_length);
+        if max_length >= PADDING_LENGTH as usize || reserved > self.ring.capacity {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "claim longer than the ring capacity"));
+        }
+        let capacity = self.ring.capacity;
+        let producer_position = self.ring.word(PRODUCER_POSITION_OFFSET);
+        let mut producer = producer_position.load(Ordering::Acquire);
+        let padding = loop {
+            let consumer = self.ring.word(CONSUMER_POSITION_OFFSET).load(Ordering::Acquire);
+            let to_end = capacity - producer % capacity;
+            let padding = if reserved > to_end { to_end } else { 0 };
+            // `producer` may be stale and behind `consumer`, in which case the exchange fails. When
+            // only the padding fits, it is reserved alone so the claim can start the next lap.
+            let (claimed, complete) = if (producer + padding + reserved).saturating_sub(consumer) <= capacity {
+                (producer + padding + reserved, true)
+            } else if padding > 0 && (producer + padding).saturating_sub(consumer) <= capacity {
+                (producer + padding, false)
+            } else {
+                return Ok(None);
+            };
+            match producer_position.compare_exchange_weak(producer, claimed, Ordering::AcqRel, Ordering::Acquire) {
+                Ok(_) if complete => break padding,
+                Ok(_) => {
+                    self.ring.commit(producer, claimed, PADDING_LENGTH);
+                    producer = claimed;
+                }
+                Err(current) => producer = current,
+            }
+        };
+        let position = producer + padding;
+        self.ring.claim(position, position + reserved);
+        if padding > 0 {
+            self.ring.commit(producer, position, PADDING_LENGTH);
+        }
+        let offset = self.ring.record_offset(position) + RECORD_HEADER_LENGTH;
+        // SAFETY: the exchange gave this claim the room from `position`, zeroed by the consumer and
+        // not read by any subscriber until the claim commits it, and it ends within the region.
+        let room = unsafe { slice::from_raw_parts_mut(self.ring.map.as_mut_ptr().add(offset), max_length) };
+        Ok(Some(RingClaim { ring: &self.ring, writer: MessageWriter::new(room), position, reserved, committed: false }))
+    }
+}
+
+/// Room claimed in a ring for one message. Dropping the claim without committing publishes
+/// nothing: its room becomes a padding record, freed by the consumer like any other.
+#[derive(Debug)]
+pub struct RingClaim<'a> {
+    ring: &'a RingMap,
+    writer: MessageWriter<&'a mut [u8]>,
+    position: u64,
+    reserved: u64,
+    committed: bool,
+}
+
+impl<'a> RingClaim<'a> {
+    /// Writer over the claimed room; one of its message methods encodes the message.
+    #[inline]
+    pub fn writer(&mut self) -> &mut MessageWriter<&'a mut [u8]> {
+        &mut self.writer
+    }
+
+    /// Publishes the message and returns the position of its record in the ring. Fails with
+    /// `TimedOut` when the consumer aborted the claim, which then published nothing.
+    pub fn commit(mut self) -> io::Result<u64> {
+        let message_length = self.writer.len();
+        if message_length == 0 {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "no message written to the claim"));
+        }
+        self.committed = true;
+        if !self.ring.commit_claim(self.position, self.position + self.reserved, message_length as u32) {
+            return Err(io::Error::new(io::ErrorKind::TimedOut, "claim aborted by the consumer"));
+        }
+        Ok(self.position)
+    }
+}
+
+impl Drop for RingClaim<'_> {
+    fn drop(&mut self) {
+        if !self.committed {
+            self.ring.commit_claim(self.position, self.position + self.reserved, PADDING_LENGTH);
+        }
+    }
+}
+
+/// A message read from a ring, borrowed from the mapping for the consumer and from a copy for
+/// tailing subscribers.
+#[derive(Clone, Copy, Debug)]
+pub struct RingRecord<'a> {
+    pub position: u64,
+    message: &'a [u8],
+}
+
+impl<'a> RingRecord<'a> {
+    /// The framed message bytes exactly as they were published.
+    #[inline]
+    pub fn bytes(&self) -> &'a [u8] {
+        self.message
+    }
+
+    #[inline]
+    pub fn message(&self) -> SbeResult<AnyMessage<'a>> {
+        AnyMessage::decode(self.message, 0)
+    }
+}
+
+/// Reads the messages published to a ring, either as its consumer or as a tailing subscriber.
+#[derive(Debug)]
+pub struct RingSubscriber {
+    ring: RingMap,
+    consume: bool,
+    position: u64,
+    buffer: Vec<u8>,
+    /// Pending claim the consumer was last found blocked on, and when.
+    stalled: Option<(u64, Instant)>,
+}
+
+impl RingSubscriber {
+    /// The consumer of the ring at `path`, starting at the first unconsumed message. Polling frees
+    /// the space of the polled messages; a ring has a single consumer.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::new(path.as_ref(), true)
+    }
+
+    /// A tailing subscriber to the ring at `path`, starting at the first unconsumed message. It
+    /// never frees space, and skips ahead to the consumer when it falls behind it.
+    pub fn tail<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::new(path.as_ref(), false)
+    }
+
+    fn new(path: &Path, consume: bool) -> io::Result<Self> {
+        let ring = RingMap::open(path)?;
+        let position = ring.word(CONSUMER_POSITION_OFFSET).load(Ordering::Acquire);
+        Ok(Self { ring, consume, position, buffer: Vec::new(), stalled: None })
+    }
+
+    /// Position of the next message to poll.
+    #[inline]
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Aborts the claim the consumer is blocked on once this consumer has found it pending for at
+    /// least `timeout`, e.g. because its publisher died before committing it, and returns its
+    /// position. The claim becomes padding, so the next poll moves past it. Called while polls find
+    /// nothing, the first call for a claim starts its timeout; the timeout must exceed the time any
+    /// live publisher takes to encode a message, as a slow publisher still writing its aborted room
+    /// would corrupt the record that reuses it.
+    pub fn abort_stalled_claim(&mut self, timeout: Duration) -> io::Result<Option<u64>> {
+        if !self.consume {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "only the consumer aborts claims"));
+        }
+        let position = self.ring.word(CONSUMER_POSITION_OFFSET).load(Ordering::Acquire);
+        let end = match self.ring.pending(position) {
+            Some(end) => end,
+            None => {
+                self.stalled = None;
+                return Ok(None);
+            }
+        };
+        let since = match self.stalled {
+            Some((stalled, since)) if stalled == position => since,
+            _ => {
+                let now = Instant::now();
+                self.stalled = Some((position, now));
+                now
+            }
+        };
+        if since.elapsed() < timeout || !self.ring.abort(position, end) {
+            return Ok(None);
+        }
+        self.stalled = None;
+        Ok(Some(position))
+    }
+
+    /// Calls `f` for every message committed since the last poll, up to the first claim not
+    /// committed yet, and returns how many there were.
+    pub fn poll<F>(&mut self, f: F) -> io::Result<usize>
+    where
+        F: FnMut(&RingRecord<'_>),
+    {
+        if self.consume {
+            self.poll_consumer(f)
+        } else {
+            self.poll_tail(f)
+        }
+    }
+
+    fn poll_consumer<F>(&mut self, mut f: F) -> io::Result<usize>
+    where
+        F: FnMut(&RingRecord<'_>),
+    {
+        let start = self.ring.word(CONSUMER_POSITION_OFFSET).load(Ordering::Acquire);
+        self.position = start;
+        let mut count = 0;
+        let mut result = Ok(());
+        while let Some((end, message_length)) = self.ring.committed(self.position) {
+            result = self.ring.check_record(self.position, end, message_length);
+            if result.is_err() {
+                break;
+            }
+            if message_length != PADDING_LENGTH {
+                // SAFETY: checked above, and only this consumer frees the record, after `f` returns.
+                let message = unsafe { self.ring.message(self.position, message_length) };
+                f(&RingRecord { position: self.position, message });
+                count += 1;
+            }
+            self.position = end;
+        }
+        if self.position > start {
+            self.ring.word(READ_POSITION_OFFSET).store(self.position, Ordering::Release);
+            self.ring.zero(start, self.position);
+            self.ring.word(CONSUMER_POSITION_OFFSET).store(self.position, Ordering::Release);
+        }
+        result.map(|()| count)
+    }
+
+    fn poll_tail<F>(&mut self, mut f: F) -> io::Result<usize>
+    where
+        F: FnMut(&RingRecord<'_>),
+    {
+        let read_position = self.ring.word(READ_POSITION_OFFSET);
+        let mut count = 0;
+        loop {
+            self.position = self.position.max(read_position.load(Ordering::Acquire));
+            let (end, message_length) = match self.ring.committed(self.position) {
+                Some(record) => record,
+                None => return Ok(count),
+            };
+            let checked = self.ring.check_record(self.position, end, message_length);
+            self.buffer.clear();
+            if checked.is_ok() && message_length != PADDING_LENGTH {
+                // The consumer may zero the record and a publisher reuse its room while it is
+                // copied, in which case the read position has moved past it.
+                self.ring.copy_message(self.position, message_length, &mut self.buffer);
+            }
+            fence(Ordering::Acquire);
+            if read_position.load(Ordering::Relaxed) > self.position {
+                continue;
+            }
+            checked?;
+            if message_length != PADDING_LENGTH {
+                f(&RingRecord { position: self.position, message: &self.buffer });
+                count += 1;
+            }
+            self.position = end;
+        }
+    }
+}
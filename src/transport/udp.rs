@@ -0,0 +1,251 @@
+//! Market data fan-out over UDP, typically multicast.
+//!
+//! A [`UdpPublisher`] batches `PriceIncrement`, `PriceSnapshot` and `LastMarketTrade` messages
+//! into datagrams, each starting with a packet header holding its sequence number, sending time,
+//! length and message count. A datagram is sent once the next message would not fit, or on
+//! [`UdpPublisher::flush`]. A [`UdpSubscriber`] receives datagrams, reports sequence number gaps
+//! and stale datagrams, and hands back a decoder per message, read in place by a [`PacketDecoder`].
+//! A restarted publisher numbers its datagrams from 1 again; the subscriber tells them from stale
+//! ones by their sending time, later than that of the last datagram received, and follows the new
+//! sequence numbers from there.
+//!
+//! Both wrap a `std::net::UdpSocket`; socket options missing from std, such as address reuse
+//! for several subscribers on one host, are set on the socket before handing it over.
+use crate::*;
+
+use std::io;
+use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4, UdpSocket};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Default for [`UdpPublisher::max_datagram_length`]: an Ethernet MTU less the IP and UDP headers.
+pub const DEFAULT_MAX_DATAGRAM_LENGTH: usize = 1472;
+/// Largest UDP payload over IPv4.
+pub const MAX_DATAGRAM_LENGTH: usize = 65_507;
+
+fn invalid_data(err: SbeErr) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err)
+}
+
+/// Nanoseconds since the Unix epoch.
+fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_nanos() as u64)
+}
+
+/// Batches market data messages into datagrams sent to one destination.
+#[derive(Debug)]
+pub struct UdpPublisher {
+    socket: UdpSocket,
+    destination: SocketAddr,
+    max_datagram_length: usize,
+    sequence_number: u64,
+    buffer: Vec<u8>,
+    length: usize,
+    message_count: u16,
+}
+
+impl UdpPublisher {
+    /// Publisher sending from `socket` to `destination`.
+    pub fn new(socket: UdpSocket, destination: SocketAddr) -> Self {
+        let mut publisher = Self {
+            socket,
+            destination,
+            max_datagram_length: 0,
+            sequence_number: 0,
+            buffer: Vec::new(),
+            length: packet_header_codec::ENCODED_LENGTH,
+            message_count: 0,
+        };
+        publisher.set_max_datagram_length(DEFAULT_MAX_DATAGRAM_LENGTH);
+        publisher
+    }
+
+    /// Publisher to the multicast `group`, sending from `interface`, e.g. `127.0.0.1` for
+    /// loopback. Datagrams are looped back to subscribers on the same host.
+    pub fn multicast(group: SocketAddrV4, interface: Ipv4Addr) -> io::Result<Self> {
+        let socket = UdpSocket::bind(SocketAddrV4::new(interface, 0))?;
+        socket.set_multicast_loop_v4(true)?;
+        Ok(Self::new(socket, group.into()))
+    }
+
+    /// Largest datagram sent, packet header included, at most [`MAX_DATAGRAM_LENGTH`].
+    pub fn max_datagram_length(mut self, max_datagram_length: usize) -> Self {
+        self.set_max_datagram_length(max_datagram_length);
+        self
+    }
+
+    fn set_max_datagram_length(&mut self, max_datagram_length: usize) {
+        self.max_datagram_length = max_datagram_length.min(MAX_DATAGRAM_LENGTH);
+        // Room for a full datagram of batched messages plus the next message, which is encoded
+        // in place before it is known whether it still fits.
+        self.buffer.resize(2 * self.max_datagram_length, 0);
+    }
+
+    #[inline]
+    pub fn socket(&self) -> &UdpSocket {
+        &self.socket
+    }
+
+    /// Sequence number of the last datagram sent; the first datagram is number 1.
+    #[inline]
+    pub fn sequence_number(&self) -> u64 {
+        self.sequence_number
+    }
+
+    /// Number of messages batched and not yet sent.
+    #[inline]
+    pub fn pending_messages(&self) -> u16 {
+        self.message_count
+    }
+
+    pub fn price_increment<F>(&mut self, encode: F) -> io::Result<()>
+    where
+        F: FnOnce(&mut PriceIncrementEncoder<'_>),
+    {
+        self.append(|writer| writer.price_increment(encode))
+    }
+
+    pub fn price_snapshot<F>(&mut self, encode: F) -> io::Result<()>
+    where
+        F: FnOnce(&mut PriceSnapshotEncoder<'_>),
+    {
+        self.append(|writer| writer.price_snapshot(encode))
+    }
+
+    pub fn last_market_trade<F>(&mut self, encode: F) -> io::Result<()>
+    where
+        F: FnOnce(&mut LastMarketTradeEncoder<'_>),
+    {
+        self.append(|writer| writer.last_market_trade(encode))
+    }
+
+    /// Encodes one message after the batched ones, first sending those as a datagram if the
+    /// message does not fit after them. If that send fails, the batched messages are dropped along
+    /// with this one and the error is returned.
+    fn append<W>(&mut self, write: W) -> io::Result<()>
+    where
+        W: for<'w> FnOnce(&'w mut MessageWriter<&'w mut [u8]>) -> SbeResult<&'w [u8]>,
+    {
+        let start = self.length;
+        let length = write(&mut MessageWriter::new(&mut self.buffer[start..]))
+            .map_err(invalid_data)?
+            .len();
+        let body_offset = packet_header_codec::ENCODED_LENGTH;
+        if body_offset + length > self.max_datagram_length {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "message longer than a datagram"));
+        }
+        if start + length <= self.max_datagram_length && self.message_count < u16::MAX {
+            self.length += length;
+            self.message_count += 1;
+            return Ok(());
+        }
+        self.send()?;
+        self.buffer.copy_within(start..start + length, body_offset);
+        self.length = body_offset + length;
+        self.message_count = 1;
+        Ok(())
+    }
+
+    /// Sends the batched messages as one datagram; does nothing when there are none.
+    pub fn flush(&mut self) -> io::Result<()> {
+        if self.message_count == 0 {
+            return Ok(());
+        }
+        self.send()
+    }
+
+    /// Sends the batched messages and starts a new batch. A datagram that fails to send is dropped.
+    fn send(&mut self) -> io::Result<()> {
+        let length = self.length;
+        let mut header = PacketHeaderEncoder::<WriteBuf<'_>>::default().wrap(WriteBuf::new(&mut self.buffer), 0);
+        header.sequence_number(self.sequence_number + 1);
+        header.sending_time(now());
+        header.packet_length(length as u32);
+        header.message_count(self.message_count);
+        self.length = packet_header_codec::ENCODED_LENGTH;
+        self.message_count = 0;
+        self.socket.send_to(&self.buffer[..length], self.destination)?;
+        self.sequence_number += 1;
+        Ok(())
+    }
+}
+
+/// Packet header of a received datagram.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Packet {
+    pub sequence_number: u64,
+    pub sending_time: u64,
+    pub message_count: u16,
+    /// Datagrams missed between the highest sequence number received before and this one.
+    pub gap: u64,
+    /// The sequence number is not above the highest one received before, nor was the datagram sent
+    /// after it: the datagram is a duplicate or arrived out of order. Its messages are not handed
+    /// out and its gap is 0.
+    pub stale: bool,
+    /// The sequence number went back although the datagram was sent after the last one received:
+    /// the publisher restarted. Sequence numbers are followed from this one on and its gap is 0.
+    pub restarted: bool,
+}
+
+/// Receives datagrams of batched messages.
+#[derive(Debug)]
+pub struct UdpSubscriber {
+    socket: UdpSocket,
+    buffer: Vec<u8>,
+    /// Sequence number and sending time of the last datagram whose messages were handed out.
+    last: Option<(u64, u64)>,
+}
+
+impl UdpSubscriber {
+    /// Subscriber receiving on `socket`, already bound and joined to any multicast group.
+    pub fn new(socket: UdpSocket) -> Self {
+        Self { socket, buffer: vec![0; MAX_DATAGRAM_LENGTH], last: None }
+    }
+
+    /// Subscriber to the multicast `group`, joined on `interface`, e.g. `127.0.0.1` for loopback.
+    pub fn multicast(group: SocketAddrV4, interface: Ipv4Addr) -> io::Result<Self> {
+        let socket = UdpSocket::bind(SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, group.port()))?;
+        socket.join_multicast_v4(group.ip(), &interface)?;
+        Ok(Self::new(socket))
+    }
+
+    #[inline]
+    pub fn socket(&self) -> &UdpSocket {
+        &self.socket
+    }
+
+    /// Receives one datagram, blocking unless the socket is non-blocking or has a read timeout,
+    /// and calls `f` with each of its messages in order, unless the datagram is stale. Malformed
+    /// datagrams are `InvalidData` errors wrapping the [`SbeErr`], `TrailingBytes` for a datagram
+    /// longer than its packet length; messages before the malformed one were handed to `f`.
+    pub fn receive<F>(&mut self, mut f: F) -> io::Result<Packet>
+    where
+        F: FnMut(AnyMessage<'_>),
+    {
+        let received = self.socket.recv(&mut self.buffer)?;
+        let packet = PacketDecoder::wrap(&self.buffer[..received]).map_err(invalid_data)?;
+        if packet.bytes().len() != received {
+            return Err(invalid_data(SbeErr::TrailingBytes));
+        }
+        let sequence_number = packet.sequence_number();
+        let sending_time = packet.sending_time();
+        let (gap, stale, restarted) = match self.last {
+            Some((last, last_sending_time)) if sequence_number <= last => {
+                let restarted = sending_time > last_sending_time;
+                (0, !restarted, restarted)
+            }
+            Some((last, _)) => (sequence_number - last - 1, false, false),
+            None => (0, false, false),
+        };
+        let message_count = packet.message_count();
+        let packet_header = Packet { sequence_number, sending_time, message_count, gap, stale, restarted };
+        if stale {
+            return Ok(packet_header);
+        }
+        self.last = Some((sequence_number, sending_time));
+
+        for message in packet.messages() {
+            f(message.and_then(|message| message.message()).map_err(invalid_data)?);
+        }
+        Ok(packet_header)
+    }
+}
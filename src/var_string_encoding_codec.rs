@@ -0,0 +1,38 @@
+use crate::*;
+
+/// encoded length of the 'length' field preceding the data
+pub const LENGTH_ENCODED_LENGTH: usize = 4;
+/// largest 'length'
+pub const MAX_LENGTH: usize = 1073741824;
+
+/// Reads the length at `offset` and checks that the data fits in `buf`. Returns the offset and
+/// the length of the data.
+pub(crate) fn var_data_extent<O: ByteOrder>(buf: &ReadBuf<'_, O>, offset: usize) -> SbeResult<(usize, usize)> {
+    if buf.data.len() < offset + LENGTH_ENCODED_LENGTH {
+        return Err(SbeErr::BufferTooShort);
+    }
+    let length = buf.get_u32_at(offset) as usize;
+    let data_offset = offset + LENGTH_ENCODED_LENGTH;
+    if length > MAX_LENGTH || buf.data.len() < data_offset + length {
+        return Err(SbeErr::BufferTooShort);
+    }
+    Ok((data_offset, length))
+}
+
+/// Writes `value` prefixed by its length at `offset`. Returns the limit after the data.
+pub(crate) fn encode_var_data<O: ByteOrder>(
+    buf: &mut WriteBuf<'_, O>,
+    offset: usize,
+    value: &[u8],
+) -> SbeResult<usize> {
+    if value.len() > MAX_LENGTH {
+        return Err(SbeErr::VarDataTooLong);
+    }
+    let limit = offset + LENGTH_ENCODED_LENGTH + value.len();
+    if buf.data.len() < limit {
+        return Err(SbeErr::BufferTooShort);
+    }
+    buf.put_u32_at(offset, value.len() as u32);
+    buf.put_slice_at(offset + LENGTH_ENCODED_LENGTH, value);
+    Ok(limit)
+}
@@ -0,0 +1,134 @@
+use com_dev_4fx_sor_codecs_sbe::*;
+
+#[test]
+fn big_endian_buffers_write_and_read_network_order() {
+    let mut data = [0_u8; 16];
+    let mut buf = WriteBuf::with_byte_order(&mut data, BigEndian);
+    buf.put_u16_at(0, 0x0102);
+    buf.put_i32_at(2, -2);
+    buf.put_f64_at(6, 1.5);
+    assert_eq!(data[..6], [0x01, 0x02, 0xff, 0xff, 0xff, 0xfe]);
+    assert_eq!(data[6..14], 1.5_f64.to_be_bytes());
+
+    let buf = ReadBuf::with_byte_order(&data, BigEndian);
+    assert_eq!(buf.get_u16_at(0), 0x0102);
+    assert_eq!(buf.get_i32_at(2), -2);
+    assert_eq!(buf.get_f64_at(6), 1.5);
+    assert_eq!(ReadBuf::new(&data).get_u16_at(0), 0x0201);
+}
+
+#[test]
+fn schema_byte_order_is_the_default() {
+    let mut data = [0_u8; 8];
+    WriteBuf::new(&mut data).put_u64_at(0, 0x0102_0304_0506_0708);
+    assert_eq!(data, 0x0102_0304_0506_0708_u64.to_le_bytes());
+    assert_eq!(ReadBuf::with_byte_order(&data, SchemaByteOrder::default()).get_u64_at(0), 0x0102_0304_0506_0708);
+}
+
+#[test]
+fn messages_round_trip_in_big_endian() {
+    let mut data = [0_u8; 128];
+    let buf = WriteBuf::with_byte_order(&mut data, BigEndian);
+    let mut order = NewOrderSingleEncoder::default()
+        .wrap(buf, message_header_codec::ENCODED_LENGTH)
+        .header(0)
+        .parent()
+        .unwrap();
+    order.cl_ord_id(0x0102_0304_0506_0708);
+    order.cl_ord_link_id(u64::MAX);
+    order.instrument_id(7);
+    order.target_comp_id(2);
+    order.source_comp_id(3);
+    order.side(OrderSide::SELL);
+    order.order_type(OrderType::LIMIT);
+    order.price(1.25);
+    order.order_qty(1_000.0);
+    order.time_in_force(TimeInForce::IOC);
+    order.transact_time(11);
+    order.effective_time(u64::MAX);
+    order.expire_time(u64::MAX);
+    order.target_strategy(Strategy::VENUE);
+    order.source_strategy(Strategy::TWAP);
+    let (length, _) = order.finish().unwrap();
+    let length = message_header_codec::ENCODED_LENGTH + length;
+
+    let block_length = new_order_single_codec::SBE_BLOCK_LENGTH;
+    assert_eq!(data[..2], block_length.to_be_bytes());
+    assert_eq!(data[8..16], 0x0102_0304_0506_0708_u64.to_be_bytes());
+
+    let header = MessageHeaderDecoder::default().wrap(ReadBuf::with_byte_order(&data[..length], BigEndian), 0);
+    assert_eq!((header.block_length(), header.template_id()), (block_length, new_order_single_codec::SBE_TEMPLATE_ID));
+    assert_eq!(header.version(), new_order_single_codec::SBE_SCHEMA_VERSION);
+    let order = NewOrderSingleDecoder::default().header(header);
+    assert_eq!(order.cl_ord_id(), 0x0102_0304_0506_0708);
+    assert_eq!(order.instrument_id(), 7);
+    assert_eq!(order.side(), OrderSide::SELL);
+    assert_eq!(order.price(), 1.25);
+    assert_eq!(order.order_qty(), 1_000.0);
+    assert_eq!(order.time_in_force(), TimeInForce::IOC);
+    assert_eq!(order.transact_time(), 11);
+    assert_eq!(order.target_strategy(), Strategy::VENUE);
+}
+
+#[test]
+fn groups_round_trip_in_big_endian() {
+    let bids = [
+        PriceLevel { id: 1, price: 1.1, leaves_qty: 1_000_000.0, transact_time: 5 },
+        PriceLevel { id: 2, price: 1.05, leaves_qty: 2_000_000.0, transact_time: -5 },
+    ];
+    let offers = [PriceLevel { id: 3, price: 1.2, leaves_qty: 500_000.0, transact_time: 6 }];
+    let mut data = [0_u8; 256];
+    let buf = WriteBuf::with_byte_order(&mut data, BigEndian);
+    let mut snapshot = PriceSnapshotEncoder::default()
+        .wrap(buf, message_header_codec::ENCODED_LENGTH)
+        .header(0)
+        .parent()
+        .unwrap();
+    snapshot.comp_id(2);
+    snapshot.instrument_id(1);
+    snapshot.md_req_id(9);
+    assert_eq!(snapshot.encode_bids(bids), Ok(2));
+    assert_eq!(snapshot.encode_offers(offers), Ok(1));
+    let (length, _) = snapshot.finish().unwrap();
+    let bytes = &data[..message_header_codec::ENCODED_LENGTH + length];
+
+    let header = MessageHeaderDecoder::default().wrap(ReadBuf::with_byte_order(bytes, BigEndian), 0);
+    let mut snapshot = PriceSnapshotDecoder::default().header(header);
+    assert_eq!((snapshot.comp_id(), snapshot.instrument_id(), snapshot.md_req_id()), (2, 1, 9));
+    assert_eq!(snapshot.bids().unwrap().collect::<Vec<_>>(), bids);
+    assert_eq!(snapshot.offers().unwrap().collect::<Vec<_>>(), offers);
+
+    let header = MessageHeaderDecoder::default().wrap(ReadBuf::with_byte_order(bytes, BigEndian), 0);
+    let mut group = PriceSnapshotDecoder::default().header(header).bids_decoder();
+    let mut levels = [PriceLevel::default(); 2];
+    assert_eq!(group.read_all_into(&mut levels), Ok(2));
+    assert_eq!(levels, bids);
+    let mut group = group.parent().unwrap().offers_decoder();
+    assert_eq!(group.advance(), Ok(Some(0)));
+    assert_eq!(group.offer_decoder().to_price_level(), offers[0]);
+}
+
+#[test]
+fn instrument_ids_round_trip_in_big_endian() {
+    let mut data = [0_u8; 128];
+    let buf = WriteBuf::with_byte_order(&mut data, BigEndian);
+    let mut request = MarketDataRequestEncoder::default()
+        .wrap(buf, message_header_codec::ENCODED_LENGTH)
+        .header(0)
+        .parent()
+        .unwrap();
+    request.md_req_id(9);
+    request.target_comp_id(2);
+    request.source_comp_id(3);
+    request.subscription_request_type(SubscriptionRequestType::SNAPSHOT_AND_UPDATES);
+    request.market_depth(5);
+    request.transact_time(11);
+    assert_eq!(request.encode_instruments([0x0102_0304, 7]), Ok(2));
+    let (length, _) = request.finish().unwrap();
+    let bytes = &data[..message_header_codec::ENCODED_LENGTH + length];
+
+    let header = MessageHeaderDecoder::default().wrap(ReadBuf::with_byte_order(bytes, BigEndian), 0);
+    let mut request = MarketDataRequestDecoder::default().header(header);
+    assert_eq!((request.md_req_id(), request.market_depth()), (9, 5));
+    assert_eq!(request.instruments().unwrap().collect::<Vec<_>>(), vec![0x0102_0304, 7]);
+}
@@ -0,0 +1,114 @@
+use com_dev_4fx_sor_codecs_sbe::*;
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::Hasher;
+
+fn execution_report(buf: &mut [u8], price: f64, last_qty: f64, text: &str) -> usize {
+    let mut report = ExecutionReportEncoder::default()
+        .wrap(WriteBuf::new(buf), message_header_codec::ENCODED_LENGTH)
+        .header(0)
+        .parent()
+        .unwrap();
+    report.cl_ord_id(1);
+    report.side(OrderSide::BUY);
+    report.price(price);
+    report.avg_price(f64::NAN);
+    report.last_qty(last_qty);
+    report.exec_type(ExecType::FILL);
+    report.text(text.as_bytes()).unwrap();
+    message_header_codec::ENCODED_LENGTH + report.encoded_length()
+}
+
+fn price_snapshot(buf: &mut [u8], bids: &[PriceLevel]) -> usize {
+    price_snapshot_in(buf, bids, SchemaByteOrder::default())
+}
+
+fn price_snapshot_in<O: ByteOrder>(buf: &mut [u8], bids: &[PriceLevel], byte_order: O) -> usize {
+    let mut snapshot = PriceSnapshotEncoder::default()
+        .wrap(WriteBuf::with_byte_order(buf, byte_order), message_header_codec::ENCODED_LENGTH)
+        .header(0)
+        .parent()
+        .unwrap();
+    snapshot.instrument_id(1);
+    snapshot.encode_bids(bids).unwrap();
+    snapshot.encode_offers(std::iter::empty::<PriceLevel>()).unwrap();
+    snapshot.get_limit()
+}
+
+fn hash<'a>(message: &impl MessageDiff<'a>) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    message.hash_fields(&mut hasher).unwrap();
+    hasher.finish()
+}
+
+#[test]
+fn execution_reports_differ_by_field_and_text_with_tolerance_and_nan_equality() {
+    let (mut old, mut new) = ([0_u8; 256], [0_u8; 256]);
+    let old_length = execution_report(&mut old, 1.1, 100.0, "fill");
+    let new_length = execution_report(&mut new, 1.1 + 1e-12, 50.0, "partial fill");
+    let old = ExecutionReportDecoder::default().header(MessageHeaderDecoder::default().wrap(ReadBuf::new(&old[..old_length]), 0));
+    let new = ExecutionReportDecoder::default().header(MessageHeaderDecoder::default().wrap(ReadBuf::new(&new[..new_length]), 0));
+
+    let diffs = old.diffs(&new, &DiffOptions::default().f64_tolerance(1e-9)).unwrap();
+    assert_eq!(
+        diffs,
+        vec![
+            FieldDiff { entry: None, field: "lastQty", old: FieldValue::F64(100.0), new: FieldValue::F64(50.0) },
+            FieldDiff { entry: None, field: "text", old: FieldValue::Bytes(b"fill"), new: FieldValue::Bytes(b"partial fill") },
+        ]
+    );
+    assert_eq!(old.diff(&new, &DiffOptions::default(), |_| {}).unwrap(), 3);
+    assert!(old.fields_eq(&old, &DiffOptions::default()).unwrap());
+    assert_eq!(hash(&old), hash(&old));
+    assert_ne!(hash(&old), hash(&new));
+}
+
+#[test]
+fn price_snapshots_report_group_count_and_entry_differences() {
+    let level = PriceLevel { id: 1, price: 1.1, leaves_qty: 1e6, transact_time: 7 };
+    let (mut old, mut new) = ([0_u8; 256], [0_u8; 256]);
+    let old_length = price_snapshot(&mut old, &[level]);
+    let new_length = price_snapshot(&mut new, &[PriceLevel { price: 1.2, ..level }, level]);
+    let mut old = match AnyMessage::decode(&old[..old_length], 0).unwrap() {
+        AnyMessage::PriceSnapshot(decoder) => decoder,
+        other => panic!("decoded {:?}", other),
+    };
+    let new = match AnyMessage::decode(&new[..new_length], 0).unwrap() {
+        AnyMessage::PriceSnapshot(decoder) => decoder,
+        other => panic!("decoded {:?}", other),
+    };
+    old.bids().unwrap();
+
+    let diffs = old.diffs(&new, &DiffOptions::default()).unwrap();
+    assert_eq!(
+        diffs,
+        vec![
+            FieldDiff { entry: None, field: "bids", old: FieldValue::U16(1), new: FieldValue::U16(2) },
+            FieldDiff { entry: Some(("bids", 0)), field: "price", old: FieldValue::F64(1.1), new: FieldValue::F64(1.2) },
+        ]
+    );
+}
+
+#[test]
+fn big_endian_decoders_diff_and_hash_like_schema_order_ones() {
+    let level = PriceLevel { id: 1, price: 1.1, leaves_qty: 1e6, transact_time: 7 };
+    let (mut old, mut new, mut schema_order) = ([0_u8; 256], [0_u8; 256], [0_u8; 256]);
+    let old_length = price_snapshot_in(&mut old, &[level], BigEndian);
+    let new_length = price_snapshot_in(&mut new, &[PriceLevel { price: 1.2, ..level }], BigEndian);
+    let schema_order_length = price_snapshot(&mut schema_order, &[level]);
+    let big_endian = |bytes| MessageHeaderDecoder::default().wrap(ReadBuf::with_byte_order(bytes, BigEndian), 0);
+    let old = PriceSnapshotDecoder::default().header(big_endian(&old[..old_length]));
+    let new = PriceSnapshotDecoder::default().header(big_endian(&new[..new_length]));
+
+    let diffs = old.diffs(&new, &DiffOptions::default()).unwrap();
+    assert_eq!(
+        diffs,
+        vec![FieldDiff { entry: Some(("bids", 0)), field: "price", old: FieldValue::F64(1.1), new: FieldValue::F64(1.2) }]
+    );
+    assert!(old.fields_eq(&old, &DiffOptions::default()).unwrap());
+    let schema_order = match AnyMessage::decode(&schema_order[..schema_order_length], 0).unwrap() {
+        AnyMessage::PriceSnapshot(decoder) => decoder,
+        other => panic!("decoded {:?}", other),
+    };
+    assert_eq!(hash(&old), hash(&schema_order));
+}
@@ -0,0 +1,59 @@
+use com_dev_4fx_sor_codecs_sbe::instrument_registry::InstrumentRegistry;
+use com_dev_4fx_sor_codecs_sbe::*;
+
+use std::convert::TryInto;
+
+fn definition(writer: &mut MessageWriter<Vec<u8>>, instrument_id: u32, symbol: &str, tick_size: f64) -> Vec<u8> {
+    writer.reset();
+    writer
+        .instrument_definition(|definition| {
+            definition.instrument_id(instrument_id);
+            definition.symbol(&to_char_array(symbol.as_bytes()).unwrap());
+            definition.base_currency(symbol.as_bytes()[..3].try_into().unwrap());
+            definition.term_currency(symbol.as_bytes()[3..6].try_into().unwrap());
+            definition.tick_size(tick_size);
+            definition.min_qty(1_000.0);
+            definition.max_qty(f64::NAN);
+            definition.price_precision(5);
+            definition.instrument_status(InstrumentStatus::ACTIVE);
+            definition.transact_time(1);
+        })
+        .unwrap()
+        .to_vec()
+}
+
+#[test]
+fn registry_is_populated_from_definitions_and_queried_by_id_or_symbol() {
+    let mut writer = MessageWriter::with_capacity(256);
+    let mut registry = InstrumentRegistry::new();
+    registry.handle(&definition(&mut writer, 1, "EURUSD", 0.00001)).unwrap();
+    registry.handle(&definition(&mut writer, 2, "USDJPY", 0.001)).unwrap();
+    writer.reset();
+    let logon = writer.logon_request(|logon| logon.transact_time(1)).unwrap().to_vec();
+    registry.handle(&logon).unwrap();
+
+    assert_eq!(registry.len(), 2);
+    let eurusd = registry.get(1).unwrap();
+    assert_eq!((eurusd.symbol(), eurusd.base_currency(), eurusd.term_currency()), ("EURUSD", "EUR", "USD"));
+    assert_eq!(registry.by_symbol("USDJPY").unwrap().instrument_id, 2);
+    assert!(registry.by_symbol("GBPUSD").is_none());
+
+    registry.handle(&definition(&mut writer, 2, "GBPUSD", 0.00001)).unwrap();
+    assert_eq!(registry.len(), 2);
+    assert!(registry.by_symbol("USDJPY").is_none());
+    assert_eq!(registry.by_symbol("GBPUSD").unwrap().instrument_id, 2);
+}
+
+#[test]
+fn prices_round_to_tick_and_quantities_respect_bounds() {
+    let mut writer = MessageWriter::with_capacity(256);
+    let mut registry = InstrumentRegistry::new();
+    registry.handle(&definition(&mut writer, 1, "EURUSD", 0.00005)).unwrap();
+    let eurusd = registry.get(1).unwrap();
+
+    assert_eq!(eurusd.round_to_tick(1.102_37), 1.102_35);
+    assert_eq!(eurusd.round_to_tick(1.102_38), 1.102_4);
+    assert!(!eurusd.accepts_qty(999.0));
+    assert!(eurusd.accepts_qty(1e9));
+    assert!(!eurusd.accepts_qty(f64::NAN));
+}
@@ -0,0 +1,165 @@
+use com_dev_4fx_sor_codecs_sbe::journal::{
+    Direction, JournalPosition, JournalReader, JournalWriter, RECORD_HEADER_LENGTH,
+};
+use com_dev_4fx_sor_codecs_sbe::*;
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::{env, process};
+
+/// Empty journal directory, unique to the test and process.
+fn journal_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("sbe-journal-{}-{}", name, process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+fn new_order_single(writer: &mut MessageWriter<Vec<u8>>, cl_ord_id: u64) -> Vec<u8> {
+    writer.reset();
+    writer
+        .new_order_single(|order| {
+            order.cl_ord_id(cl_ord_id);
+            order.cl_ord_link_id(u64::MAX);
+            order.instrument_id(1);
+            order.target_comp_id(2);
+            order.source_comp_id(3);
+            order.side(OrderSide::BUY);
+            order.order_type(OrderType::LIMIT);
+            order.price(1.1);
+            order.order_qty(1_000.0);
+            order.time_in_force(TimeInForce::GTC);
+            order.transact_time(1);
+            order.effective_time(u64::MAX);
+            order.expire_time(u64::MAX);
+            order.target_strategy(Strategy::VENUE);
+            order.source_strategy(Strategy::TWAP);
+        })
+        .unwrap()
+        .to_vec()
+}
+
+/// Journal of orders `cl_ord_id` 1..=`count` appended at timestamp `10 * cl_ord_id`, with
+/// segments holding `records_per_segment` records.
+fn write_orders(name: &str, count: u64, records_per_segment: u64) -> PathBuf {
+    let dir = journal_dir(name);
+    let mut writer = MessageWriter::with_capacity(512);
+    let record_length = (RECORD_HEADER_LENGTH + new_order_single(&mut writer, 0).len()) as u64;
+    let mut journal = JournalWriter::open(&dir, records_per_segment * record_length).unwrap();
+    for cl_ord_id in 1..=count {
+        journal.append(10 * cl_ord_id, Direction::INBOUND, &new_order_single(&mut writer, cl_ord_id)).unwrap();
+    }
+    journal.flush().unwrap();
+    dir
+}
+
+/// `(timestamp, clOrdId)` of every record replayed from `position`.
+fn replay_from(reader: &JournalReader, position: JournalPosition) -> Vec<(u64, u64)> {
+    let mut records = Vec::new();
+    reader
+        .replay_from(position, |record| match record.message() {
+            Ok(AnyMessage::NewOrderSingle(order)) => records.push((record.timestamp, order.cl_ord_id())),
+            other => panic!("unexpected {:?}", other),
+        })
+        .unwrap();
+    records
+}
+
+#[test]
+fn appends_past_max_segment_length_roll_to_a_new_segment() {
+    let dir = write_orders("roll", 7, 3);
+    let reader = JournalReader::open(&dir).unwrap();
+
+    assert_eq!(reader.segment_count(), 3);
+    let first_timestamps: Vec<_> = (0..3).map(|segment| reader.segment_first_timestamp(segment).unwrap()).collect();
+    assert_eq!(first_timestamps, vec![10, 40, 70]);
+    let lengths: Vec<_> = (0..3).map(|segment| reader.read_segment(segment).unwrap().records().count()).collect();
+    assert_eq!(lengths, vec![3, 3, 1]);
+    let segment = reader.read_segment(1).unwrap();
+    let record = segment.records().next().unwrap();
+    assert_eq!((record.offset, record.timestamp, record.direction), (0, 40, Direction::INBOUND));
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn reopening_drops_a_torn_tail_and_continues_the_newest_segment() {
+    let dir = write_orders("torn", 4, 3);
+    assert_eq!(JournalReader::open(&dir).unwrap().segment_count(), 2);
+    let newest_path = fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .max()
+        .unwrap();
+    let complete_length = fs::metadata(&newest_path).unwrap().len();
+    let mut writer = MessageWriter::with_capacity(512);
+    let torn = new_order_single(&mut writer, 5);
+    let mut file = OpenOptions::new().append(true).open(&newest_path).unwrap();
+    let mut header = [0_u8; RECORD_HEADER_LENGTH];
+    header[..4].copy_from_slice(&(torn.len() as u32).to_le_bytes());
+    header[4] = Direction::INBOUND as u8;
+    header[8..].copy_from_slice(&50_u64.to_le_bytes());
+    file.write_all(&header).unwrap();
+    file.write_all(&torn[..torn.len() / 2]).unwrap();
+    drop(file);
+
+    let mut journal = JournalWriter::open(&dir, u64::MAX).unwrap();
+    assert_eq!(fs::metadata(&newest_path).unwrap().len(), complete_length);
+    journal.append(60, Direction::OUTBOUND, &new_order_single(&mut writer, 6)).unwrap();
+    journal.flush().unwrap();
+
+    let reader = JournalReader::open(&dir).unwrap();
+    assert_eq!(reader.segment_count(), 2);
+    assert_eq!(replay_from(&reader, JournalPosition::default()), vec![(10, 1), (20, 2), (30, 3), (40, 4), (60, 6)]);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn seek_finds_the_first_record_at_or_after_a_timestamp_across_segments() {
+    let dir = write_orders("seek", 7, 3);
+    let reader = JournalReader::open(&dir).unwrap();
+    let record_length = reader.read_segment(0).unwrap().records().next().unwrap().encoded_length();
+
+    assert_eq!(reader.seek(0).unwrap(), Some(JournalPosition { segment: 0, offset: 0 }));
+    assert_eq!(reader.seek(20).unwrap(), Some(JournalPosition { segment: 0, offset: record_length }));
+    // Past the last record of the first segment, before the first record of the second.
+    assert_eq!(reader.seek(35).unwrap(), Some(JournalPosition { segment: 1, offset: 0 }));
+    assert_eq!(reader.seek(40).unwrap(), Some(JournalPosition { segment: 1, offset: 0 }));
+    assert_eq!(reader.seek(61).unwrap(), Some(JournalPosition { segment: 2, offset: 0 }));
+    assert_eq!(reader.seek(71).unwrap(), None);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn replay_from_a_position_continues_into_later_segments() {
+    let dir = write_orders("replay", 7, 3);
+    let reader = JournalReader::open(&dir).unwrap();
+
+    let position = reader.seek(25).unwrap().unwrap();
+    assert_eq!(replay_from(&reader, position), vec![(30, 3), (40, 4), (50, 5), (60, 6), (70, 7)]);
+    let position = reader.seek(70).unwrap().unwrap();
+    assert_eq!(replay_from(&reader, position), vec![(70, 7)]);
+    let all: Vec<_> = (1..=7).map(|cl_ord_id| (10 * cl_ord_id, cl_ord_id)).collect();
+    assert_eq!(replay_from(&reader, JournalPosition::default()), all);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn seek_starts_in_the_earlier_segment_when_a_timestamp_spans_a_segment_boundary() {
+    let dir = journal_dir("boundary");
+    let mut writer = MessageWriter::with_capacity(512);
+    let record_length = (RECORD_HEADER_LENGTH + new_order_single(&mut writer, 0).len()) as u64;
+    let mut journal = JournalWriter::open(&dir, 3 * record_length).unwrap();
+    for (cl_ord_id, timestamp) in [(1, 10), (2, 20), (3, 20), (4, 20), (5, 30)] {
+        journal.append(timestamp, Direction::INBOUND, &new_order_single(&mut writer, cl_ord_id)).unwrap();
+    }
+    journal.flush().unwrap();
+    let reader = JournalReader::open(&dir).unwrap();
+    assert_eq!(reader.segment_first_timestamp(1), Some(20));
+
+    let position = reader.seek(20).unwrap().unwrap();
+    assert_eq!(position, JournalPosition { segment: 0, offset: record_length as usize });
+    assert_eq!(replay_from(&reader, position), vec![(20, 2), (20, 3), (20, 4), (30, 5)]);
+    assert_eq!(reader.read_segment(1).unwrap().seek(25), Some(record_length as usize));
+    assert_eq!(reader.read_segment(1).unwrap().seek(31), None);
+    fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,202 @@
+use com_dev_4fx_sor_codecs_sbe::*;
+
+fn new_order_single<'w>(writer: &'w mut MessageWriter<&mut [u8]>, cl_ord_id: u64) -> SbeResult<&'w [u8]> {
+    writer.new_order_single(|order| {
+        order.cl_ord_id(cl_ord_id);
+        order.cl_ord_link_id(u64::MAX);
+        order.instrument_id(1);
+        order.target_comp_id(2);
+        order.source_comp_id(3);
+        order.side(OrderSide::BUY);
+        order.order_type(OrderType::LIMIT);
+        order.price(1.1);
+        order.order_qty(1_000.0);
+        order.time_in_force(TimeInForce::GTC);
+        order.transact_time(1);
+        order.effective_time(u64::MAX);
+        order.expire_time(u64::MAX);
+        order.target_strategy(Strategy::VENUE);
+        order.source_strategy(Strategy::TWAP);
+    })
+}
+
+fn order_cancel_request<'w>(writer: &'w mut MessageWriter<&mut [u8]>, cl_ord_id: u64) -> SbeResult<&'w [u8]> {
+    writer.order_cancel_request(|request| {
+        request.orig_cl_ord_id(cl_ord_id - 1);
+        request.order_id(1);
+        request.cl_ord_id(cl_ord_id);
+        request.instrument_id(1);
+        request.target_comp_id(2);
+        request.source_comp_id(3);
+        request.target_strategy(Strategy::VENUE);
+        request.source_strategy(Strategy::TWAP);
+        request.side(OrderSide::BUY);
+        request.price(1.1);
+        request.transact_time(1);
+    })
+}
+
+fn price_increment(writer: &mut MessageWriter<Vec<u8>>, instrument_id: u32) -> Vec<u8> {
+    let price_level = PriceLevel { id: instrument_id, price: 1.1, leaves_qty: 1_000_000.0, transact_time: 1 };
+    let level_increment = PriceLevelIncrement { price_level, update_action: UpdateAction::NEW };
+    writer.reset();
+    writer
+        .price_increment(|increment| {
+            increment.comp_id(2);
+            increment.instrument_id(instrument_id);
+            let _ = increment.encode_bids([level_increment]);
+            let _ = increment.encode_offers(std::iter::empty::<PriceLevelIncrement>());
+        })
+        .unwrap()
+        .to_vec()
+}
+
+/// `(templateId, message bytes)` of every message of the packet.
+fn messages(packet: &PacketDecoder<'_>) -> Vec<(u16, Vec<u8>)> {
+    packet
+        .messages()
+        .map(|message| {
+            let message = message.unwrap();
+            (message.header().template_id(), message.bytes().to_vec())
+        })
+        .collect()
+}
+
+#[test]
+fn packets_carry_messages_behind_a_header_with_consecutive_sequence_numbers() {
+    let mut packet = PacketEncoder::with_capacity(1024);
+    let order = packet.message(|writer| new_order_single(writer, 10)).unwrap().to_vec();
+    let cancel = packet.message(|writer| order_cancel_request(writer, 11)).unwrap().to_vec();
+    assert_eq!((packet.sequence_number(), packet.message_count()), (1, 2));
+    let first = packet.finish(7).unwrap().to_vec();
+
+    let mut writer = MessageWriter::with_capacity(512);
+    let increments: Vec<_> = (1..=3).map(|instrument_id| price_increment(&mut writer, instrument_id)).collect();
+    for increment in &increments {
+        packet.push(increment).unwrap();
+    }
+    let second = packet.finish(8).unwrap().to_vec();
+    assert!(packet.is_empty());
+
+    let decoder = PacketDecoder::wrap(&first).unwrap();
+    assert_eq!((decoder.sequence_number(), decoder.sending_time(), decoder.message_count()), (1, 7, 2));
+    assert_eq!(
+        messages(&decoder),
+        vec![
+            (new_order_single_codec::SBE_TEMPLATE_ID, order),
+            (order_cancel_request_codec::SBE_TEMPLATE_ID, cancel)
+        ]
+    );
+    let decoder = PacketDecoder::wrap(&second).unwrap();
+    assert_eq!((decoder.sequence_number(), decoder.sending_time(), decoder.message_count()), (2, 8, 3));
+    let template_id = price_increment_codec::SBE_TEMPLATE_ID;
+    let expected: Vec<_> = increments.into_iter().map(|bytes| (template_id, bytes)).collect();
+    assert_eq!(messages(&decoder), expected);
+    let instrument_ids: Vec<_> = decoder
+        .messages()
+        .map(|message| match message.unwrap().message().unwrap() {
+            AnyMessage::PriceIncrement(increment) => increment.instrument_id(),
+            other => panic!("unexpected {:?}", other),
+        })
+        .collect();
+    assert_eq!(instrument_ids, vec![1, 2, 3]);
+}
+
+#[test]
+fn messages_that_do_not_fit_are_not_appended() {
+    let mut writer = MessageWriter::with_capacity(512);
+    let increment = price_increment(&mut writer, 1);
+    let mut packet = PacketEncoder::new(vec![0; packet_header_codec::ENCODED_LENGTH + increment.len() + 8]);
+    packet.push(&increment).unwrap();
+
+    assert_eq!(packet.push(&increment), Err(SbeErr::BufferTooShort));
+    assert_eq!(packet.message(|writer| new_order_single(writer, 10)).unwrap_err(), SbeErr::BufferTooShort);
+    assert_eq!((packet.message_count(), packet.len()), (1, packet_header_codec::ENCODED_LENGTH + increment.len()));
+    let decoder = PacketDecoder::wrap(packet.finish(1).unwrap()).unwrap();
+    assert_eq!(decoder.messages().count(), 1);
+}
+
+#[test]
+fn back_to_back_packets_and_malformed_messages_are_delimited() {
+    let mut packet = PacketEncoder::with_capacity(1024);
+    let mut stream = Vec::new();
+    for cl_ord_id in [10, 20] {
+        packet.message(|writer| new_order_single(writer, cl_ord_id)).unwrap();
+        stream.extend_from_slice(packet.finish(1).unwrap());
+    }
+
+    let first = PacketDecoder::wrap(&stream).unwrap();
+    let second = PacketDecoder::wrap(&stream[first.bytes().len()..]).unwrap();
+    assert_eq!((first.sequence_number(), second.sequence_number()), (1, 2));
+    assert_eq!((first.messages().count(), second.messages().count()), (1, 1));
+    assert_eq!(PacketDecoder::wrap(&stream[..first.bytes().len() - 1]).unwrap_err(), SbeErr::BufferTooShort);
+
+    let mut malformed = first.bytes().to_vec();
+    let schema_id_offset = packet_header_codec::ENCODED_LENGTH + 4;
+    malformed[schema_id_offset..schema_id_offset + 2].copy_from_slice(&999_u16.to_le_bytes());
+    let decoder = PacketDecoder::wrap(&malformed).unwrap();
+    let mut messages = decoder.messages();
+    assert_eq!(messages.next().unwrap().unwrap_err(), SbeErr::UnknownSchemaId(999));
+    assert!(messages.next().is_none());
+}
+
+/// Packet of the given messages, rewritten by `edit` once the packet header is written.
+fn packet_of(cl_ord_ids: &[u64], edit: impl FnOnce(&mut Vec<u8>)) -> Vec<u8> {
+    let mut packet = PacketEncoder::with_capacity(1024);
+    for &cl_ord_id in cl_ord_ids {
+        packet.message(|writer| new_order_single(writer, cl_ord_id)).unwrap();
+    }
+    let mut bytes = packet.finish(1).unwrap().to_vec();
+    edit(&mut bytes);
+    bytes
+}
+
+fn cl_ord_ids(decoder: &PacketDecoder<'_>) -> Vec<SbeResult<u64>> {
+    decoder
+        .messages()
+        .map(|message| match message?.message()? {
+            AnyMessage::NewOrderSingle(order) => Ok(order.cl_ord_id()),
+            other => panic!("unexpected {:?}", other),
+        })
+        .collect()
+}
+
+#[test]
+fn messages_with_an_unknown_template_id_are_skipped_by_their_block_length() {
+    let bytes = packet_of(&[10, 20, 30], |bytes| {
+        let template_id_offset = packet_header_codec::ENCODED_LENGTH + 2;
+        bytes[template_id_offset..template_id_offset + 2].copy_from_slice(&999_u16.to_le_bytes());
+    });
+    let decoder = PacketDecoder::wrap(&bytes).unwrap();
+    assert_eq!(decoder.message_count(), 3);
+    assert_eq!(cl_ord_ids(&decoder), vec![Ok(20), Ok(30)]);
+
+    // The block of the unknown message overruns the packet.
+    let bytes = packet_of(&[10], |bytes| {
+        let template_id_offset = packet_header_codec::ENCODED_LENGTH + 2;
+        bytes[template_id_offset..template_id_offset + 2].copy_from_slice(&999_u16.to_le_bytes());
+        let block_length_offset = packet_header_codec::ENCODED_LENGTH;
+        bytes[block_length_offset..block_length_offset + 2].copy_from_slice(&200_u16.to_le_bytes());
+    });
+    assert_eq!(cl_ord_ids(&PacketDecoder::wrap(&bytes).unwrap()), vec![Err(SbeErr::BufferTooShort)]);
+}
+
+#[test]
+fn bytes_after_the_last_message_are_rejected() {
+    let trailing = |bytes: &mut Vec<u8>| {
+        bytes.extend_from_slice(&[0; 8]);
+        let length = bytes.len() as u32;
+        PacketHeaderEncoder::<WriteBuf<'_>>::default().wrap(WriteBuf::new(bytes), 0).packet_length(length);
+    };
+    let bytes = packet_of(&[10, 20], trailing);
+    let decoder = PacketDecoder::wrap(&bytes).unwrap();
+    assert_eq!(decoder.messages().size_hint(), (0, Some(3)));
+    assert_eq!(cl_ord_ids(&decoder), vec![Ok(10), Ok(20), Err(SbeErr::TrailingBytes)]);
+
+    // A message count short of the messages leaves them as trailing bytes too.
+    let bytes = packet_of(&[10, 20], |bytes| {
+        PacketHeaderEncoder::<WriteBuf<'_>>::default().wrap(WriteBuf::new(bytes), 0).message_count(1);
+    });
+    assert_eq!(cl_ord_ids(&PacketDecoder::wrap(&bytes).unwrap()), vec![Ok(10), Err(SbeErr::TrailingBytes)]);
+    assert_eq!(cl_ord_ids(&PacketDecoder::wrap(&packet_of(&[10, 20], |_| ())).unwrap()), vec![Ok(10), Ok(20)]);
+}
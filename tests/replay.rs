@@ -0,0 +1,162 @@
+use com_dev_4fx_sor_codecs_sbe::journal::{Direction, JournalReader, JournalWriter};
+use com_dev_4fx_sor_codecs_sbe::replay::{MarketDataReplayer, ReplayClock, ReplaySpeed, ReplayStats, VirtualClock};
+use com_dev_4fx_sor_codecs_sbe::*;
+
+use std::io;
+use std::path::PathBuf;
+use std::{env, fs, process};
+
+const CLOCK_ORIGIN: u64 = 100;
+
+/// Journal of a snapshot at 1000, an order at 1500 (not market data), an increment at 3000 and
+/// a trade at 7000, every `transactTime` being 1.
+fn write_market_data(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("sbe-replay-{}-{}", name, process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    let mut journal = JournalWriter::open(&dir, u64::MAX).unwrap();
+    let mut writer = MessageWriter::with_capacity(512);
+    let level = PriceLevel { id: 1, price: 1.1, leaves_qty: 1_000_000.0, transact_time: 1 };
+
+    let snapshot = writer.price_snapshot(|snapshot| {
+        snapshot.comp_id(2);
+        snapshot.instrument_id(1);
+        let _ = snapshot.encode_bids([level]);
+        let _ = snapshot.encode_offers([level, level]);
+    });
+    journal.append(1_000, Direction::INBOUND, snapshot.unwrap()).unwrap();
+    writer.reset();
+    let order = writer.new_order_single(|order| {
+        order.cl_ord_id(10);
+        order.cl_ord_link_id(u64::MAX);
+        order.instrument_id(1);
+        order.target_comp_id(2);
+        order.source_comp_id(3);
+        order.side(OrderSide::BUY);
+        order.order_type(OrderType::LIMIT);
+        order.price(1.1);
+        order.order_qty(1_000.0);
+        order.time_in_force(TimeInForce::GTC);
+        order.transact_time(1);
+        order.effective_time(u64::MAX);
+        order.expire_time(u64::MAX);
+        order.target_strategy(Strategy::VENUE);
+        order.source_strategy(Strategy::TWAP);
+    });
+    journal.append(1_500, Direction::OUTBOUND, order.unwrap()).unwrap();
+    writer.reset();
+    let increment = writer.price_increment(|increment| {
+        increment.comp_id(2);
+        increment.instrument_id(1);
+        let _ = increment.encode_bids(std::iter::empty::<PriceLevelIncrement>());
+        let offer = PriceLevelIncrement { price_level: level, update_action: UpdateAction::UPDATE };
+        let _ = increment.encode_offers([offer]);
+    });
+    journal.append(3_000, Direction::INBOUND, increment.unwrap()).unwrap();
+    writer.reset();
+    let trade = writer.last_market_trade(|trade| {
+        trade.comp_id(2);
+        trade.instrument_id(1);
+        trade.quantity(1_000_000.0);
+        trade.price(1.1);
+        trade.transact_time(1);
+    });
+    journal.append(7_000, Direction::INBOUND, trade.unwrap()).unwrap();
+    journal.flush().unwrap();
+    dir
+}
+
+/// `(replayTime, recordedTime, transactTime of every trade and price level)` of every event.
+fn replay(
+    replayer: &mut MarketDataReplayer<VirtualClock>,
+    reader: &JournalReader,
+) -> (Vec<(u64, u64, Vec<i64>)>, ReplayStats) {
+    let mut events = Vec::new();
+    let stats = replayer
+        .replay(reader, |event| {
+            let transact_times = match event.message().unwrap() {
+                AnyMessage::PriceSnapshot(mut snapshot) => {
+                    let bids: Vec<_> = snapshot.bids().unwrap().collect();
+                    let offers: Vec<_> = snapshot.offers().unwrap().collect();
+                    bids.iter().chain(&offers).map(|level| level.transact_time).collect()
+                }
+                AnyMessage::PriceIncrement(mut increment) => {
+                    let bids: Vec<_> = increment.bids().unwrap().collect();
+                    let offers: Vec<_> = increment.offers().unwrap().collect();
+                    bids.iter().chain(&offers).map(|increment| increment.price_level.transact_time).collect()
+                }
+                AnyMessage::LastMarketTrade(trade) => vec![trade.transact_time() as i64],
+                other => panic!("unexpected {:?}", other),
+            };
+            events.push((event.replay_time, event.recorded_time, transact_times));
+        })
+        .unwrap();
+    (events, stats)
+}
+
+fn replay_times(speed: ReplaySpeed, reader: &JournalReader) -> (Vec<u64>, u64) {
+    let mut replayer = MarketDataReplayer::new(VirtualClock::new(CLOCK_ORIGIN), speed).unwrap();
+    let (events, stats) = replay(&mut replayer, reader);
+    assert_eq!(stats, ReplayStats { emitted: 3, skipped: 1 });
+    let recorded_times: Vec<_> = events.iter().map(|(_, recorded_time, _)| *recorded_time).collect();
+    assert_eq!(recorded_times, vec![1_000, 3_000, 7_000]);
+    (events.into_iter().map(|(replay_time, _, _)| replay_time).collect(), replayer.clock().now())
+}
+
+#[test]
+fn original_speed_keeps_the_recorded_gaps() {
+    let dir = write_market_data("original");
+    let reader = JournalReader::open(&dir).unwrap();
+    assert_eq!(replay_times(ReplaySpeed::Original, &reader), (vec![100, 2_100, 6_100], 6_100));
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn multiplier_divides_the_recorded_gaps() {
+    let dir = write_market_data("multiplier");
+    let reader = JournalReader::open(&dir).unwrap();
+    assert_eq!(replay_times(ReplaySpeed::Multiplier(4.0), &reader), (vec![100, 600, 1_600], 1_600));
+    assert_eq!(replay_times(ReplaySpeed::Multiplier(0.5), &reader), (vec![100, 4_100, 12_100], 12_100));
+
+    // Gaps scaled past the end of the clock saturate instead of overflowing.
+    let clock = VirtualClock::new(u64::MAX - 1);
+    let mut replayer = MarketDataReplayer::new(clock, ReplaySpeed::Multiplier(f64::MIN_POSITIVE)).unwrap();
+    let (events, _) = replay(&mut replayer, &reader);
+    let replay_times: Vec<_> = events.into_iter().map(|(replay_time, _, _)| replay_time).collect();
+    assert_eq!(replay_times, vec![u64::MAX - 1, u64::MAX, u64::MAX]);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn multipliers_that_are_not_finite_and_positive_are_rejected() {
+    for factor in [0.0, -0.0, -2.0, f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+        let replayer = MarketDataReplayer::new(VirtualClock::default(), ReplaySpeed::Multiplier(factor));
+        assert_eq!(replayer.unwrap_err().kind(), io::ErrorKind::InvalidInput, "factor {}", factor);
+    }
+}
+
+#[test]
+fn as_fast_as_possible_does_not_wait_on_the_clock() {
+    let dir = write_market_data("as-fast-as-possible");
+    let reader = JournalReader::open(&dir).unwrap();
+    assert_eq!(replay_times(ReplaySpeed::AsFastAsPossible, &reader), (vec![100, 100, 100], 100));
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn transact_times_are_rewritten_to_the_replay_time_only_when_asked() {
+    let dir = write_market_data("rewrite");
+    let reader = JournalReader::open(&dir).unwrap();
+
+    let mut replayer = MarketDataReplayer::new(VirtualClock::new(CLOCK_ORIGIN), ReplaySpeed::Original)
+        .unwrap()
+        .rewrite_transact_time(true);
+    let (events, _) = replay(&mut replayer, &reader);
+    let transact_times: Vec<_> = events.into_iter().map(|(_, _, transact_times)| transact_times).collect();
+    assert_eq!(transact_times, vec![vec![100; 3], vec![2_100], vec![6_100]]);
+
+    let mut replayer = MarketDataReplayer::new(VirtualClock::new(CLOCK_ORIGIN), ReplaySpeed::Original).unwrap();
+    let (events, _) = replay(&mut replayer, &reader);
+    let transact_times: Vec<_> = events.into_iter().map(|(_, _, transact_times)| transact_times).collect();
+    assert_eq!(transact_times, vec![vec![1; 3], vec![1], vec![1]]);
+    fs::remove_dir_all(dir).unwrap();
+}
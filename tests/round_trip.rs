@@ -0,0 +1,911 @@
+//! Property-based round trips for every message.
+//!
+//! Each message is encoded from generated field values, decoded through [`AnyMessage`] and
+//! re-encoded from the decoded values; both encodings must be byte-identical. Field domains
+//! include NaN and arbitrary bit patterns for prices, null sentinels, every enum variant and
+//! group counts from 0 to 300. Decoding arbitrary bytes through the checked API must not panic.
+use com_dev_4fx_sor_codecs_sbe::*;
+use com_dev_4fx_sor_codecs_sbe::Strategy;
+use proptest::collection::vec;
+use proptest::prelude::*;
+use proptest::sample::select;
+use proptest::strategy::Strategy as Generator;
+
+const BUFFER_LENGTH: usize = 32768;
+/// past the 254 entries a `SmallGroupSizeEncoding` header can count
+const MAX_GROUP_COUNT: usize = 300;
+
+macro_rules! variants {
+    ($enum:ident) => {
+        select((0..=u8::MAX).filter(|&v| $enum::from(v) as u8 == v).map($enum::from).collect::<Vec<_>>())
+    };
+}
+
+fn id_u64() -> BoxedStrategy<u64> {
+    prop_oneof![Just(u64::MAX), Just(0), any::<u64>()].boxed()
+}
+
+fn id_u32() -> BoxedStrategy<u32> {
+    prop_oneof![Just(u32::MAX), Just(0), any::<u32>()].boxed()
+}
+
+fn decimal() -> BoxedStrategy<f64> {
+    prop_oneof![Just(f64::NAN), any::<u64>().prop_map(f64::from_bits), proptest::num::f64::ANY].boxed()
+}
+
+fn price_level() -> impl Generator<Value = PriceLevel> {
+    (id_u32(), decimal(), decimal(), any::<i64>()).prop_map(|(id, price, leaves_qty, transact_time)| PriceLevel {
+        id,
+        price,
+        leaves_qty,
+        transact_time,
+    })
+}
+
+fn price_level_increment() -> impl Generator<Value = PriceLevelIncrement> {
+    (price_level(), variants!(UpdateAction))
+        .prop_map(|(price_level, update_action)| PriceLevelIncrement { price_level, update_action })
+}
+
+fn text() -> impl Generator<Value = Vec<u8>> {
+    vec(any::<u8>(), 0..64)
+}
+
+fn price_levels() -> impl Generator<Value = Vec<PriceLevel>> {
+    vec(price_level(), 0..=MAX_GROUP_COUNT)
+}
+
+fn price_level_increments() -> impl Generator<Value = Vec<PriceLevelIncrement>> {
+    vec(price_level_increment(), 0..=MAX_GROUP_COUNT)
+}
+
+macro_rules! wrap_encoder {
+    ($encoder:ident, $buf:expr) => {
+        $encoder::default()
+            .wrap(WriteBuf::new($buf), message_header_codec::ENCODED_LENGTH)
+            .header(0)
+            .parent()
+            .unwrap()
+    };
+}
+
+macro_rules! decode {
+    ($variant:ident, $bytes:expr) => {
+        match AnyMessage::decode($bytes, 0).unwrap() {
+            AnyMessage::$variant(decoder) => decoder,
+            other => panic!("decoded {:?}", other),
+        }
+    };
+}
+
+/// Round trip of a message made of fixed-length fields, char arrays and at most one trailing
+/// var data field; every field must be listed.
+macro_rules! fixed_round_trip {
+    (
+        $test:ident, $variant:ident, $encoder:ident, { $($field:ident: $strategy:expr),+ $(,)? }
+        $(chars: { $($chars:ident: $chars_strategy:expr),+ $(,)? })?
+        $(var_data: $var_data:ident)?
+    ) => {
+        proptest! {
+            #[test]
+            fn $test($($field in $strategy,)+ $($($chars in $chars_strategy,)+)? $($var_data in text())?) {
+                let mut first = vec![0_u8; BUFFER_LENGTH];
+                let mut encoder = wrap_encoder!($encoder, &mut first);
+                $(encoder.$field($field);)+
+                $($(encoder.$chars(&$chars);)+)?
+                $(encoder.$var_data(&$var_data).unwrap();)?
+                let first_length = message_header_codec::ENCODED_LENGTH + encoder.finish().unwrap().0;
+
+                let decoder = decode!($variant, &first[..first_length]);
+                let mut second = vec![0_u8; BUFFER_LENGTH];
+                let mut encoder = wrap_encoder!($encoder, &mut second);
+                $(encoder.$field(decoder.$field());)+
+                $($(encoder.$chars(decoder.$chars());)+)?
+                $(
+                    let mut decoder = decoder;
+                    let decoded = decoder.$var_data().unwrap();
+                    prop_assert_eq!(decoded, &$var_data[..]);
+                    encoder.$var_data(decoded).unwrap();
+                )?
+                let second_length = message_header_codec::ENCODED_LENGTH + encoder.finish().unwrap().0;
+
+                prop_assert_eq!(&first[..first_length], &second[..second_length]);
+            }
+        }
+    };
+}
+
+fixed_round_trip!(last_market_trade, LastMarketTrade, LastMarketTradeEncoder, {
+    comp_id: id_u32(),
+    instrument_id: id_u32(),
+    quantity: decimal(),
+    price: decimal(),
+    transact_time: id_u64(),
+});
+
+fixed_round_trip!(price_snapshot_request, PriceSnapshotRequest, PriceSnapshotRequestEncoder, {
+    target_comp_id: id_u32(),
+    source_comp_id: id_u32(),
+    instrument_id: id_u32(),
+    transact_time: id_u64(),
+});
+
+fixed_round_trip!(new_order_single, NewOrderSingle, NewOrderSingleEncoder, {
+    cl_ord_id: id_u64(),
+    cl_ord_link_id: id_u64(),
+    instrument_id: id_u32(),
+    target_comp_id: id_u32(),
+    source_comp_id: id_u32(),
+    side: variants!(OrderSide),
+    order_type: variants!(OrderType),
+    price: decimal(),
+    order_qty: decimal(),
+    time_in_force: variants!(TimeInForce),
+    transact_time: id_u64(),
+    effective_time: id_u64(),
+    expire_time: id_u64(),
+    target_strategy: variants!(Strategy),
+    source_strategy: variants!(Strategy),
+});
+
+fixed_round_trip!(execution_report, ExecutionReport, ExecutionReportEncoder, {
+    cl_ord_id: id_u64(),
+    cl_ord_link_id: id_u64(),
+    instrument_id: id_u32(),
+    target_comp_id: id_u32(),
+    source_comp_id: id_u32(),
+    side: variants!(OrderSide),
+    order_type: variants!(OrderType),
+    price: decimal(),
+    order_qty: decimal(),
+    time_in_force: variants!(TimeInForce),
+    transact_time: id_u64(),
+    effective_time: id_u64(),
+    expire_time: id_u64(),
+    target_strategy: variants!(Strategy),
+    source_strategy: variants!(Strategy),
+    orig_cl_ord_id: id_u64(),
+    exec_id: id_u64(),
+    order_id: id_u64(),
+    exec_type: variants!(ExecType),
+    order_status: variants!(OrderStatus),
+    order_rej_reason: variants!(OrderRejectReason),
+    last_qty: decimal(),
+    last_price: decimal(),
+    leaves_qty: decimal(),
+    cum_qty: decimal(),
+    avg_price: decimal(),
+    ord_status_req_id: id_u64(),
+    mass_status_req_id: id_u64(),
+    last_rpt_requested: variants!(BooleanType),
+} var_data: text);
+
+fixed_round_trip!(order_cancel_request, OrderCancelRequest, OrderCancelRequestEncoder, {
+    orig_cl_ord_id: id_u64(),
+    order_id: id_u64(),
+    cl_ord_id: id_u64(),
+    instrument_id: id_u32(),
+    target_comp_id: id_u32(),
+    source_comp_id: id_u32(),
+    target_strategy: variants!(Strategy),
+    source_strategy: variants!(Strategy),
+    side: variants!(OrderSide),
+    price: decimal(),
+    transact_time: id_u64(),
+});
+
+fixed_round_trip!(order_cancel_reject, OrderCancelReject, OrderCancelRejectEncoder, {
+    orig_cl_ord_id: id_u64(),
+    order_id: id_u64(),
+    cl_ord_id: id_u64(),
+    order_status: variants!(OrderStatus),
+    instrument_id: id_u32(),
+    target_comp_id: id_u32(),
+    source_comp_id: id_u32(),
+    target_strategy: variants!(Strategy),
+    source_strategy: variants!(Strategy),
+    cancel_rej_response_to: variants!(CancelRejectResponseTo),
+    cancel_rej_reason: variants!(CancelRejectReason),
+    transact_time: id_u64(),
+} var_data: text);
+
+fixed_round_trip!(logon_request, LogonRequest, LogonRequestEncoder, {
+    target_comp_id: id_u32(),
+    source_comp_id: id_u32(),
+    transact_time: id_u64(),
+});
+
+fixed_round_trip!(logon_response, LogonResponse, LogonResponseEncoder, {
+    target_comp_id: id_u32(),
+    source_comp_id: id_u32(),
+    transact_time: id_u64(),
+});
+
+fixed_round_trip!(logout_request, LogoutRequest, LogoutRequestEncoder, {
+    target_comp_id: id_u32(),
+    source_comp_id: id_u32(),
+    transact_time: id_u64(),
+});
+
+fixed_round_trip!(logout_response, LogoutResponse, LogoutResponseEncoder, {
+    target_comp_id: id_u32(),
+    source_comp_id: id_u32(),
+    transact_time: id_u64(),
+});
+
+fixed_round_trip!(instrument_definition, InstrumentDefinition, InstrumentDefinitionEncoder, {
+    instrument_id: id_u32(),
+    tick_size: decimal(),
+    min_qty: decimal(),
+    max_qty: decimal(),
+    price_precision: any::<u8>(),
+    instrument_status: variants!(InstrumentStatus),
+    transact_time: id_u64(),
+} chars: {
+    symbol: any::<[u8; instrument_definition_codec::SYMBOL_LENGTH]>(),
+    base_currency: any::<[u8; instrument_definition_codec::CURRENCY_LENGTH]>(),
+    term_currency: any::<[u8; instrument_definition_codec::CURRENCY_LENGTH]>(),
+});
+
+fixed_round_trip!(market_data_request_reject, MarketDataRequestReject, MarketDataRequestRejectEncoder, {
+    md_req_id: id_u64(),
+    target_comp_id: id_u32(),
+    source_comp_id: id_u32(),
+    md_req_rej_reason: variants!(MarketDataRequestRejectReason),
+    transact_time: id_u64(),
+} var_data: text);
+
+fixed_round_trip!(market_data_request_ack, MarketDataRequestAck, MarketDataRequestAckEncoder, {
+    md_req_id: id_u64(),
+    target_comp_id: id_u32(),
+    source_comp_id: id_u32(),
+    subscription_request_type: variants!(SubscriptionRequestType),
+    transact_time: id_u64(),
+});
+
+fixed_round_trip!(order_status_request, OrderStatusRequest, OrderStatusRequestEncoder, {
+    ord_status_req_id: id_u64(),
+    cl_ord_id: id_u64(),
+    order_id: id_u64(),
+    instrument_id: id_u32(),
+    target_comp_id: id_u32(),
+    source_comp_id: id_u32(),
+    target_strategy: variants!(Strategy),
+    source_strategy: variants!(Strategy),
+    transact_time: id_u64(),
+});
+
+fixed_round_trip!(order_mass_status_request, OrderMassStatusRequest, OrderMassStatusRequestEncoder, {
+    mass_status_req_id: id_u64(),
+    mass_status_req_type: variants!(MassStatusReqType),
+    instrument_id: id_u32(),
+    strategy: variants!(Strategy),
+    target_comp_id: id_u32(),
+    source_comp_id: id_u32(),
+    target_strategy: variants!(Strategy),
+    source_strategy: variants!(Strategy),
+    transact_time: id_u64(),
+});
+
+fixed_round_trip!(order_mass_cancel_request, OrderMassCancelRequest, OrderMassCancelRequestEncoder, {
+    cl_ord_id: id_u64(),
+    mass_cancel_request_type: variants!(MassCancelRequestType),
+    instrument_id: id_u32(),
+    side: variants!(OrderSide),
+    strategy: variants!(Strategy),
+    target_comp_id: id_u32(),
+    source_comp_id: id_u32(),
+    target_strategy: variants!(Strategy),
+    source_strategy: variants!(Strategy),
+    transact_time: id_u64(),
+});
+
+fixed_round_trip!(order_mass_cancel_report, OrderMassCancelReport, OrderMassCancelReportEncoder, {
+    cl_ord_id: id_u64(),
+    mass_cancel_request_type: variants!(MassCancelRequestType),
+    mass_cancel_response: variants!(MassCancelResponse),
+    mass_cancel_reject_reason: variants!(MassCancelRejectReason),
+    total_affected_orders: id_u32(),
+    instrument_id: id_u32(),
+    side: variants!(OrderSide),
+    strategy: variants!(Strategy),
+    target_comp_id: id_u32(),
+    source_comp_id: id_u32(),
+    target_strategy: variants!(Strategy),
+    source_strategy: variants!(Strategy),
+    transact_time: id_u64(),
+} var_data: text);
+
+fixed_round_trip!(business_message_reject, BusinessMessageReject, BusinessMessageRejectEncoder, {
+    ref_template_id: any::<u16>(),
+    business_reject_ref_id: id_u64(),
+    business_reject_reason: variants!(BusinessRejectReason),
+    target_comp_id: id_u32(),
+    source_comp_id: id_u32(),
+    transact_time: id_u64(),
+} var_data: text);
+
+/// Round trip of a price message whose bid and offer groups are re-encoded from the entries
+/// decoded both by the group iterators and by the bulk `read_all_into`.
+macro_rules! group_round_trip {
+    ($test:ident, $variant:ident, $encoder:ident, $levels:expr) => {
+        proptest! {
+            #[test]
+            fn $test(
+                comp_id in id_u32(),
+                instrument_id in id_u32(),
+                md_req_id in id_u64(),
+                bids in $levels,
+                offers in $levels,
+            ) {
+                let mut first = vec![0_u8; BUFFER_LENGTH];
+                let mut encoder = wrap_encoder!($encoder, &mut first);
+                encoder.comp_id(comp_id);
+                encoder.instrument_id(instrument_id);
+                encoder.md_req_id(md_req_id);
+                prop_assert_eq!(encoder.encode_bids(&bids).unwrap(), bids.len());
+                prop_assert_eq!(encoder.encode_offers(&offers).unwrap(), offers.len());
+                let first_length = encoder.get_limit();
+
+                let mut decoder = decode!($variant, &first[..first_length]);
+                let decoded_bids: Vec<_> = decoder.bids().unwrap().collect();
+                let decoded_offers: Vec<_> = decoder.offers().unwrap().collect();
+                let mut second = vec![0_u8; BUFFER_LENGTH];
+                let mut encoder = wrap_encoder!($encoder, &mut second);
+                encoder.comp_id(decoder.comp_id());
+                encoder.instrument_id(decoder.instrument_id());
+                encoder.md_req_id(decoder.md_req_id());
+                encoder.encode_bids(&decoded_bids).unwrap();
+                encoder.encode_offers(&decoded_offers).unwrap();
+                let second_length = encoder.get_limit();
+
+                prop_assert_eq!(&first[..first_length], &second[..second_length]);
+
+                let decoder = decode!($variant, &first[..first_length]);
+                let mut group = decoder.bids_decoder();
+                let mut bulk_bids = vec![Default::default(); group.count() as usize];
+                prop_assert_eq!(group.read_all_into(&mut bulk_bids).unwrap(), bids.len());
+                let mut group = group.parent().unwrap().offers_decoder();
+                let mut bulk_offers = vec![Default::default(); group.count() as usize];
+                prop_assert_eq!(group.read_all_into(&mut bulk_offers).unwrap(), offers.len());
+                prop_assert_eq!(group.parent().unwrap().get_limit(), first_length);
+                let mut third = vec![0_u8; BUFFER_LENGTH];
+                let mut encoder = wrap_encoder!($encoder, &mut third);
+                encoder.comp_id(comp_id);
+                encoder.instrument_id(instrument_id);
+                encoder.md_req_id(md_req_id);
+                encoder.encode_bids(&bulk_bids).unwrap();
+                encoder.encode_offers(&bulk_offers).unwrap();
+                let third_length = encoder.get_limit();
+
+                prop_assert_eq!(&first[..first_length], &third[..third_length]);
+            }
+        }
+    };
+}
+
+group_round_trip!(price_snapshot, PriceSnapshot, PriceSnapshotEncoder, price_levels());
+group_round_trip!(price_increment, PriceIncrement, PriceIncrementEncoder, price_level_increments());
+
+proptest! {
+    #[test]
+    fn market_data_request(
+        md_req_id in id_u64(),
+        target_comp_id in id_u32(),
+        source_comp_id in id_u32(),
+        subscription_request_type in variants!(SubscriptionRequestType),
+        market_depth in any::<u16>(),
+        transact_time in id_u64(),
+        instrument_ids in vec(id_u32(), 0..=MAX_GROUP_COUNT),
+    ) {
+        let mut first = vec![0_u8; BUFFER_LENGTH];
+        let mut encoder = wrap_encoder!(MarketDataRequestEncoder, &mut first);
+        encoder.md_req_id(md_req_id);
+        encoder.target_comp_id(target_comp_id);
+        encoder.source_comp_id(source_comp_id);
+        encoder.subscription_request_type(subscription_request_type);
+        encoder.market_depth(market_depth);
+        encoder.transact_time(transact_time);
+        prop_assert_eq!(encoder.encode_instruments(instrument_ids.iter().copied()).unwrap(), instrument_ids.len());
+        let first_length = message_header_codec::ENCODED_LENGTH + encoder.finish().unwrap().0;
+
+        let mut decoder = decode!(MarketDataRequest, &first[..first_length]);
+        let mut second = vec![0_u8; BUFFER_LENGTH];
+        let mut encoder = wrap_encoder!(MarketDataRequestEncoder, &mut second);
+        encoder.md_req_id(decoder.md_req_id());
+        encoder.target_comp_id(decoder.target_comp_id());
+        encoder.source_comp_id(decoder.source_comp_id());
+        encoder.subscription_request_type(decoder.subscription_request_type());
+        encoder.market_depth(decoder.market_depth());
+        encoder.transact_time(decoder.transact_time());
+        let decoded: Vec<_> = decoder.instruments().unwrap().collect();
+        prop_assert_eq!(&decoded, &instrument_ids);
+        encoder.encode_instruments(decoded).unwrap();
+        let second_length = message_header_codec::ENCODED_LENGTH + encoder.finish().unwrap().0;
+
+        prop_assert_eq!(&first[..first_length], &second[..second_length]);
+    }
+}
+
+#[test]
+fn price_snapshot_before_md_req_id_decodes_with_null_md_req_id() {
+    let mut current = vec![0_u8; BUFFER_LENGTH];
+    let mut encoder = wrap_encoder!(PriceSnapshotEncoder, &mut current);
+    encoder.comp_id(7);
+    encoder.instrument_id(8);
+    encoder.md_req_id(9);
+    encoder.encode_bids([PriceLevel::default()]).unwrap();
+    encoder.encode_offers(std::iter::empty::<PriceLevel>()).unwrap();
+    let length = encoder.get_limit();
+
+    // the same message as a pre-mdReqId publisher encodes it: shorter block, older version
+    let block_start = message_header_codec::ENCODED_LENGTH;
+    let old_block_end = block_start + price_snapshot_codec::BLOCK_LENGTH_BEFORE_MD_REQ_ID as usize;
+    let mut old = current[..old_block_end].to_vec();
+    old.extend_from_slice(&current[block_start + price_snapshot_codec::SBE_BLOCK_LENGTH as usize..length]);
+    let mut header = MessageHeaderEncoder::<WriteBuf<'_>>::default().wrap(WriteBuf::new(&mut old), 0);
+    header.block_length(price_snapshot_codec::BLOCK_LENGTH_BEFORE_MD_REQ_ID);
+    header.version(price_snapshot_codec::MD_REQ_ID_SINCE_VERSION - 1);
+
+    let mut decoder = decode!(PriceSnapshot, &old);
+    assert_eq!((decoder.comp_id(), decoder.instrument_id()), (7, 8));
+    assert_eq!(decoder.md_req_id(), u64::MAX);
+    assert_eq!(decoder.bids().unwrap().len(), 1);
+    assert_eq!(decoder.offers().unwrap().len(), 0);
+}
+
+#[test]
+fn unsolicited_price_messages_carry_a_null_md_req_id() {
+    let mut buf = vec![0xa5_u8; BUFFER_LENGTH];
+    let mut encoder = wrap_encoder!(PriceSnapshotEncoder, &mut buf);
+    encoder.comp_id(7);
+    encoder.instrument_id(8);
+    let length = message_header_codec::ENCODED_LENGTH + encoder.finish().unwrap().0;
+    assert_eq!(decode!(PriceSnapshot, &buf[..length]).md_req_id(), u64::MAX);
+
+    let mut buf = vec![0xa5_u8; BUFFER_LENGTH];
+    let mut writer = MessageWriter::new(&mut buf[..]);
+    let increment = writer
+        .price_increment(|increment| {
+            increment.comp_id(7);
+            increment.instrument_id(8);
+            let _ = increment.encode_bids(&[] as &[PriceLevelIncrement]);
+            let _ = increment.encode_offers(&[] as &[PriceLevelIncrement]);
+        })
+        .unwrap();
+    assert_eq!(decode!(PriceIncrement, increment).md_req_id(), u64::MAX);
+}
+
+#[test]
+fn finish_reports_the_first_required_field_left_unset() {
+    let mut buf = vec![0_u8; BUFFER_LENGTH];
+    let mut encoder = wrap_encoder!(NewOrderSingleEncoder, &mut buf);
+    encoder.cl_ord_id(1);
+    encoder.cl_ord_link_id(u64::MAX);
+    encoder.instrument_id(1);
+    encoder.target_comp_id(2);
+    encoder.source_comp_id(3);
+    encoder.order_type(OrderType::LIMIT);
+    encoder.price(1.1);
+    encoder.order_qty(1_000.0);
+    encoder.time_in_force(TimeInForce::GTC);
+    encoder.transact_time(1);
+    encoder.effective_time(u64::MAX);
+    encoder.expire_time(u64::MAX);
+    encoder.target_strategy(Strategy::VENUE);
+    encoder.source_strategy(Strategy::TWAP);
+    assert_eq!(encoder.finish().unwrap_err(), SbeErr::MissingRequiredField("side"));
+}
+
+#[test]
+fn finish_leaves_fields_added_in_later_schema_versions_null() {
+    let mut buf = vec![0xa5_u8; BUFFER_LENGTH];
+    let mut encoder = wrap_encoder!(ExecutionReportEncoder, &mut buf);
+    encoder.cl_ord_id(1);
+    encoder.cl_ord_link_id(u64::MAX);
+    encoder.instrument_id(1);
+    encoder.target_comp_id(2);
+    encoder.source_comp_id(3);
+    encoder.side(OrderSide::BUY);
+    encoder.order_type(OrderType::LIMIT);
+    encoder.price(1.1);
+    encoder.order_qty(1_000.0);
+    encoder.time_in_force(TimeInForce::GTC);
+    encoder.transact_time(1);
+    encoder.effective_time(u64::MAX);
+    encoder.expire_time(u64::MAX);
+    encoder.target_strategy(Strategy::TWAP);
+    encoder.source_strategy(Strategy::VENUE);
+    encoder.orig_cl_ord_id(u64::MAX);
+    encoder.exec_id(1);
+    encoder.order_id(1);
+    encoder.exec_type(ExecType::NEW);
+    encoder.order_status(OrderStatus::NEW);
+    encoder.order_rej_reason(OrderRejectReason::NullVal);
+    encoder.last_qty(f64::NAN);
+    encoder.last_price(f64::NAN);
+    encoder.leaves_qty(1_000.0);
+    encoder.cum_qty(0.0);
+    encoder.avg_price(f64::NAN);
+    let length = message_header_codec::ENCODED_LENGTH + encoder.finish().unwrap().0;
+
+    let mut decoder = decode!(ExecutionReport, &buf[..length]);
+    assert_eq!((decoder.ord_status_req_id(), decoder.mass_status_req_id()), (u64::MAX, u64::MAX));
+    assert_eq!(decoder.last_rpt_requested(), BooleanType::NullVal);
+    assert_eq!(decoder.text(), Ok(&b""[..]));
+}
+
+#[test]
+fn text_can_only_be_written_once() {
+    let mut buf = vec![0_u8; BUFFER_LENGTH];
+    let mut encoder = wrap_encoder!(BusinessMessageRejectEncoder, &mut buf);
+    encoder.business_reject_ref_id(1);
+    encoder.target_comp_id(2);
+    encoder.source_comp_id(3);
+    encoder.transact_time(4);
+    assert_eq!(encoder.reject_for(&[], &SbeErr::BufferTooShort), Ok(()));
+    assert_eq!(encoder.text(b"appended"), Err(SbeErr::DuplicateField("text")));
+    let length = message_header_codec::ENCODED_LENGTH + encoder.finish().unwrap().0;
+
+    let mut decoder = decode!(BusinessMessageReject, &buf[..length]);
+    assert_eq!(decoder.text(), Ok(SbeErr::BufferTooShort.business_reject().1.as_bytes()));
+    assert_eq!(decoder.encoded_length(), length - message_header_codec::ENCODED_LENGTH);
+}
+
+#[test]
+fn groups_that_do_not_fit_leave_the_encoder_usable() {
+    let level = PriceLevelIncrement::default();
+    let block_end = message_header_codec::ENCODED_LENGTH + price_increment_codec::SBE_BLOCK_LENGTH as usize;
+    let group_length = group_size_encoding_codec::ENCODED_LENGTH + price_level_increment_codec::ENCODED_LENGTH;
+    let mut buf = vec![0_u8; block_end + 2 * group_length];
+    let mut encoder = wrap_encoder!(PriceIncrementEncoder, &mut buf);
+    encoder.comp_id(2);
+    encoder.instrument_id(1);
+
+    assert_eq!(encoder.encode_bids([level, level, level]), Err(SbeErr::BufferTooShort));
+    assert_eq!(encoder.encode_bids([level]), Ok(1));
+    assert_eq!(encoder.encode_offers([level]), Ok(1));
+    let length = message_header_codec::ENCODED_LENGTH + encoder.finish().unwrap().0;
+    assert_eq!(length, buf.len());
+    let mut decoder = decode!(PriceIncrement, &buf);
+    assert_eq!((decoder.bids().unwrap().len(), decoder.offers().unwrap().len()), (1, 1));
+}
+
+#[test]
+fn price_groups_hold_more_entries_than_small_group_headers_can_count() {
+    let bids: Vec<_> = (0..1_000).map(|id| PriceLevel { id, price: 1.1, leaves_qty: 1.0, transact_time: 1 }).collect();
+    let increments: Vec<_> = bids
+        .iter()
+        .map(|&price_level| PriceLevelIncrement { price_level, update_action: UpdateAction::NEW })
+        .collect();
+    let mut buf = vec![0_u8; 2 * BUFFER_LENGTH];
+
+    let mut encoder = wrap_encoder!(PriceSnapshotEncoder, &mut buf);
+    encoder.comp_id(2);
+    encoder.instrument_id(1);
+    assert_eq!(encoder.encode_bids(&bids), Ok(1_000));
+    assert_eq!(encoder.encode_offers(&bids[..300]), Ok(300));
+    let length = message_header_codec::ENCODED_LENGTH + encoder.finish().unwrap().0;
+    let mut decoder = decode!(PriceSnapshot, &buf[..length]);
+    assert_eq!(decoder.bids().unwrap().collect::<Vec<_>>(), bids);
+    assert_eq!(decoder.offers().unwrap().collect::<Vec<_>>(), &bids[..300]);
+
+    let mut encoder = wrap_encoder!(PriceIncrementEncoder, &mut buf);
+    encoder.comp_id(2);
+    encoder.instrument_id(1);
+    assert_eq!(encoder.encode_bids(&increments[..300]), Ok(300));
+    assert_eq!(encoder.encode_offers(&increments), Ok(1_000));
+    let length = message_header_codec::ENCODED_LENGTH + encoder.finish().unwrap().0;
+    let mut decoder = decode!(PriceIncrement, &buf[..length]);
+    assert_eq!(decoder.bids().unwrap().collect::<Vec<_>>(), &increments[..300]);
+    assert_eq!(decoder.offers().unwrap().collect::<Vec<_>>(), increments);
+}
+
+#[test]
+fn group_decoders_report_groups_cut_short_instead_of_reading_past_the_buffer() {
+    let level = PriceLevel { id: 1, price: 1.1, leaves_qty: 1.0, transact_time: 1 };
+    let mut buf = vec![0_u8; BUFFER_LENGTH];
+    let mut encoder = wrap_encoder!(PriceSnapshotEncoder, &mut buf);
+    encoder.comp_id(2);
+    encoder.instrument_id(1);
+    encoder.encode_bids([level, level]).unwrap();
+    encoder.encode_offers([level]).unwrap();
+    let length = message_header_codec::ENCODED_LENGTH + encoder.finish().unwrap().0;
+    let block_end = message_header_codec::ENCODED_LENGTH + price_snapshot_codec::SBE_BLOCK_LENGTH as usize;
+
+    for cut in block_end..=length {
+        let header = MessageHeaderDecoder::default().wrap(ReadBuf::new(&buf[..cut]), 0);
+        let mut bids = PriceSnapshotDecoder::default().header(header).bids_decoder();
+        let mut read = Vec::new();
+        let result = loop {
+            match bids.advance() {
+                Ok(Some(_)) => {
+                    let mut entry = bids.bid_decoder();
+                    read.push(entry.to_price_level());
+                    bids = entry.parent().unwrap();
+                }
+                Ok(None) => {
+                    let mut offers = bids.parent().unwrap().offers_decoder();
+                    let mut levels = [PriceLevel::default(); 2];
+                    break offers.read_all_into(&mut levels).map(|count| read.extend(&levels[..count]));
+                }
+                Err(err) => break Err(err),
+            }
+        };
+        if cut == length {
+            assert_eq!((result, read), (Ok(()), vec![level; 3]));
+        } else {
+            assert_eq!(result, Err(SbeErr::BufferTooShort), "cut at {}", cut);
+        }
+    }
+}
+
+/// Rewrites a current price message with two groups of `entry_length` entries as a version 0
+/// publisher encodes it: a `block_length` block without `mdReqId` and `SmallGroupSizeEncoding`
+/// group headers.
+fn as_version_0(current: &[u8], block_length: u16, entry_length: usize) -> Vec<u8> {
+    let header_length = message_header_codec::ENCODED_LENGTH;
+    let current_block_length = MessageHeaderDecoder::default().wrap(ReadBuf::new(current), 0).block_length();
+    let mut old = current[..header_length + block_length as usize].to_vec();
+    let mut offset = header_length + current_block_length as usize;
+    for _ in 0..2 {
+        let group = GroupSizeEncodingDecoder::default().wrap(ReadBuf::new(current), offset);
+        assert_eq!(group.block_length() as usize, entry_length);
+        let count = group.num_in_group() as usize;
+        old.extend_from_slice(&[entry_length as u8, count as u8]);
+        offset += group_size_encoding_codec::ENCODED_LENGTH;
+        old.extend_from_slice(&current[offset..offset + count * entry_length]);
+        offset += count * entry_length;
+    }
+    assert_eq!(offset, current.len());
+    let mut header = MessageHeaderEncoder::<WriteBuf<'_>>::default().wrap(WriteBuf::new(&mut old), 0);
+    header.block_length(block_length);
+    header.version(0);
+    old
+}
+
+#[test]
+fn version_0_price_messages_decode_with_small_group_headers() {
+    let level = |id| PriceLevel { id, price: 1.1, leaves_qty: 1.0, transact_time: 1 };
+    let increment = |id| PriceLevelIncrement { price_level: level(id), update_action: UpdateAction::DELETE };
+    let mut buf = vec![0_u8; BUFFER_LENGTH];
+
+    let mut encoder = wrap_encoder!(PriceSnapshotEncoder, &mut buf);
+    encoder.comp_id(7);
+    encoder.instrument_id(8);
+    encoder.md_req_id(9);
+    encoder.encode_bids([level(1), level(2)]).unwrap();
+    encoder.encode_offers([level(3)]).unwrap();
+    let length = encoder.get_limit();
+    let block_length = price_snapshot_codec::BLOCK_LENGTH_BEFORE_MD_REQ_ID;
+    let old = as_version_0(&buf[..length], block_length, price_level_codec::ENCODED_LENGTH);
+    assert_eq!(old.len(), length - 8 - 2 * 2);
+    let mut decoder = decode!(PriceSnapshot, &old);
+    assert_eq!((decoder.comp_id(), decoder.instrument_id(), decoder.md_req_id()), (7, 8, u64::MAX));
+    assert_eq!(decoder.bids().unwrap().collect::<Vec<_>>(), vec![level(1), level(2)]);
+    assert_eq!(decoder.offers().unwrap().collect::<Vec<_>>(), vec![level(3)]);
+    assert_eq!(AnyMessage::decode(&old, 0).unwrap().end_offset(), Ok(old.len()));
+
+    let mut encoder = wrap_encoder!(PriceIncrementEncoder, &mut buf);
+    encoder.comp_id(7);
+    encoder.instrument_id(8);
+    encoder.md_req_id(9);
+    encoder.encode_bids(std::iter::empty::<PriceLevelIncrement>()).unwrap();
+    encoder.encode_offers([increment(4), increment(5)]).unwrap();
+    let length = encoder.get_limit();
+    let old = as_version_0(
+        &buf[..length],
+        price_increment_codec::BLOCK_LENGTH_BEFORE_MD_REQ_ID,
+        price_level_increment_codec::ENCODED_LENGTH,
+    );
+    let mut decoder = decode!(PriceIncrement, &old);
+    assert_eq!((decoder.comp_id(), decoder.instrument_id(), decoder.md_req_id()), (7, 8, u64::MAX));
+    assert_eq!(decoder.bids().unwrap().len(), 0);
+    assert_eq!(decoder.offers().unwrap().collect::<Vec<_>>(), vec![increment(4), increment(5)]);
+    assert_eq!(AnyMessage::decode(&old, 0).unwrap().end_offset(), Ok(old.len()));
+}
+
+macro_rules! read_fields {
+    ($decoder:expr, $($field:ident),+) => {
+        {
+            $(let _ = $decoder.$field();)+
+        }
+    };
+}
+
+/// Reads every field reachable through the checked decoding API.
+fn read_all(message: AnyMessage<'_>) {
+    match message {
+        AnyMessage::PriceIncrement(mut decoder) => {
+            read_fields!(decoder, comp_id, instrument_id, md_req_id);
+            if let Ok(bids) = decoder.bids() {
+                bids.for_each(drop);
+                if let Ok(offers) = decoder.offers() {
+                    offers.for_each(drop);
+                }
+            }
+        }
+        AnyMessage::PriceSnapshot(mut decoder) => {
+            read_fields!(decoder, comp_id, instrument_id, md_req_id);
+            if let Ok(bids) = decoder.bids() {
+                bids.for_each(drop);
+                if let Ok(offers) = decoder.offers() {
+                    offers.for_each(drop);
+                }
+            }
+        }
+        AnyMessage::PriceSnapshotRequest(decoder) => {
+            read_fields!(decoder, target_comp_id, source_comp_id, instrument_id, transact_time);
+        }
+        AnyMessage::LastMarketTrade(decoder) => {
+            read_fields!(decoder, comp_id, instrument_id, quantity, price, transact_time);
+        }
+        AnyMessage::NewOrderSingle(decoder) => {
+            read_fields!(
+                decoder, cl_ord_id, cl_ord_link_id, instrument_id, target_comp_id, source_comp_id, side,
+                order_type, price, order_qty, time_in_force, transact_time, effective_time, expire_time,
+                target_strategy, source_strategy
+            );
+        }
+        AnyMessage::ExecutionReport(mut decoder) => {
+            read_fields!(
+                decoder, cl_ord_id, cl_ord_link_id, instrument_id, target_comp_id, source_comp_id, side,
+                order_type, price, order_qty, time_in_force, transact_time, effective_time, expire_time,
+                target_strategy, source_strategy, orig_cl_ord_id, exec_id, order_id, exec_type, order_status,
+                order_rej_reason, last_qty, last_price, leaves_qty, cum_qty, avg_price, ord_status_req_id,
+                mass_status_req_id, last_rpt_requested, text
+            );
+        }
+        AnyMessage::OrderCancelRequest(decoder) => {
+            read_fields!(
+                decoder, orig_cl_ord_id, order_id, cl_ord_id, instrument_id, target_comp_id, source_comp_id,
+                target_strategy, source_strategy, side, price, transact_time
+            );
+        }
+        AnyMessage::OrderCancelReject(mut decoder) => {
+            read_fields!(
+                decoder, orig_cl_ord_id, order_id, cl_ord_id, order_status, instrument_id, target_comp_id,
+                source_comp_id, target_strategy, source_strategy, cancel_rej_response_to, cancel_rej_reason,
+                transact_time, text
+            );
+        }
+        AnyMessage::LogonRequest(decoder) => read_fields!(decoder, target_comp_id, source_comp_id, transact_time),
+        AnyMessage::LogonResponse(decoder) => read_fields!(decoder, target_comp_id, source_comp_id, transact_time),
+        AnyMessage::LogoutRequest(decoder) => read_fields!(decoder, target_comp_id, source_comp_id, transact_time),
+        AnyMessage::LogoutResponse(decoder) => read_fields!(decoder, target_comp_id, source_comp_id, transact_time),
+        AnyMessage::InstrumentDefinition(decoder) => {
+            read_fields!(
+                decoder, instrument_id, symbol, base_currency, term_currency, tick_size, min_qty, max_qty,
+                price_precision, instrument_status, transact_time
+            );
+        }
+        AnyMessage::MarketDataRequest(mut decoder) => {
+            read_fields!(
+                decoder, md_req_id, target_comp_id, source_comp_id, subscription_request_type, market_depth,
+                transact_time
+            );
+            if let Ok(instruments) = decoder.instruments() {
+                instruments.for_each(drop);
+            }
+        }
+        AnyMessage::MarketDataRequestReject(mut decoder) => {
+            read_fields!(decoder, md_req_id, target_comp_id, source_comp_id, md_req_rej_reason, transact_time, text);
+        }
+        AnyMessage::MarketDataRequestAck(decoder) => {
+            read_fields!(decoder, md_req_id, target_comp_id, source_comp_id, subscription_request_type, transact_time);
+        }
+        AnyMessage::OrderStatusRequest(decoder) => {
+            read_fields!(
+                decoder, ord_status_req_id, cl_ord_id, order_id, instrument_id, target_comp_id, source_comp_id,
+                target_strategy, source_strategy, transact_time
+            );
+        }
+        AnyMessage::OrderMassStatusRequest(decoder) => {
+            read_fields!(
+                decoder, mass_status_req_id, mass_status_req_type, instrument_id, strategy, target_comp_id,
+                source_comp_id, target_strategy, source_strategy, transact_time
+            );
+        }
+        AnyMessage::OrderMassCancelRequest(decoder) => {
+            read_fields!(
+                decoder, cl_ord_id, mass_cancel_request_type, instrument_id, side, strategy, target_comp_id,
+                source_comp_id, target_strategy, source_strategy, transact_time
+            );
+        }
+        AnyMessage::OrderMassCancelReport(mut decoder) => {
+            read_fields!(
+                decoder, cl_ord_id, mass_cancel_request_type, mass_cancel_response, mass_cancel_reject_reason,
+                total_affected_orders, instrument_id, side, strategy, target_comp_id, source_comp_id,
+                target_strategy, source_strategy, transact_time, text
+            );
+        }
+        AnyMessage::BusinessMessageReject(mut decoder) => {
+            read_fields!(
+                decoder, ref_template_id, business_reject_ref_id, business_reject_reason, target_comp_id,
+                source_comp_id, transact_time, text
+            );
+        }
+    }
+}
+
+fn template_id() -> impl Generator<Value = u16> {
+    prop_oneof![known_template_id(), any::<u16>()]
+}
+
+fn known_template_id() -> impl Generator<Value = u16> {
+    select(vec![
+        price_increment_codec::SBE_TEMPLATE_ID,
+        price_snapshot_codec::SBE_TEMPLATE_ID,
+        price_snapshot_request_codec::SBE_TEMPLATE_ID,
+        last_market_trade_codec::SBE_TEMPLATE_ID,
+        new_order_single_codec::SBE_TEMPLATE_ID,
+        execution_report_codec::SBE_TEMPLATE_ID,
+        order_cancel_request_codec::SBE_TEMPLATE_ID,
+        order_cancel_reject_codec::SBE_TEMPLATE_ID,
+        logon_request_codec::SBE_TEMPLATE_ID,
+        logon_response_codec::SBE_TEMPLATE_ID,
+        logout_request_codec::SBE_TEMPLATE_ID,
+        logout_response_codec::SBE_TEMPLATE_ID,
+        instrument_definition_codec::SBE_TEMPLATE_ID,
+        market_data_request_codec::SBE_TEMPLATE_ID,
+        market_data_request_reject_codec::SBE_TEMPLATE_ID,
+        market_data_request_ack_codec::SBE_TEMPLATE_ID,
+        order_status_request_codec::SBE_TEMPLATE_ID,
+        order_mass_status_request_codec::SBE_TEMPLATE_ID,
+        order_mass_cancel_request_codec::SBE_TEMPLATE_ID,
+        order_mass_cancel_report_codec::SBE_TEMPLATE_ID,
+        business_message_reject_codec::SBE_TEMPLATE_ID,
+    ])
+}
+
+proptest! {
+    #[test]
+    fn decode_arbitrary_bytes(bytes in vec(any::<u8>(), 0..512), offset in 0_usize..16) {
+        if let Ok(message) = AnyMessage::decode(&bytes, offset) {
+            read_all(message);
+        }
+    }
+
+    #[test]
+    fn decode_arbitrary_body(
+        block_length in prop_oneof![0_u16..160, any::<u16>()],
+        template_id in template_id(),
+        version in prop_oneof![0_u16..5, any::<u16>()],
+        body in vec(any::<u8>(), 0..1024),
+    ) {
+        let mut bytes = vec![0_u8; message_header_codec::ENCODED_LENGTH];
+        let mut header = MessageHeaderEncoder::<WriteBuf<'_>>::default().wrap(WriteBuf::new(&mut bytes), 0);
+        header.block_length(block_length);
+        header.template_id(template_id);
+        header.schema_id(new_order_single_codec::SBE_SCHEMA_ID);
+        header.version(version);
+        bytes.extend_from_slice(&body);
+        if let Ok(message) = AnyMessage::decode(&bytes, 0) {
+            read_all(message);
+        }
+    }
+
+    #[test]
+    fn block_lengths_below_the_minimum_are_rejected_without_waiting_for_the_body(
+        template_id in known_template_id(),
+        version in 0_u16..5,
+        shortfall in 1_u16..=8,
+    ) {
+        let header = |block_length: u16| {
+            let mut bytes = vec![0_u8; message_header_codec::ENCODED_LENGTH];
+            let mut header = MessageHeaderEncoder::<WriteBuf<'_>>::default().wrap(WriteBuf::new(&mut bytes), 0);
+            header.block_length(block_length);
+            header.template_id(template_id);
+            header.schema_id(new_order_single_codec::SBE_SCHEMA_ID);
+            header.version(version);
+            bytes
+        };
+        let mut full = header(u16::MAX);
+        full.resize(full.len() + u16::MAX as usize, 0);
+        let min_block_length = AnyMessage::decode(&full, 0).unwrap().min_block_length();
+        prop_assume!(min_block_length >= shortfall);
+        let short = header(min_block_length - shortfall);
+        prop_assert_eq!(AnyMessage::decode(&short, 0).unwrap_err(), SbeErr::BlockLengthTooShort);
+        prop_assert_eq!(AnyMessage::decode(&header(min_block_length), 0).unwrap_err(), SbeErr::BufferTooShort);
+    }
+}
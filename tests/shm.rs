@@ -0,0 +1,286 @@
+use com_dev_4fx_sor_codecs_sbe::transport::shm::{RingPublisher, RingSubscriber};
+use com_dev_4fx_sor_codecs_sbe::*;
+
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use std::process::{self, Command, Stdio};
+use std::time::Duration;
+use std::{env, fs, thread};
+
+/// Ring path handed to the [`publisher_process`] child.
+const PUBLISHER_RING_ENV: &str = "SHM_TEST_PUBLISHER_RING";
+/// Ring path handed to the [`abandoning_publisher_process`] child.
+const ABANDONING_RING_ENV: &str = "SHM_TEST_ABANDONING_RING";
+const MESSAGES: u64 = 1_000;
+const MAX_MESSAGE_LENGTH: usize = 256;
+
+fn ring_path(name: &str) -> PathBuf {
+    let shm = PathBuf::from("/dev/shm");
+    let dir = if shm.is_dir() { shm } else { env::temp_dir() };
+    dir.join(format!("sbe-{}-{}.ring", name, std::process::id()))
+}
+
+fn new_order_single<'w>(writer: &'w mut MessageWriter<&mut [u8]>, cl_ord_id: u64) -> SbeResult<&'w [u8]> {
+    writer.new_order_single(|order| {
+        order.cl_ord_id(cl_ord_id);
+        order.cl_ord_link_id(u64::MAX);
+        order.instrument_id(1);
+        order.target_comp_id(2);
+        order.source_comp_id(3);
+        order.side(OrderSide::BUY);
+        order.order_type(OrderType::LIMIT);
+        order.price(1.1);
+        order.order_qty(1_000.0);
+        order.time_in_force(TimeInForce::GTC);
+        order.transact_time(1);
+        order.effective_time(u64::MAX);
+        order.expire_time(u64::MAX);
+        order.target_strategy(Strategy::VENUE);
+        order.source_strategy(Strategy::TWAP);
+    })
+}
+
+/// Publishes `cl_ord_id`, reporting whether the ring was back-pressured instead.
+fn publish(publisher: &mut RingPublisher, cl_ord_id: u64) -> bool {
+    match publisher.try_claim(MAX_MESSAGE_LENGTH).unwrap() {
+        Some(mut claim) => {
+            new_order_single(claim.writer(), cl_ord_id).unwrap();
+            claim.commit().unwrap();
+            true
+        }
+        None => false,
+    }
+}
+
+fn cl_ord_ids(subscriber: &mut RingSubscriber) -> Vec<u64> {
+    let mut cl_ord_ids = Vec::new();
+    subscriber
+        .poll(|record| {
+            if let Ok(AnyMessage::NewOrderSingle(order)) = record.message() {
+                cl_ord_ids.push(order.cl_ord_id());
+            }
+        })
+        .unwrap();
+    cl_ord_ids
+}
+
+/// Child process of `messages_cross_from_a_publisher_process_in_order`; does nothing when run
+/// by the test harness itself.
+#[test]
+fn publisher_process() {
+    let path = match env::var_os(PUBLISHER_RING_ENV) {
+        Some(path) => path,
+        None => return,
+    };
+    let mut publisher = RingPublisher::open(path).unwrap();
+    for cl_ord_id in 0..MESSAGES {
+        while !publish(&mut publisher, cl_ord_id) {
+            thread::yield_now();
+        }
+    }
+}
+
+#[test]
+fn messages_cross_from_a_publisher_process_in_order() {
+    let path = ring_path("cross-process");
+    RingPublisher::create(&path, 1024).unwrap();
+    let mut consumer = RingSubscriber::open(&path).unwrap();
+    let mut child = Command::new(env::current_exe().unwrap())
+        .args(["publisher_process", "--exact"])
+        .env(PUBLISHER_RING_ENV, &path)
+        .stdout(Stdio::null())
+        .spawn()
+        .unwrap();
+
+    let mut received = Vec::new();
+    while (received.len() as u64) < MESSAGES {
+        let polled = cl_ord_ids(&mut consumer);
+        if polled.is_empty() {
+            if let Some(status) = child.try_wait().unwrap() {
+                assert!(status.success(), "publisher process failed");
+            }
+            thread::yield_now();
+        }
+        received.extend(polled);
+    }
+
+    assert!(child.wait().unwrap().success());
+    assert_eq!(received, (0..MESSAGES).collect::<Vec<_>>());
+    fs::remove_file(path).unwrap();
+}
+
+/// Child process of `claims_abandoned_by_a_dead_publisher_are_aborted_after_a_timeout`: publishes
+/// one message, then exits in the middle of encoding the next, leaving its claim pending.
+#[test]
+fn abandoning_publisher_process() {
+    let path = match env::var_os(ABANDONING_RING_ENV) {
+        Some(path) => path,
+        None => return,
+    };
+    let mut publisher = RingPublisher::open(path).unwrap();
+    assert!(publish(&mut publisher, 0));
+    let mut claim = publisher.try_claim(MAX_MESSAGE_LENGTH).unwrap().unwrap();
+    new_order_single(claim.writer(), 1).unwrap();
+    process::exit(0);
+}
+
+#[test]
+fn claims_abandoned_by_a_dead_publisher_are_aborted_after_a_timeout() {
+    let path = ring_path("abandoned");
+    let mut publisher = RingPublisher::create(&path, 1024).unwrap();
+    let mut consumer = RingSubscriber::open(&path).unwrap();
+    let status = Command::new(env::current_exe().unwrap())
+        .args(["abandoning_publisher_process", "--exact"])
+        .env(ABANDONING_RING_ENV, &path)
+        .stdout(Stdio::null())
+        .status()
+        .unwrap();
+    assert!(status.success());
+    assert!(publish(&mut publisher, 2));
+
+    assert_eq!(cl_ord_ids(&mut consumer), vec![0]);
+    assert_eq!(cl_ord_ids(&mut consumer), Vec::<u64>::new(), "the dead claim blocks the consumer");
+    let stalled = consumer.position();
+    assert_eq!(consumer.abort_stalled_claim(Duration::from_millis(20)).unwrap(), None);
+    thread::sleep(Duration::from_millis(20));
+    assert_eq!(consumer.abort_stalled_claim(Duration::from_millis(20)).unwrap(), Some(stalled));
+    assert_eq!(cl_ord_ids(&mut consumer), vec![2]);
+    assert_eq!(consumer.abort_stalled_claim(Duration::ZERO).unwrap(), None);
+
+    let mut tail = RingSubscriber::tail(&path).unwrap();
+    assert_eq!(tail.abort_stalled_claim(Duration::ZERO).unwrap_err().kind(), ErrorKind::InvalidInput);
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn claims_aborted_by_the_consumer_publish_nothing() {
+    let path = ring_path("aborted");
+    let mut publisher = RingPublisher::create(&path, 1024).unwrap();
+    let mut consumer = RingSubscriber::open(&path).unwrap();
+    let mut tail = RingSubscriber::tail(&path).unwrap();
+
+    let mut claim = publisher.try_claim(MAX_MESSAGE_LENGTH).unwrap().unwrap();
+    new_order_single(claim.writer(), 0).unwrap();
+    assert_eq!(consumer.abort_stalled_claim(Duration::ZERO).unwrap(), Some(0));
+    assert_eq!(claim.commit().unwrap_err().kind(), ErrorKind::TimedOut);
+    assert!(publish(&mut publisher, 1));
+    assert_eq!(cl_ord_ids(&mut tail), vec![1]);
+    assert_eq!(cl_ord_ids(&mut consumer), vec![1]);
+
+    // A claim committed before the timeout elapses is not aborted.
+    let mut claim = publisher.try_claim(MAX_MESSAGE_LENGTH).unwrap().unwrap();
+    assert_eq!(consumer.abort_stalled_claim(Duration::from_secs(60)).unwrap(), None);
+    new_order_single(claim.writer(), 2).unwrap();
+    claim.commit().unwrap();
+    assert_eq!(consumer.abort_stalled_claim(Duration::ZERO).unwrap(), None);
+    assert_eq!(cl_ord_ids(&mut consumer), vec![2]);
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn claims_are_back_pressured_until_the_consumer_frees_space() {
+    let path = ring_path("back-pressure");
+    let mut publisher = RingPublisher::create(&path, 1024).unwrap();
+    let mut consumer = RingSubscriber::open(&path).unwrap();
+    let mut tail = RingSubscriber::tail(&path).unwrap();
+
+    drop(publisher.try_claim(MAX_MESSAGE_LENGTH).unwrap());
+    let mut published = 0;
+    while publish(&mut publisher, published) {
+        published += 1;
+    }
+    assert!(published > 0);
+    assert_eq!(cl_ord_ids(&mut tail), (0..published).collect::<Vec<_>>());
+    assert!(!publish(&mut publisher, published), "tailing does not free space");
+
+    assert_eq!(cl_ord_ids(&mut consumer), (0..published).collect::<Vec<_>>());
+    assert!(publish(&mut publisher, published));
+    assert_eq!(cl_ord_ids(&mut tail), vec![published]);
+    assert_eq!(cl_ord_ids(&mut consumer), vec![published]);
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn records_of_varying_length_wrap_over_many_laps() {
+    let path = ring_path("wrap");
+    let mut publisher = RingPublisher::create(&path, 1024).unwrap();
+    let mut consumer = RingSubscriber::open(&path).unwrap();
+    let mut tail = RingSubscriber::tail(&path).unwrap();
+
+    let mut published = Vec::new();
+    let mut tailed = Vec::new();
+    let mut consumed = Vec::new();
+    for cl_ord_id in 0..200 {
+        // Claims of changing length move record boundaries between laps; some are abandoned.
+        let max_length = [96, 300, 128, 512][cl_ord_id as usize % 4];
+        let mut claim = publisher.try_claim(max_length).unwrap().expect("consumer keeps up");
+        if cl_ord_id % 7 == 3 {
+            drop(claim);
+        } else {
+            new_order_single(claim.writer(), cl_ord_id).unwrap();
+            claim.commit().unwrap();
+            published.push(cl_ord_id);
+        }
+        tailed.extend(cl_ord_ids(&mut tail));
+        consumed.extend(cl_ord_ids(&mut consumer));
+    }
+    assert!(consumer.position() > 10 * publisher.capacity());
+    assert_eq!(consumed, published);
+    assert_eq!(tailed, published);
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn tails_that_fall_behind_the_consumer_skip_ahead() {
+    let path = ring_path("skip-ahead");
+    let mut publisher = RingPublisher::create(&path, 1024).unwrap();
+    let mut consumer = RingSubscriber::open(&path).unwrap();
+    let mut tail = RingSubscriber::tail(&path).unwrap();
+
+    for cl_ord_id in 0..10 {
+        assert!(publish(&mut publisher, cl_ord_id));
+        assert_eq!(cl_ord_ids(&mut consumer), vec![cl_ord_id]);
+    }
+    assert!(publish(&mut publisher, 10));
+    assert_eq!(cl_ord_ids(&mut tail), vec![10]);
+    assert_eq!(tail.position(), consumer.position() + 16 + MAX_MESSAGE_LENGTH as u64);
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn concurrent_publishers_each_keep_their_order() {
+    const PUBLISHERS: u64 = 4;
+    let path = ring_path("concurrent");
+    RingPublisher::create(&path, 1024).unwrap();
+    let mut consumer = RingSubscriber::open(&path).unwrap();
+    let publishers: Vec<_> = (0..PUBLISHERS)
+        .map(|publisher| {
+            let mut publisher_ring = RingPublisher::open(&path).unwrap();
+            thread::spawn(move || {
+                for cl_ord_id in publisher * MESSAGES..(publisher + 1) * MESSAGES {
+                    while !publish(&mut publisher_ring, cl_ord_id) {
+                        thread::yield_now();
+                    }
+                }
+            })
+        })
+        .collect();
+
+    let mut received = Vec::new();
+    while (received.len() as u64) < PUBLISHERS * MESSAGES {
+        let polled = cl_ord_ids(&mut consumer);
+        if polled.is_empty() {
+            thread::yield_now();
+        }
+        received.extend(polled);
+    }
+    for publisher in publishers {
+        publisher.join().unwrap();
+    }
+    for publisher in 0..PUBLISHERS {
+        let ids = publisher * MESSAGES..(publisher + 1) * MESSAGES;
+        let own: Vec<_> = received.iter().copied().filter(|cl_ord_id| ids.contains(cl_ord_id)).collect();
+        assert_eq!(own, ids.collect::<Vec<_>>());
+    }
+    fs::remove_file(path).unwrap();
+}
@@ -0,0 +1,542 @@
+use com_dev_4fx_sor_codecs_sbe::simulator::VenueSimulator;
+use com_dev_4fx_sor_codecs_sbe::*;
+
+/// `(clOrdId, execType, orderStatus, ordStatusReqId, massStatusReqId, lastRptRequested)`
+type Report = (u64, ExecType, OrderStatus, u64, u64, BooleanType);
+/// `(clOrdId, massCancelResponse, massCancelRejectReason, totalAffectedOrders)`
+type MassCancelReport = (u64, MassCancelResponse, MassCancelRejectReason, u32);
+
+fn handle(simulator: &mut VenueSimulator, message: &[u8]) -> Vec<Report> {
+    handle_with_mass_cancel_reports(simulator, message).0
+}
+
+/// Execution reports and mass cancel reports; the latter must be sent ahead of the former.
+fn handle_with_mass_cancel_reports(
+    simulator: &mut VenueSimulator,
+    message: &[u8],
+) -> (Vec<Report>, Vec<MassCancelReport>) {
+    let (mut reports, mut mass_cancel_reports) = (Vec::new(), Vec::new());
+    simulator
+        .handle(1, message, |response| match AnyMessage::decode(response, 0) {
+            Ok(AnyMessage::ExecutionReport(report)) => reports.push((
+                report.cl_ord_id(),
+                report.exec_type(),
+                report.order_status(),
+                report.ord_status_req_id(),
+                report.mass_status_req_id(),
+                report.last_rpt_requested(),
+            )),
+            Ok(AnyMessage::OrderMassCancelReport(report)) => {
+                assert!(reports.is_empty(), "mass cancel report precedes the execution reports");
+                mass_cancel_reports.push((
+                    report.cl_ord_id(),
+                    report.mass_cancel_response(),
+                    report.mass_cancel_reject_reason(),
+                    report.total_affected_orders(),
+                ));
+            }
+            _ => {}
+        })
+        .unwrap();
+    (reports, mass_cancel_reports)
+}
+
+fn simulator_with_resting_orders(writer: &mut MessageWriter<Vec<u8>>) -> VenueSimulator {
+    let mut simulator = VenueSimulator::new();
+    for instrument_id in 1..=2 {
+        writer.reset();
+        let snapshot = writer
+            .price_snapshot(|snapshot| {
+                snapshot.comp_id(2);
+                snapshot.instrument_id(instrument_id);
+                let offer = PriceLevel { id: 1, price: 1.2, leaves_qty: 1_000_000.0, transact_time: 1 };
+                let _ = snapshot.encode_bids(std::iter::empty::<PriceLevel>());
+                let _ = snapshot.encode_offers([offer]);
+            })
+            .unwrap()
+            .to_vec();
+        handle(&mut simulator, &snapshot);
+    }
+    let orders = [(10, 1, Strategy::TWAP), (11, 2, Strategy::VWAP), (12, 1, Strategy::VWAP)];
+    for (cl_ord_id, instrument_id, strategy) in orders {
+        writer.reset();
+        let order = writer
+            .new_order_single(|order| {
+                order.cl_ord_id(cl_ord_id);
+                order.cl_ord_link_id(u64::MAX);
+                order.instrument_id(instrument_id);
+                order.target_comp_id(2);
+                order.source_comp_id(3);
+                order.side(OrderSide::BUY);
+                order.order_type(OrderType::LIMIT);
+                order.price(1.1);
+                order.order_qty(1_000.0);
+                order.time_in_force(TimeInForce::GTC);
+                order.transact_time(1);
+                order.effective_time(u64::MAX);
+                order.expire_time(u64::MAX);
+                order.target_strategy(Strategy::VENUE);
+                order.source_strategy(strategy);
+            })
+            .unwrap()
+            .to_vec();
+        handle(&mut simulator, &order);
+    }
+    simulator
+}
+
+fn order_status_request(writer: &mut MessageWriter<Vec<u8>>, cl_ord_id: u64, order_id: u64) -> Vec<u8> {
+    writer.reset();
+    writer
+        .order_status_request(|request| {
+            request.ord_status_req_id(7);
+            request.cl_ord_id(cl_ord_id);
+            request.order_id(order_id);
+            request.instrument_id(1);
+            request.target_comp_id(2);
+            request.source_comp_id(3);
+            request.target_strategy(Strategy::VENUE);
+            request.source_strategy(Strategy::TWAP);
+            request.transact_time(1);
+        })
+        .unwrap()
+        .to_vec()
+}
+
+fn order_mass_status_request(
+    writer: &mut MessageWriter<Vec<u8>>,
+    request_type: MassStatusReqType,
+    instrument_id: u32,
+    strategy: Strategy,
+) -> Vec<u8> {
+    writer.reset();
+    writer
+        .order_mass_status_request(|request| {
+            request.mass_status_req_id(8);
+            request.mass_status_req_type(request_type);
+            request.instrument_id(instrument_id);
+            request.strategy(strategy);
+            request.target_comp_id(2);
+            request.source_comp_id(3);
+            request.target_strategy(Strategy::VENUE);
+            request.source_strategy(Strategy::TWAP);
+            request.transact_time(1);
+        })
+        .unwrap()
+        .to_vec()
+}
+
+#[test]
+fn order_status_requests_report_the_order_by_cl_ord_id_or_order_id() {
+    let mut writer = MessageWriter::with_capacity(512);
+    let mut simulator = simulator_with_resting_orders(&mut writer);
+    let status = |cl_ord_id| (cl_ord_id, ExecType::ORDER_STATUS, OrderStatus::NEW, 7, u64::MAX, BooleanType::YES);
+
+    let by_cl_ord_id = handle(&mut simulator, &order_status_request(&mut writer, 11, u64::MAX));
+    assert_eq!(by_cl_ord_id, vec![status(11)]);
+    let by_order_id = handle(&mut simulator, &order_status_request(&mut writer, u64::MAX, 3));
+    assert_eq!(by_order_id, vec![status(12)]);
+    let unknown = handle(&mut simulator, &order_status_request(&mut writer, 99, u64::MAX));
+    assert_eq!(unknown, vec![(99, ExecType::ORDER_STATUS, OrderStatus::REJECTED, 7, u64::MAX, BooleanType::YES)]);
+}
+
+#[test]
+fn order_mass_status_requests_report_matching_working_orders_and_flag_the_last() {
+    let mut writer = MessageWriter::with_capacity(512);
+    let mut simulator = simulator_with_resting_orders(&mut writer);
+    let reported = |reports: Vec<Report>| -> Vec<(u64, BooleanType)> {
+        assert!(reports.iter().all(|report| report.1 == ExecType::ORDER_STATUS && report.4 == 8));
+        reports.into_iter().map(|report| (report.0, report.5)).collect()
+    };
+
+    let request = order_mass_status_request(&mut writer, MassStatusReqType::ALL_ORDERS, u32::MAX, Strategy::NullVal);
+    let all = handle(&mut simulator, &request);
+    assert_eq!(reported(all), vec![(10, BooleanType::NO), (11, BooleanType::NO), (12, BooleanType::YES)]);
+    let request = order_mass_status_request(&mut writer, MassStatusReqType::INSTRUMENT, 1, Strategy::NullVal);
+    let instrument = handle(&mut simulator, &request);
+    assert_eq!(reported(instrument), vec![(10, BooleanType::NO), (12, BooleanType::YES)]);
+    let request = order_mass_status_request(&mut writer, MassStatusReqType::STRATEGY, u32::MAX, Strategy::VWAP);
+    let strategy = handle(&mut simulator, &request);
+    assert_eq!(reported(strategy), vec![(11, BooleanType::NO), (12, BooleanType::YES)]);
+
+    let request = order_mass_status_request(&mut writer, MassStatusReqType::INSTRUMENT, 3, Strategy::NullVal);
+    let none = handle(&mut simulator, &request);
+    assert_eq!(none, vec![(u64::MAX, ExecType::ORDER_STATUS, OrderStatus::REJECTED, u64::MAX, 8, BooleanType::YES)]);
+}
+
+fn order_mass_cancel_request(
+    writer: &mut MessageWriter<Vec<u8>>,
+    request_type: MassCancelRequestType,
+    instrument_id: u32,
+    side: OrderSide,
+) -> Vec<u8> {
+    writer.reset();
+    writer
+        .order_mass_cancel_request(|request| {
+            request.cl_ord_id(20);
+            request.mass_cancel_request_type(request_type);
+            request.instrument_id(instrument_id);
+            request.side(side);
+            request.strategy(Strategy::NullVal);
+            request.target_comp_id(2);
+            request.source_comp_id(3);
+            request.target_strategy(Strategy::VENUE);
+            request.source_strategy(Strategy::TWAP);
+            request.transact_time(1);
+        })
+        .unwrap()
+        .to_vec()
+}
+
+#[test]
+fn order_mass_cancel_requests_cancel_matching_working_orders_after_the_report() {
+    let mut writer = MessageWriter::with_capacity(512);
+    let mut simulator = simulator_with_resting_orders(&mut writer);
+
+    let request = order_mass_cancel_request(&mut writer, MassCancelRequestType::INSTRUMENT, 3, OrderSide::NullVal);
+    let (reports, mass_cancel) = handle_with_mass_cancel_reports(&mut simulator, &request);
+    let rejected = (20, MassCancelResponse::REJECTED, MassCancelRejectReason::UNKNOWN_INSTRUMENT, 0);
+    assert_eq!((reports, mass_cancel), (vec![], vec![rejected]));
+
+    let request = order_mass_cancel_request(&mut writer, MassCancelRequestType::INSTRUMENT, 1, OrderSide::NullVal);
+    let (reports, mass_cancel) = handle_with_mass_cancel_reports(&mut simulator, &request);
+    assert_eq!(mass_cancel, vec![(20, MassCancelResponse::ACCEPTED, MassCancelRejectReason::NullVal, 2)]);
+    let cancelled: Vec<_> = reports.iter().map(|report| (report.0, report.1, report.2)).collect();
+    assert_eq!(
+        cancelled,
+        vec![(10, ExecType::CANCELLED, OrderStatus::CANCELLED), (12, ExecType::CANCELLED, OrderStatus::CANCELLED)]
+    );
+    assert_eq!(simulator.working_orders(), 1);
+
+    let request =
+        order_mass_cancel_request(&mut writer, MassCancelRequestType::ALL_ORDERS, u32::MAX, OrderSide::NullVal);
+    let (reports, mass_cancel) = handle_with_mass_cancel_reports(&mut simulator, &request);
+    assert_eq!(mass_cancel, vec![(20, MassCancelResponse::ACCEPTED, MassCancelRejectReason::NullVal, 1)]);
+    assert_eq!(reports.iter().map(|report| report.0).collect::<Vec<_>>(), vec![11]);
+    assert_eq!(simulator.working_orders(), 0);
+}
+
+#[test]
+fn messages_that_fail_to_decode_are_answered_with_a_business_message_reject() {
+    let mut writer = MessageWriter::with_capacity(512);
+    let mut simulator = simulator_with_resting_orders(&mut writer);
+    let mut unsupported = order_status_request(&mut writer, 10, u64::MAX);
+    let mut header = MessageHeaderEncoder::<WriteBuf<'_>>::default().wrap(WriteBuf::new(&mut unsupported), 0);
+    header.template_id(999);
+
+    let mut rejects = Vec::new();
+    let result = simulator.handle(1, &unsupported, |response| {
+        if let Ok(AnyMessage::BusinessMessageReject(mut reject)) = AnyMessage::decode(response, 0) {
+            let text = String::from_utf8(reject.text().unwrap().to_vec()).unwrap();
+            rejects.push((reject.ref_template_id(), reject.business_reject_reason(), text));
+        }
+    });
+
+    assert_eq!(result.unwrap_err(), SbeErr::UnknownTemplateId(999));
+    assert_eq!(rejects, vec![(999, BusinessRejectReason::UNSUPPORTED_MESSAGE_TYPE, "unknown template id".to_string())]);
+}
+
+/// `ExecutionReport` or `OrderCancelReject` fields the matching tests check.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Response {
+    /// `(clOrdId, execType, orderStatus, (lastQty, lastPx) of a fill, leavesQty, cumQty)`
+    Report(u64, ExecType, OrderStatus, Option<(f64, f64)>, f64, f64),
+    /// `(clOrdId, ordRejReason)` of a `REJECTED` report
+    Rejected(u64, OrderRejectReason),
+    /// `(clOrdId, origClOrdId, orderStatus, cxlRejReason)`
+    CancelReject(u64, u64, OrderStatus, CancelRejectReason),
+}
+
+fn partial_fill(cl_ord_id: u64, fill: (f64, f64), leaves_qty: f64, cum_qty: f64) -> Response {
+    Response::Report(cl_ord_id, ExecType::PARTIAL_FILL, OrderStatus::PARTIALLY_FILLED, Some(fill), leaves_qty, cum_qty)
+}
+
+fn responses(simulator: &mut VenueSimulator, now: u64, message: &[u8]) -> Vec<Response> {
+    let mut responses = Vec::new();
+    simulator.handle(now, message, |response| collect_response(&mut responses, response)).unwrap();
+    responses
+}
+
+fn collect_response(responses: &mut Vec<Response>, response: &[u8]) {
+    responses.push(match AnyMessage::decode(response, 0).unwrap() {
+        AnyMessage::ExecutionReport(report) if report.exec_type() == ExecType::REJECTED => {
+            assert_eq!(report.order_status(), OrderStatus::REJECTED);
+            Response::Rejected(report.cl_ord_id(), report.order_rej_reason())
+        }
+        AnyMessage::ExecutionReport(report) => {
+            let fill = Some((report.last_qty(), report.last_price())).filter(|(last_qty, _)| !last_qty.is_nan());
+            let (cl_ord_id, exec_type, status) = (report.cl_ord_id(), report.exec_type(), report.order_status());
+            Response::Report(cl_ord_id, exec_type, status, fill, report.leaves_qty(), report.cum_qty())
+        }
+        AnyMessage::OrderCancelReject(reject) => Response::CancelReject(
+            reject.cl_ord_id(),
+            reject.orig_cl_ord_id(),
+            reject.order_status(),
+            reject.cancel_rej_reason(),
+        ),
+        other => panic!("unexpected {:?}", other),
+    });
+}
+
+/// Simulator whose instrument 1 book has bids `1_000 @ 1.0` and offers `1_000 @ 1.2, 2_000 @ 1.3`.
+fn simulator_with_book(writer: &mut MessageWriter<Vec<u8>>) -> VenueSimulator {
+    let mut simulator = VenueSimulator::new();
+    let snapshot = price_snapshot(writer, &[(1.0, 1_000.0)], &[(1.2, 1_000.0), (1.3, 2_000.0)]);
+    assert_eq!(responses(&mut simulator, 1, &snapshot), vec![]);
+    simulator
+}
+
+/// Snapshot of instrument 1 with `(price, quantity)` levels.
+fn price_snapshot(writer: &mut MessageWriter<Vec<u8>>, bids: &[(f64, f64)], offers: &[(f64, f64)]) -> Vec<u8> {
+    let level = |&(price, leaves_qty): &(f64, f64)| PriceLevel { id: 1, price, leaves_qty, transact_time: 1 };
+    writer.reset();
+    writer
+        .price_snapshot(|snapshot| {
+            snapshot.comp_id(2);
+            snapshot.instrument_id(1);
+            let _ = snapshot.encode_bids(bids.iter().map(level));
+            let _ = snapshot.encode_offers(offers.iter().map(level));
+        })
+        .unwrap()
+        .to_vec()
+}
+
+/// GTC limit order to buy `1_500 @ 1.25` of instrument 1, adjusted by `amend`.
+fn limit_order<F>(writer: &mut MessageWriter<Vec<u8>>, cl_ord_id: u64, amend: F) -> Vec<u8>
+where
+    F: FnOnce(&mut NewOrderSingleEncoder<'_>),
+{
+    writer.reset();
+    writer
+        .new_order_single(|order| {
+            order.cl_ord_id(cl_ord_id);
+            order.cl_ord_link_id(u64::MAX);
+            order.instrument_id(1);
+            order.target_comp_id(2);
+            order.source_comp_id(3);
+            order.side(OrderSide::BUY);
+            order.order_type(OrderType::LIMIT);
+            order.price(1.25);
+            order.order_qty(1_500.0);
+            order.time_in_force(TimeInForce::GTC);
+            order.transact_time(1);
+            order.effective_time(u64::MAX);
+            order.expire_time(u64::MAX);
+            order.target_strategy(Strategy::VENUE);
+            order.source_strategy(Strategy::TWAP);
+            amend(order);
+        })
+        .unwrap()
+        .to_vec()
+}
+
+fn order_cancel_request(writer: &mut MessageWriter<Vec<u8>>, cl_ord_id: u64, orig_cl_ord_id: u64) -> Vec<u8> {
+    order_cancel_request_from(writer, 3, cl_ord_id, orig_cl_ord_id)
+}
+
+fn order_cancel_request_from(
+    writer: &mut MessageWriter<Vec<u8>>,
+    source_comp_id: u32,
+    cl_ord_id: u64,
+    orig_cl_ord_id: u64,
+) -> Vec<u8> {
+    writer.reset();
+    writer
+        .order_cancel_request(|request| {
+            request.orig_cl_ord_id(orig_cl_ord_id);
+            request.order_id(u64::MAX);
+            request.cl_ord_id(cl_ord_id);
+            request.instrument_id(1);
+            request.target_comp_id(2);
+            request.source_comp_id(source_comp_id);
+            request.target_strategy(Strategy::VENUE);
+            request.source_strategy(Strategy::TWAP);
+            request.side(OrderSide::BUY);
+            request.price(1.25);
+            request.transact_time(1);
+        })
+        .unwrap()
+        .to_vec()
+}
+
+#[test]
+fn limit_orders_fill_against_the_book_and_rest_until_the_next_snapshot_fills_them() {
+    let mut writer = MessageWriter::with_capacity(512);
+    let mut simulator = simulator_with_book(&mut writer);
+
+    let order = limit_order(&mut writer, 10, |_| {});
+    assert_eq!(
+        responses(&mut simulator, 2, &order),
+        vec![
+            Response::Report(10, ExecType::NEW, OrderStatus::NEW, None, 1_500.0, 0.0),
+            partial_fill(10, (1_000.0, 1.2), 500.0, 1_000.0),
+        ]
+    );
+    assert_eq!(simulator.working_orders(), 1);
+
+    let snapshot = price_snapshot(&mut writer, &[], &[(1.25, 300.0), (1.26, 1_000.0)]);
+    assert_eq!(
+        responses(&mut simulator, 3, &snapshot),
+        vec![partial_fill(10, (300.0, 1.25), 200.0, 1_300.0)]
+    );
+    let snapshot = price_snapshot(&mut writer, &[], &[(1.24, 1_000.0)]);
+    assert_eq!(
+        responses(&mut simulator, 4, &snapshot),
+        vec![Response::Report(10, ExecType::FILL, OrderStatus::FILLED, Some((200.0, 1.24)), 0.0, 1_500.0)]
+    );
+    assert_eq!(simulator.working_orders(), 0);
+}
+
+#[test]
+fn ioc_orders_cancel_the_unfilled_remainder() {
+    let mut writer = MessageWriter::with_capacity(512);
+    let mut simulator = simulator_with_book(&mut writer);
+
+    let order = limit_order(&mut writer, 10, |order| order.time_in_force(TimeInForce::IOC));
+    assert_eq!(
+        responses(&mut simulator, 2, &order),
+        vec![
+            Response::Report(10, ExecType::NEW, OrderStatus::NEW, None, 1_500.0, 0.0),
+            partial_fill(10, (1_000.0, 1.2), 500.0, 1_000.0),
+            Response::Report(10, ExecType::CANCELLED, OrderStatus::CANCELLED, None, 0.0, 1_000.0),
+        ]
+    );
+    assert_eq!(simulator.working_orders(), 0);
+}
+
+#[test]
+fn fok_orders_are_killed_without_trading_unless_they_fill_completely() {
+    let mut writer = MessageWriter::with_capacity(512);
+    let mut simulator = simulator_with_book(&mut writer);
+
+    let order = limit_order(&mut writer, 10, |order| order.time_in_force(TimeInForce::FOK));
+    assert_eq!(
+        responses(&mut simulator, 2, &order),
+        vec![
+            Response::Report(10, ExecType::NEW, OrderStatus::NEW, None, 1_500.0, 0.0),
+            Response::Report(10, ExecType::CANCELLED, OrderStatus::CANCELLED, None, 0.0, 0.0),
+        ]
+    );
+    // The killed order left the book untouched for an order that can fill completely.
+    let order = limit_order(&mut writer, 11, |order| {
+        order.time_in_force(TimeInForce::FOK);
+        order.price(1.3);
+    });
+    assert_eq!(
+        responses(&mut simulator, 3, &order),
+        vec![
+            Response::Report(11, ExecType::NEW, OrderStatus::NEW, None, 1_500.0, 0.0),
+            partial_fill(11, (1_000.0, 1.2), 500.0, 1_000.0),
+            Response::Report(11, ExecType::FILL, OrderStatus::FILLED, Some((500.0, 1.3)), 0.0, 1_500.0),
+        ]
+    );
+    assert_eq!(simulator.working_orders(), 0);
+}
+
+#[test]
+fn gtd_orders_expire_at_their_expire_time_and_are_rejected_once_it_has_passed() {
+    let mut writer = MessageWriter::with_capacity(512);
+    let mut simulator = simulator_with_book(&mut writer);
+    let gtd = |order: &mut NewOrderSingleEncoder<'_>| {
+        order.side(OrderSide::SELL);
+        order.price(1.1);
+        order.time_in_force(TimeInForce::GTD);
+        order.expire_time(100);
+    };
+
+    let order = limit_order(&mut writer, 10, gtd);
+    assert_eq!(
+        responses(&mut simulator, 2, &order),
+        vec![Response::Report(10, ExecType::NEW, OrderStatus::NEW, None, 1_500.0, 0.0)]
+    );
+    let mut expired = Vec::new();
+    simulator.expire(99, |response| collect_response(&mut expired, response));
+    assert_eq!(expired, vec![]);
+    simulator.expire(100, |response| collect_response(&mut expired, response));
+    assert_eq!(expired, vec![Response::Report(10, ExecType::EXPIRED, OrderStatus::EXPIRED, None, 0.0, 0.0)]);
+    assert_eq!(simulator.working_orders(), 0);
+
+    let stale = limit_order(&mut writer, 11, gtd);
+    assert_eq!(responses(&mut simulator, 100, &stale), vec![Response::Rejected(11, OrderRejectReason::STALE_ORDER)]);
+}
+
+#[test]
+fn duplicate_cl_ord_ids_and_instruments_without_a_book_are_rejected() {
+    let mut writer = MessageWriter::with_capacity(512);
+    let mut simulator = simulator_with_book(&mut writer);
+
+    let order = limit_order(&mut writer, 10, |order| order.price(1.1));
+    assert_eq!(
+        responses(&mut simulator, 2, &order),
+        vec![Response::Report(10, ExecType::NEW, OrderStatus::NEW, None, 1_500.0, 0.0)]
+    );
+    assert_eq!(responses(&mut simulator, 3, &order), vec![Response::Rejected(10, OrderRejectReason::DUPLICATE_ORDER)]);
+    let unknown = limit_order(&mut writer, 11, |order| order.instrument_id(9));
+    assert_eq!(responses(&mut simulator, 4, &unknown), vec![Response::Rejected(11, OrderRejectReason::UNKNOWN_SYMBOL)]);
+    assert_eq!(simulator.working_orders(), 1);
+}
+
+#[test]
+fn cancels_are_acknowledged_for_working_orders_and_rejected_otherwise() {
+    let mut writer = MessageWriter::with_capacity(512);
+    let mut simulator = simulator_with_book(&mut writer);
+    let order = limit_order(&mut writer, 10, |order| order.price(1.1));
+    responses(&mut simulator, 2, &order);
+
+    let cancel = order_cancel_request(&mut writer, 11, 10);
+    assert_eq!(
+        responses(&mut simulator, 3, &cancel),
+        vec![Response::Report(11, ExecType::CANCELLED, OrderStatus::CANCELLED, None, 0.0, 0.0)]
+    );
+    assert_eq!(simulator.working_orders(), 0);
+    let too_late = order_cancel_request(&mut writer, 12, 10);
+    assert_eq!(
+        responses(&mut simulator, 4, &too_late),
+        vec![Response::CancelReject(12, 10, OrderStatus::CANCELLED, CancelRejectReason::TOO_LATE_TO_CANCEL)]
+    );
+    let unknown = order_cancel_request(&mut writer, 13, 99);
+    assert_eq!(
+        responses(&mut simulator, 5, &unknown),
+        vec![Response::CancelReject(13, 99, OrderStatus::REJECTED, CancelRejectReason::UNKNOWN_ORDER)]
+    );
+}
+
+#[test]
+fn cancels_from_another_session_are_rejected_as_unknown_orders() {
+    let mut writer = MessageWriter::with_capacity(512);
+    let mut simulator = simulator_with_book(&mut writer);
+    let order = limit_order(&mut writer, 10, |order| order.price(1.1));
+    responses(&mut simulator, 2, &order);
+
+    let foreign = order_cancel_request_from(&mut writer, 4, 11, 10);
+    assert_eq!(
+        responses(&mut simulator, 3, &foreign),
+        vec![Response::CancelReject(11, 10, OrderStatus::REJECTED, CancelRejectReason::UNKNOWN_ORDER)]
+    );
+    writer.reset();
+    let foreign_mass_cancel = writer
+        .order_mass_cancel_request(|request| {
+            request.cl_ord_id(12);
+            request.mass_cancel_request_type(MassCancelRequestType::ALL_ORDERS);
+            request.instrument_id(u32::MAX);
+            request.side(OrderSide::NullVal);
+            request.strategy(Strategy::NullVal);
+            request.target_comp_id(2);
+            request.source_comp_id(4);
+            request.target_strategy(Strategy::VENUE);
+            request.source_strategy(Strategy::TWAP);
+            request.transact_time(1);
+        })
+        .unwrap()
+        .to_vec();
+    let (reports, mass_cancel) = handle_with_mass_cancel_reports(&mut simulator, &foreign_mass_cancel);
+    let accepted_none = (12, MassCancelResponse::ACCEPTED, MassCancelRejectReason::NullVal, 0);
+    assert_eq!((reports, mass_cancel), (vec![], vec![accepted_none]));
+    assert_eq!(simulator.working_orders(), 1);
+
+    let own = order_cancel_request(&mut writer, 13, 10);
+    assert_eq!(
+        responses(&mut simulator, 4, &own),
+        vec![Response::Report(13, ExecType::CANCELLED, OrderStatus::CANCELLED, None, 0.0, 0.0)]
+    );
+}
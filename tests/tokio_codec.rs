@@ -0,0 +1,183 @@
+use bytes::BytesMut;
+use com_dev_4fx_sor_codecs_sbe::tokio_codec::{Encode, SbeCodec, SbeFrame};
+use com_dev_4fx_sor_codecs_sbe::*;
+use futures_util::{SinkExt, StreamExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio_util::codec::{Decoder, Encoder, Framed};
+
+fn new_order_single<'w>(writer: &'w mut MessageWriter<&mut [u8]>, cl_ord_id: u64) -> SbeResult<&'w [u8]> {
+    writer.new_order_single(|order| {
+        order.cl_ord_id(cl_ord_id);
+        order.cl_ord_link_id(u64::MAX);
+        order.instrument_id(1);
+        order.target_comp_id(2);
+        order.source_comp_id(3);
+        order.side(OrderSide::BUY);
+        order.order_type(OrderType::LIMIT);
+        order.price(1.1);
+        order.order_qty(1_000.0);
+        order.time_in_force(TimeInForce::GTC);
+        order.transact_time(1);
+        order.effective_time(u64::MAX);
+        order.expire_time(u64::MAX);
+        order.target_strategy(Strategy::VENUE);
+        order.source_strategy(Strategy::TWAP);
+    })
+}
+
+fn execution_report<'w>(
+    writer: &'w mut MessageWriter<&mut [u8]>,
+    cl_ord_id: u64,
+    text: &[u8],
+) -> SbeResult<&'w [u8]> {
+    writer.execution_report(|report| {
+        report.cl_ord_id(cl_ord_id);
+        report.exec_type(ExecType::NEW);
+        report.order_status(OrderStatus::NEW);
+        let _ = report.text(text);
+    })
+}
+
+fn price_snapshot<'w>(writer: &'w mut MessageWriter<&mut [u8]>) -> SbeResult<&'w [u8]> {
+    writer.price_snapshot(|snapshot| {
+        snapshot.comp_id(2);
+        snapshot.instrument_id(1);
+        let level = |id, price| PriceLevel { id, price, leaves_qty: 1_000_000.0, transact_time: 1 };
+        let _ = snapshot.encode_bids([level(1, 1.1), level(2, 1.0)]);
+        let _ = snapshot.encode_offers([level(3, 1.2)]);
+    })
+}
+
+#[tokio::test]
+async fn framed_tcp_streams_carry_typed_messages_both_ways() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    let server = tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut framed = Framed::new(stream, SbeCodec::new());
+        let mut received = Vec::new();
+        while let Some(frame) = framed.next().await {
+            let frame = frame.unwrap();
+            let answer = match frame.message().unwrap() {
+                AnyMessage::NewOrderSingle(order) => Some(order.cl_ord_id()),
+                _ => None,
+            };
+            received.push(frame.message().unwrap().template_id());
+            if let Some(cl_ord_id) = answer {
+                let report = Encode::new(move |writer| execution_report(writer, cl_ord_id, b"accepted"));
+                framed.send(report).await.unwrap();
+            }
+        }
+        received
+    });
+
+    let mut framed = Framed::new(TcpStream::connect(address).await.unwrap(), SbeCodec::new());
+    framed.send(Encode::new(|writer| new_order_single(writer, 10))).await.unwrap();
+    framed.send(Encode::new(price_snapshot)).await.unwrap();
+    framed.send(Encode::new(|writer| new_order_single(writer, 11))).await.unwrap();
+
+    let mut reports = Vec::new();
+    for _ in 0..2 {
+        let frame = framed.next().await.unwrap().unwrap();
+        match frame.message().unwrap() {
+            AnyMessage::ExecutionReport(mut report) => {
+                reports.push((report.cl_ord_id(), report.text().unwrap().to_vec()))
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+    assert_eq!(reports, vec![(10, b"accepted".to_vec()), (11, b"accepted".to_vec())]);
+
+    drop(framed);
+    let received = server.await.unwrap();
+    assert_eq!(
+        received,
+        vec![
+            new_order_single_codec::SBE_TEMPLATE_ID,
+            price_snapshot_codec::SBE_TEMPLATE_ID,
+            new_order_single_codec::SBE_TEMPLATE_ID
+        ]
+    );
+}
+
+#[test]
+fn decoder_waits_for_the_groups_and_var_data_of_a_partial_message() {
+    let mut buffer = vec![0; 512];
+    let mut writer = MessageWriter::new(&mut buffer[..]);
+    let snapshot_length = price_snapshot(&mut writer).unwrap().len();
+    let report_length = execution_report(&mut writer, 12, b"partially received").unwrap().len();
+    let messages = &buffer[..snapshot_length + report_length];
+
+    let mut codec = SbeCodec::new();
+    let mut src = BytesMut::new();
+    let mut frames = Vec::new();
+    for chunk in messages.chunks(7) {
+        src.extend_from_slice(chunk);
+        while let Some(frame) = codec.decode(&mut src).unwrap() {
+            frames.push(frame);
+        }
+    }
+
+    assert!(src.is_empty());
+    let lengths: Vec<_> = frames.iter().map(|frame| frame.bytes().len()).collect();
+    assert_eq!(lengths, vec![snapshot_length, report_length]);
+    let (bids, offers) = match frames[0].message().unwrap() {
+        AnyMessage::PriceSnapshot(mut snapshot) => {
+            (snapshot.bids().unwrap().count(), snapshot.offers().unwrap().count())
+        }
+        other => panic!("unexpected {:?}", other),
+    };
+    assert_eq!((bids, offers), (2, 1));
+    assert_eq!(frames[1], SbeFrame::new(messages[snapshot_length..].to_vec().into()));
+}
+
+#[test]
+fn decoder_rejects_oversized_and_undecodable_messages() {
+    let mut buffer = vec![0; 512];
+    let length = price_snapshot(&mut MessageWriter::new(&mut buffer[..])).unwrap().len();
+
+    let mut src = BytesMut::from(&buffer[..length]);
+    let err = SbeCodec::new().max_frame_length(length - 1).decode(&mut src).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+
+    let mut src = BytesMut::from(&buffer[..length]);
+    src[2..4].copy_from_slice(&u16::MAX.to_le_bytes());
+    let err = SbeCodec::new().decode(&mut src).unwrap_err();
+    let sbe_err = err.get_ref().and_then(|err| err.downcast_ref::<SbeErr>());
+    assert_eq!(sbe_err, Some(&SbeErr::UnknownTemplateId(u16::MAX)));
+}
+
+#[test]
+fn encoder_appends_only_the_encoded_messages() {
+    let mut buffer = vec![0; 512];
+    let mut writer = MessageWriter::new(&mut buffer[..]);
+    let order_length = new_order_single(&mut writer, 10).unwrap().len();
+    let snapshot_length = price_snapshot(&mut writer).unwrap().len();
+    let messages = &buffer[..order_length + snapshot_length];
+
+    let mut codec = SbeCodec::new();
+    let mut dst = BytesMut::new();
+    codec.encode(Encode::new(|writer| new_order_single(writer, 10)), &mut dst).unwrap();
+    codec.encode(Encode::new(price_snapshot), &mut dst).unwrap();
+    assert_eq!(&dst[..], messages);
+    assert!(dst.capacity() < tokio_codec::DEFAULT_MAX_FRAME_LENGTH);
+
+    let mut codec = SbeCodec::new().max_frame_length(order_length - 1);
+    let err = codec.encode(Encode::new(|writer| new_order_single(writer, 11)), &mut dst).unwrap_err();
+    let sbe_err = err.get_ref().and_then(|err| err.downcast_ref::<SbeErr>());
+    assert_eq!(sbe_err, Some(&SbeErr::BufferTooShort));
+    assert_eq!(&dst[..], messages);
+}
+
+#[test]
+fn decoder_rejects_a_block_length_below_the_minimum_without_buffering() {
+    let mut buffer = vec![0; 512];
+    new_order_single(&mut MessageWriter::new(&mut buffer[..]), 10).unwrap();
+    buffer[..2].copy_from_slice(&1_u16.to_le_bytes());
+
+    // Only the header and one byte of the body have arrived.
+    let mut src = BytesMut::from(&buffer[..message_header_codec::ENCODED_LENGTH + 1]);
+    let err = SbeCodec::new().decode(&mut src).unwrap_err();
+    let sbe_err = err.get_ref().and_then(|err| err.downcast_ref::<SbeErr>());
+    assert_eq!(sbe_err, Some(&SbeErr::BlockLengthTooShort));
+}
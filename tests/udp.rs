@@ -0,0 +1,257 @@
+use com_dev_4fx_sor_codecs_sbe::transport::udp::{Packet, UdpPublisher, UdpSubscriber};
+use com_dev_4fx_sor_codecs_sbe::*;
+
+use std::io;
+use std::net::{Ipv4Addr, SocketAddrV4, UdpSocket};
+use std::time::Duration;
+
+const LOOPBACK: Ipv4Addr = Ipv4Addr::LOCALHOST;
+
+/// Subscriber on loopback multicast and a publisher to it; each test uses its own group.
+fn multicast(group: Ipv4Addr) -> (UdpPublisher, UdpSubscriber) {
+    let subscriber = UdpSubscriber::multicast(SocketAddrV4::new(group, 0), LOOPBACK).unwrap();
+    subscriber.socket().set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+    let port = subscriber.socket().local_addr().unwrap().port();
+    let publisher = UdpPublisher::multicast(SocketAddrV4::new(group, port), LOOPBACK).unwrap();
+    (publisher, subscriber)
+}
+
+fn level_increment(id: u32, update_action: UpdateAction) -> PriceLevelIncrement {
+    let price_level = PriceLevel { id, price: 1.1, leaves_qty: 1_000_000.0, transact_time: 1 };
+    PriceLevelIncrement { price_level, update_action }
+}
+
+/// `(templateId, instrumentId)` of every message of the next datagram.
+fn receive(subscriber: &mut UdpSubscriber) -> (Packet, Vec<(u16, u32)>) {
+    let mut messages = Vec::new();
+    let packet = subscriber
+        .receive(|message| {
+            let instrument_id = match &message {
+                AnyMessage::PriceIncrement(increment) => increment.instrument_id(),
+                AnyMessage::PriceSnapshot(snapshot) => snapshot.instrument_id(),
+                AnyMessage::LastMarketTrade(trade) => trade.instrument_id(),
+                other => panic!("unexpected {:?}", other),
+            };
+            messages.push((message.template_id(), instrument_id));
+        })
+        .unwrap();
+    (packet, messages)
+}
+
+#[test]
+fn market_data_is_batched_into_one_datagram_until_flushed() {
+    let (mut publisher, mut subscriber) = multicast(Ipv4Addr::new(239, 255, 70, 1));
+    for instrument_id in 1..=2 {
+        publisher
+            .price_increment(|increment| {
+                increment.comp_id(2);
+                increment.instrument_id(instrument_id);
+                let _ = increment.encode_bids([level_increment(1, UpdateAction::NEW)]);
+                let _ = increment.encode_offers([level_increment(2, UpdateAction::DELETE)]);
+            })
+            .unwrap();
+    }
+    publisher
+        .last_market_trade(|trade| {
+            trade.comp_id(2);
+            trade.instrument_id(3);
+            trade.quantity(1_000_000.0);
+            trade.price(1.1025);
+            trade.transact_time(1);
+        })
+        .unwrap();
+    assert_eq!((publisher.sequence_number(), publisher.pending_messages()), (0, 3));
+    publisher.flush().unwrap();
+    assert_eq!((publisher.sequence_number(), publisher.pending_messages()), (1, 0));
+
+    let (packet, messages) = receive(&mut subscriber);
+    assert_eq!((packet.sequence_number, packet.message_count, packet.gap), (1, 3, 0));
+    assert!(packet.sending_time > 0);
+    let increment = price_increment_codec::SBE_TEMPLATE_ID;
+    assert_eq!(messages, vec![(increment, 1), (increment, 2), (last_market_trade_codec::SBE_TEMPLATE_ID, 3)]);
+}
+
+#[test]
+fn messages_that_do_not_fit_start_the_next_datagram() {
+    let (publisher, mut subscriber) = multicast(Ipv4Addr::new(239, 255, 70, 2));
+    let mut publisher = publisher.max_datagram_length(256);
+    for instrument_id in 0..10 {
+        publisher
+            .price_snapshot(|snapshot| {
+                snapshot.comp_id(2);
+                snapshot.instrument_id(instrument_id);
+                let level = PriceLevel { id: 1, price: 1.1, leaves_qty: 1_000_000.0, transact_time: 1 };
+                let _ = snapshot.encode_bids([level, level]);
+                let _ = snapshot.encode_offers([level]);
+            })
+            .unwrap();
+    }
+    publisher.flush().unwrap();
+
+    let mut instrument_ids = Vec::new();
+    for sequence_number in 1..=publisher.sequence_number() {
+        let (packet, messages) = receive(&mut subscriber);
+        assert_eq!((packet.sequence_number, packet.gap), (sequence_number, 0));
+        assert_eq!(packet.message_count as usize, messages.len());
+        instrument_ids.extend(messages.into_iter().map(|(_, instrument_id)| instrument_id));
+    }
+    assert!(publisher.sequence_number() > 1);
+    assert_eq!(instrument_ids, (0..10).collect::<Vec<_>>());
+}
+
+/// Datagram numbered `sequence_number`, sent at `sending_time`, holding a trade of `instrument_id`.
+fn trade_datagram(sequence_number: u64, sending_time: u64, instrument_id: u32) -> Vec<u8> {
+    let mut datagram = vec![0_u8; 256];
+    let body_offset = packet_header_codec::ENCODED_LENGTH;
+    let length = MessageWriter::new(&mut datagram[body_offset..])
+        .last_market_trade(|trade| {
+            trade.comp_id(2);
+            trade.instrument_id(instrument_id);
+            trade.quantity(1_000_000.0);
+            trade.price(1.1025);
+            trade.transact_time(1);
+        })
+        .unwrap()
+        .len();
+    let mut header = PacketHeaderEncoder::<WriteBuf<'_>>::default().wrap(WriteBuf::new(&mut datagram), 0);
+    header.sequence_number(sequence_number);
+    header.sending_time(sending_time);
+    header.packet_length((body_offset + length) as u32);
+    header.message_count(1);
+    datagram.truncate(body_offset + length);
+    datagram
+}
+
+#[test]
+fn subscribers_report_sequence_number_gaps() {
+    let group = Ipv4Addr::new(239, 255, 70, 3);
+    let (mut publisher, mut subscriber) = multicast(group);
+    let port = subscriber.socket().local_addr().unwrap().port();
+
+    publisher
+        .last_market_trade(|trade| {
+            trade.comp_id(2);
+            trade.instrument_id(1);
+            trade.quantity(1_000_000.0);
+            trade.price(1.1025);
+            trade.transact_time(1);
+        })
+        .unwrap();
+    publisher.flush().unwrap();
+    let mut datagram = [0_u8; packet_header_codec::ENCODED_LENGTH];
+    let mut header = PacketHeaderEncoder::<WriteBuf<'_>>::default().wrap(WriteBuf::new(&mut datagram), 0);
+    header.sequence_number(5);
+    header.sending_time(1);
+    header.packet_length(packet_header_codec::ENCODED_LENGTH as u32);
+    header.message_count(0);
+    UdpSocket::bind((LOOPBACK, 0)).unwrap().send_to(&datagram, (group, port)).unwrap();
+
+    assert_eq!(receive(&mut subscriber).0.gap, 0);
+    let (packet, messages) = receive(&mut subscriber);
+    assert_eq!((packet.sequence_number, packet.gap), (5, 3));
+    assert!(messages.is_empty());
+}
+
+#[test]
+fn stale_datagrams_are_reported_without_moving_the_sequence_number() {
+    let group = Ipv4Addr::new(239, 255, 70, 4);
+    let (_, mut subscriber) = multicast(group);
+    let port = subscriber.socket().local_addr().unwrap().port();
+    let sender = UdpSocket::bind((LOOPBACK, 0)).unwrap();
+    // Duplicates are sent at the same time as the original, late datagrams before the last one.
+    for (sequence_number, instrument_id) in [(1, 1), (4, 4), (4, 5), (3, 3), (5, 6)] {
+        sender.send_to(&trade_datagram(sequence_number, sequence_number, instrument_id), (group, port)).unwrap();
+    }
+
+    let received: Vec<_> = (0..5)
+        .map(|_| {
+            let (packet, messages) = receive(&mut subscriber);
+            let instrument_ids: Vec<_> = messages.into_iter().map(|(_, instrument_id)| instrument_id).collect();
+            (packet.sequence_number, packet.gap, packet.stale, instrument_ids)
+        })
+        .collect();
+    assert_eq!(
+        received,
+        vec![
+            (1, 0, false, vec![1]),
+            (4, 2, false, vec![4]),
+            (4, 0, true, vec![]),
+            (3, 0, true, vec![]),
+            (5, 0, false, vec![6]),
+        ]
+    );
+}
+
+#[test]
+fn messages_are_dropped_with_the_batch_when_sending_it_fails() {
+    let socket = UdpSocket::bind((LOOPBACK, 0)).unwrap();
+    // Sending to port 0 fails.
+    let mut publisher = UdpPublisher::new(socket, (LOOPBACK, 0).into()).max_datagram_length(128);
+    let mut appended = 0;
+    let err = loop {
+        let trade = publisher.last_market_trade(|trade| {
+            trade.comp_id(2);
+            trade.instrument_id(appended);
+            trade.quantity(1_000_000.0);
+            trade.price(1.1025);
+            trade.transact_time(1);
+        });
+        match trade {
+            Ok(()) => appended += 1,
+            Err(err) => break err,
+        }
+    };
+    assert!(appended > 1);
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    assert_eq!((publisher.sequence_number(), publisher.pending_messages()), (0, 0));
+    publisher.flush().unwrap();
+}
+
+#[test]
+fn subscribers_follow_a_restarted_publisher() {
+    let group = Ipv4Addr::new(239, 255, 70, 5);
+    let (_, mut subscriber) = multicast(group);
+    let port = subscriber.socket().local_addr().unwrap().port();
+    let sender = UdpSocket::bind((LOOPBACK, 0)).unwrap();
+    // The publisher restarts after datagram 3; a duplicate of its new first datagram follows.
+    for (sequence_number, sending_time, instrument_id) in [(2, 20, 2), (3, 30, 3), (1, 40, 4), (2, 50, 5), (1, 40, 6)] {
+        sender.send_to(&trade_datagram(sequence_number, sending_time, instrument_id), (group, port)).unwrap();
+    }
+
+    let received: Vec<_> = (0..5)
+        .map(|_| {
+            let (packet, messages) = receive(&mut subscriber);
+            let instrument_ids: Vec<_> = messages.into_iter().map(|(_, instrument_id)| instrument_id).collect();
+            (packet.sequence_number, packet.gap, packet.stale, packet.restarted, instrument_ids)
+        })
+        .collect();
+    assert_eq!(
+        received,
+        vec![
+            (2, 0, false, false, vec![2]),
+            (3, 0, false, false, vec![3]),
+            (1, 0, false, true, vec![4]),
+            (2, 0, false, false, vec![5]),
+            (1, 0, true, false, vec![]),
+        ]
+    );
+}
+
+#[test]
+fn datagrams_not_matching_their_packet_length_are_rejected() {
+    let group = Ipv4Addr::new(239, 255, 70, 6);
+    let (_, mut subscriber) = multicast(group);
+    let port = subscriber.socket().local_addr().unwrap().port();
+    let sender = UdpSocket::bind((LOOPBACK, 0)).unwrap();
+    let datagram = trade_datagram(1, 1, 1);
+    let mut oversized = datagram.clone();
+    oversized.extend_from_slice(&[0; 8]);
+    sender.send_to(&oversized, (group, port)).unwrap();
+    sender.send_to(&datagram[..datagram.len() - 1], (group, port)).unwrap();
+
+    for expected in [SbeErr::TrailingBytes, SbeErr::BufferTooShort] {
+        let err = subscriber.receive(|_| panic!("malformed datagram handed out")).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(err.get_ref().and_then(|err| err.downcast_ref::<SbeErr>()), Some(&expected));
+    }
+}